@@ -0,0 +1,304 @@
+//! Parsing of `#[log_id(...)]` attributes, and the rules to resolve identifiers and levels of derived [`LogId`]s.
+//!
+//! Shared by `logid-derive` and `logid-catalog`, so catalogs list the same [`LogId`]s the derive macros create.
+//!
+//! [`LogId`]: logid_core::log_id::LogId
+
+use logid_core::log_id::LogLevel;
+use syn::{punctuated::Punctuated, spanned::Spanned, Attribute, LitStr, Token};
+
+/// Options set via `#[log_id(...)]` attributes on a type or variant.
+#[derive(Default)]
+pub struct LogIdAttrs {
+    /// Log level set with `level = "<level>"`.
+    pub level: Option<LogLevel>,
+    /// Identifier set with `name = "<identifier>"`, or its alias `id = "<identifier>"`.
+    pub name: Option<LitStr>,
+    /// Stable code set with `code = "<code>"`.
+    pub code: Option<LitStr>,
+    /// Addon kind fields are captured as, set with `capture = "<kind>"`.
+    pub capture: Option<CaptureMode>,
+    /// Set with `skip` to exclude a field from being captured.
+    pub skip: Option<proc_macro2::Span>,
+    /// Value of a field set with `default = "<expr>"` when converting from a log-id.
+    pub default: Option<syn::Expr>,
+    /// Long-form explanation set with `explain = "<text>"`.
+    pub explain: Option<LitStr>,
+    /// File relative to the package root containing the long-form explanation, set with `explain_file = "<path>"`.
+    pub explain_file: Option<LitStr>,
+    /// URL to documentation set with `help_url = "<url>"`.
+    pub help_url: Option<LitStr>,
+}
+
+/// Addon kind fields are captured as.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// One info per field in the form `<field>: <value:?>`.
+    Infos,
+    /// One JSON object payload with one member per field.
+    Payload,
+    /// Fields are not captured.
+    Off,
+}
+
+impl LogIdAttrs {
+    /// Parses all `#[log_id(...)]` attributes of the given list.
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut log_id_attrs = LogIdAttrs::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("log_id")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("level") {
+                    let level: LitStr = meta.value()?.parse()?;
+                    log_id_attrs.level = Some(parse_level(&level)?);
+                    Ok(())
+                } else if meta.path.is_ident("name") || meta.path.is_ident("id") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    if name.value().is_empty() {
+                        return Err(syn::Error::new(name.span(), "name must not be empty"));
+                    }
+                    if log_id_attrs.name.is_some() {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            "identifier is already set, only one of `name` and `id` may be set",
+                        ));
+                    }
+                    log_id_attrs.name = Some(name);
+                    Ok(())
+                } else if meta.path.is_ident("code") {
+                    let code: LitStr = meta.value()?.parse()?;
+                    let code_value = code.value();
+                    if code_value.is_empty()
+                        || !code_value
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                    {
+                        return Err(syn::Error::new(
+                            code.span(),
+                            "code must only contain ASCII alphanumeric characters, `_`, or `-`",
+                        ));
+                    }
+                    log_id_attrs.code = Some(code);
+                    Ok(())
+                } else if meta.path.is_ident("capture") {
+                    let capture: LitStr = meta.value()?.parse()?;
+                    log_id_attrs.capture = Some(match capture.value().as_str() {
+                        "infos" => CaptureMode::Infos,
+                        "payload" => CaptureMode::Payload,
+                        "off" => CaptureMode::Off,
+                        _ => return Err(syn::Error::new(
+                            capture.span(),
+                            "unknown capture kind, expected one of `infos`, `payload`, or `off`",
+                        )),
+                    });
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    log_id_attrs.skip = Some(meta.path.span());
+                    Ok(())
+                } else if meta.path.is_ident("explain") {
+                    log_id_attrs.explain = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("explain_file") {
+                    log_id_attrs.explain_file = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("help_url") {
+                    log_id_attrs.help_url = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    let default: LitStr = meta.value()?.parse()?;
+                    log_id_attrs.default = Some(default.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `log_id` attribute"))
+                }
+            })?;
+        }
+
+        Ok(log_id_attrs)
+    }
+
+    /// Returns an error if `skip` or `default` is set, because they are only allowed on fields.
+    pub fn deny_field_only(&self) -> syn::Result<()> {
+        if let Some(span) = self.skip {
+            return Err(syn::Error::new(span, "`skip` is only allowed on fields"));
+        }
+        if let Some(default) = &self.default {
+            return Err(syn::Error::new(
+                default.span(),
+                "`default` is only allowed on fields",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses the attributes of a field, where only `skip` and `default` are allowed.
+    pub fn parse_field(attrs: &[Attribute]) -> syn::Result<Self> {
+        let field_attrs = LogIdAttrs::parse(attrs)?;
+
+        if field_attrs.level.is_some()
+            || field_attrs.name.is_some()
+            || field_attrs.code.is_some()
+            || field_attrs.capture.is_some()
+            || field_attrs.explain.is_some()
+            || field_attrs.explain_file.is_some()
+            || field_attrs.help_url.is_some()
+        {
+            let attr = attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("log_id"))
+                .expect("Attributes were set, so there must be a `log_id` attribute.");
+            return Err(syn::Error::new(
+                attr.span(),
+                "only `skip` and `default` are allowed on fields",
+            ));
+        }
+
+        Ok(field_attrs)
+    }
+
+    /// Returns an error if a name, code, explanation, or help URL is set, because they are only allowed on variants and structs.
+    pub fn deny_variant_only(&self) -> syn::Result<()> {
+        if let Some(name) = &self.name {
+            return Err(syn::Error::new(
+                name.span(),
+                "`name` and `id` are only allowed on enum variants and structs",
+            ));
+        }
+        if let Some(code) = &self.code {
+            return Err(syn::Error::new(
+                code.span(),
+                "`code` is only allowed on enum variants and structs",
+            ));
+        }
+        for (attr, name) in [
+            (&self.explain, "explain"),
+            (&self.explain_file, "explain_file"),
+            (&self.help_url, "help_url"),
+        ] {
+            if let Some(attr) = attr {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    format!("`{name}` is only allowed on enum variants and structs"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the level set by the derive macro with the given name, or `None` if it is no log-id derive.
+///
+/// The inner `None` is returned for `LogId`, because its level must be set via `#[log_id(level = "...")]`.
+pub fn derived_level(derive_name: &str) -> Option<Option<LogLevel>> {
+    match derive_name {
+        "LogId" => Some(None),
+        "ErrLogId" => Some(Some(LogLevel::Error)),
+        "WarnLogId" => Some(Some(LogLevel::Warn)),
+        "InfoLogId" => Some(Some(LogLevel::Info)),
+        "DbgLogId" => Some(Some(LogLevel::Debug)),
+        "TraceLogId" => Some(Some(LogLevel::Trace)),
+        _ => None,
+    }
+}
+
+/// Returns the level with the given case-insensitive name (e.g. `warn`).
+pub fn level_from_name(name: &str) -> Option<LogLevel> {
+    match name.to_lowercase().as_str() {
+        "error" => Some(LogLevel::Error),
+        "warn" => Some(LogLevel::Warn),
+        "info" => Some(LogLevel::Info),
+        "debug" => Some(LogLevel::Debug),
+        "trace" => Some(LogLevel::Trace),
+        _ => None,
+    }
+}
+
+fn parse_level(level: &LitStr) -> syn::Result<LogLevel> {
+    level_from_name(&level.value()).ok_or_else(|| {
+        syn::Error::new(
+            level.span(),
+            "unknown log level, expected one of `error`, `warn`, `info`, `debug`, or `trace`",
+        )
+    })
+}
+
+/// Returns the default identifier of an enum variant in the form `<Enum>::<Variant>`.
+///
+/// **Note:** Variants with fields keep their field marker (e.g. `<Enum>::<Variant>(_)`).
+pub fn variant_identifier(
+    enum_name: &proc_macro2::Ident,
+    variant_name: &proc_macro2::Ident,
+    fields: &syn::Fields,
+) -> String {
+    match fields {
+        syn::Fields::Named(_) => format!("{enum_name}::{variant_name}{{..}}"),
+        syn::Fields::Unnamed(_) => format!("{enum_name}::{variant_name}(_)"),
+        syn::Fields::Unit => format!("{enum_name}::{variant_name}"),
+    }
+}
+
+/// Returns `true` if the given attributes only include the item in test builds, e.g. via `#[cfg(test)]`.
+pub fn is_test_only(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.parse_args::<syn::Meta>().ok())
+        .any(|predicate| requires_test(&predicate))
+}
+
+/// Returns `true` if the given `cfg` predicate is only true in test builds.
+fn requires_test(predicate: &syn::Meta) -> bool {
+    match predicate {
+        syn::Meta::Path(path) => path.is_ident("test"),
+        syn::Meta::List(list) if list.path.is_ident("all") => list
+            .parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+            .is_ok_and(|predicates| predicates.iter().any(requires_test)),
+        _ => false,
+    }
+}
+
+/// Returns the doc comment set via `///` or `#[doc = "..."]` attributes, with one line per attribute.
+pub fn doc_comment(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the message format string set via `#[error("...")]` (e.g. of `thiserror`), if any.
+pub fn error_msg(attrs: &[Attribute]) -> Option<LitStr> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error"))
+        .find_map(|attr| {
+            attr.parse_args_with(|input: syn::parse::ParseStream| {
+                let msg: LitStr = input.parse()?;
+                // Format arguments are not part of the message
+                let _: proc_macro2::TokenStream = input.parse()?;
+                Ok(msg)
+            })
+            .ok()
+        })
+}
+
+/// Returns the level to use, or an error if no level is set.
+pub fn require_level(level: Option<LogLevel>, item: &impl Spanned) -> syn::Result<LogLevel> {
+    level.ok_or_else(|| {
+        syn::Error::new(
+            item.span(),
+            "missing log level, set it with `#[log_id(level = \"...\")]`",
+        )
+    })
+}
@@ -70,9 +70,9 @@ pub fn bench_compare_advanced_logging(c: &mut Criterion) {
 
     let mut bench_group = c.benchmark_group("compare advanced bench");
 
-    bench_group.bench_function("tracing", |b| b.iter(|| advanced_tracing()));
+    bench_group.bench_function("tracing", |b| b.iter(advanced_tracing));
 
-    bench_group.bench_function("logid", |b| b.iter(|| advanced_logid()));
+    bench_group.bench_function("logid", |b| b.iter(advanced_logid));
 
     bench_group.finish();
 
@@ -43,7 +43,7 @@ fn main() {
     handler.start();
 
     let payload = DummyPayload {
-        vals: vec!["First", "Second", "Third", "Fourth"]
+        vals: ["First", "Second", "Third", "Fourth"]
             .iter_mut()
             .map(|f| f.to_string())
             .collect(),
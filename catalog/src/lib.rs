@@ -0,0 +1,162 @@
+//! Library to generate catalogs of all [`LogId`]s defined with [logid].
+//!
+//! A catalog lists identifier, level, module, code, doc comment, `#[error(...)]` message,
+//! and the explanation set via `#[log_id(explain = "...")]` for every [`LogId`].
+//! Support teams may use it to map an identifier or code found in a log to its meaning and remediation.
+//!
+//! Catalogs are created either by scanning the sources of all packages in a workspace,
+//! or from the [`registry`](logid::registry) of the current binary.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid_catalog::{Catalog, Format};
+//!
+//! let catalog = Catalog::from_source(
+//!     "my_crate::errors",
+//!     "src/errors.rs",
+//!     r#"
+//!         #[derive(ErrLogId, thiserror::Error)]
+//!         enum StorageError {
+//!             /// Storage is full.
+//!             #[error("No space left.")]
+//!             #[log_id(code = "E0042")]
+//!             Full,
+//!         }
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! assert!(catalog.render(Format::Markdown).contains("E0042"));
+//! ~~~
+//!
+//! [`LogId`]: logid::log_id::LogId
+
+use std::path::{Path, PathBuf};
+
+use logid::log_id::LogLevel;
+
+pub mod render;
+mod scan;
+
+pub use render::Format;
+
+/// Catalog of [`LogId`](logid::log_id::LogId)s sorted by module path and identifier.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    entries: Vec<CatalogEntry>,
+}
+
+/// One [`LogId`](logid::log_id::LogId) of a [`Catalog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    pub module_path: String,
+    pub identifier: String,
+    pub level: LogLevel,
+    pub code: Option<String>,
+    /// Source file the [`LogId`](logid::log_id::LogId) is defined in.
+    pub file: String,
+    /// Line in the source file the [`LogId`](logid::log_id::LogId) is defined at.
+    pub line: u32,
+    /// Doc comment of the enum variant or struct.
+    pub doc: String,
+    /// Message set via `#[error("...")]`.
+    pub error_msg: Option<String>,
+    /// Long-form explanation set via `#[log_id(explain = "...")]` or `#[log_id(explain_file = "...")]`.
+    pub explanation: Option<String>,
+    /// URL to documentation set via `#[log_id(help_url = "...")]`.
+    pub help_url: Option<String>,
+}
+
+impl Catalog {
+    pub fn new(mut entries: Vec<CatalogEntry>) -> Self {
+        entries
+            .sort_by(|a, b| (&a.module_path, &a.identifier).cmp(&(&b.module_path, &b.identifier)));
+        Catalog { entries }
+    }
+
+    /// Creates a catalog of all [`LogId`](logid::log_id::LogId)s in the [`registry`](logid::registry) of the current binary.
+    pub fn from_registry() -> Self {
+        let entries = logid::registry::all_ids()
+            .into_iter()
+            .map(|registered| CatalogEntry {
+                module_path: registered.get_module_path().to_string(),
+                identifier: registered.get_identifier().to_string(),
+                level: registered.get_log_level(),
+                code: registered.get_code().map(str::to_string),
+                file: registered.get_file().to_string(),
+                line: registered.get_line(),
+                doc: registered.get_doc().to_string(),
+                error_msg: registered.get_error_msg().map(str::to_string),
+                explanation: registered.get_explanation().map(str::to_string),
+                help_url: registered.get_help_url().map(str::to_string),
+            })
+            .collect();
+
+        Catalog::new(entries)
+    }
+
+    /// Creates a catalog of all packages found in the given directory and its subdirectories.
+    ///
+    /// All `.rs` files in the `src` directory of a package are scanned.
+    /// The directories `target` and hidden directories are skipped.
+    /// File paths of entries are relative to the given directory.
+    ///
+    /// **Note:** Module paths are derived from file paths, so modules declared with `#[path]` get wrong module paths.
+    pub fn scan(root: &Path) -> Result<Self, CatalogError> {
+        let mut entries = Vec::new();
+
+        for package in scan::find_packages(root)? {
+            entries.append(&mut scan::scan_package(root, &package)?);
+        }
+
+        Ok(Catalog::new(entries))
+    }
+
+    /// Creates a catalog of the given source code of the module at the given module path.
+    ///
+    /// **Note:** Explanations set via `#[log_id(explain_file = "...")]` are not resolved, because the package root is unknown.
+    pub fn from_source(module_path: &str, file: &str, source: &str) -> Result<Self, CatalogError> {
+        let entries = scan::scan_source(module_path, file, source, None)
+            .map_err(|err| CatalogError::Parsing(PathBuf::from(file), err.to_string()))?;
+
+        Ok(Catalog::new(entries))
+    }
+
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry with the given code, if any.
+    pub fn find_by_code(&self, code: &str) -> Option<&CatalogEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.code.as_deref() == Some(code))
+    }
+
+    /// Renders the catalog in the given format.
+    pub fn render(&self, format: Format) -> String {
+        render::render(self, format)
+    }
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Reading(PathBuf, std::io::Error),
+    Parsing(PathBuf, String),
+}
+
+impl std::error::Error for CatalogError {}
+
+impl std::fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogError::Reading(path, err) => {
+                write!(f, "Could not read '{}': {}", path.display(), err)
+            }
+            CatalogError::Parsing(path, err) => {
+                write!(f, "Could not parse '{}': {}", path.display(), err)
+            }
+        }
+    }
+}
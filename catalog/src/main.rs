@@ -0,0 +1,86 @@
+//! Binary to generate a catalog of all log-ids in a workspace.
+//!
+//! **Usage:**
+//!
+//! ```text
+//! logid-catalog [--format <markdown|json|html>] [--output <file>] [<path>...]
+//! ```
+//!
+//! All packages in the given paths are scanned. The current directory is scanned if no path is given.
+
+use std::path::PathBuf;
+
+use logid_catalog::{Catalog, Format};
+
+const USAGE: &str =
+    "Usage: logid-catalog [--format <markdown|json|html>] [--output <file>] [<path>...]";
+
+struct Args {
+    format: Format,
+    output: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        format: Format::default(),
+        output: None,
+        paths: Vec::new(),
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-f" | "--format" => {
+                let format = iter.next().ok_or("Missing value for `--format`.")?;
+                args.format = format.parse()?;
+            }
+            "-o" | "--output" => {
+                let output = iter.next().ok_or("Missing value for `--output`.")?;
+                args.output = Some(PathBuf::from(output));
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'.", arg)),
+            _ => args.paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if args.paths.is_empty() {
+        args.paths.push(PathBuf::from("."));
+    }
+
+    Ok(args)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let mut entries = Vec::new();
+    for path in &args.paths {
+        match Catalog::scan(path) {
+            Ok(catalog) => entries.extend_from_slice(catalog.entries()),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let rendered = Catalog::new(entries).render(args.format);
+
+    match args.output {
+        Some(output) => {
+            if let Err(err) = std::fs::write(&output, rendered) {
+                eprintln!("Could not write '{}': {}", output.display(), err);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", rendered),
+    }
+}
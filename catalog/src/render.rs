@@ -0,0 +1,183 @@
+//! Contains functions to render a [`Catalog`] as Markdown, JSON, or HTML.
+
+use logid::log_id::LogLevel;
+
+use crate::{Catalog, CatalogEntry};
+
+/// Output format of a rendered [`Catalog`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Json,
+    Html,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "Unknown format '{}'. Expected one of `markdown`, `json`, or `html`.",
+                s
+            )),
+        }
+    }
+}
+
+/// Renders the given catalog in the given format.
+pub fn render(catalog: &Catalog, format: Format) -> String {
+    match format {
+        Format::Markdown => render_markdown(catalog),
+        Format::Json => render_json(catalog),
+        Format::Html => render_html(catalog),
+    }
+}
+
+/// Returns the title of an entry, which starts with the code if the entry has one.
+fn title(entry: &CatalogEntry) -> String {
+    match &entry.code {
+        Some(code) => format!("{code}: {}", entry.identifier),
+        None => entry.identifier.clone(),
+    }
+}
+
+fn level_name(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Error => "error",
+        LogLevel::Warn => "warn",
+        LogLevel::Info => "info",
+        LogLevel::Debug => "debug",
+        LogLevel::Trace => "trace",
+    }
+}
+
+pub fn render_markdown(catalog: &Catalog) -> String {
+    let mut content = String::from("# LogId Catalog\n");
+
+    for entry in catalog.entries() {
+        content.push_str(&format!("\n## {}\n\n", title(entry)));
+        content.push_str(&format!("- **Level:** {}\n", level_name(entry.level)));
+        content.push_str(&format!("- **Module:** `{}`\n", entry.module_path));
+        content.push_str(&format!(
+            "- **Defined at:** `{}:{}`\n",
+            entry.file, entry.line
+        ));
+        if let Some(msg) = &entry.error_msg {
+            content.push_str(&format!("- **Message:** {}\n", msg));
+        }
+        if let Some(url) = &entry.help_url {
+            content.push_str(&format!("- **Help:** <{}>\n", url));
+        }
+        if !entry.doc.is_empty() {
+            content.push_str(&format!("\n{}\n", entry.doc));
+        }
+        if let Some(explanation) = &entry.explanation {
+            content.push_str(&format!("\n### Explanation\n\n{}\n", explanation));
+        }
+    }
+
+    content
+}
+
+pub fn render_json(catalog: &Catalog) -> String {
+    let entries: Vec<_> = catalog
+        .entries()
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "module": entry.module_path,
+                "identifier": entry.identifier,
+                "level": level_name(entry.level),
+                "code": entry.code,
+                "file": entry.file,
+                "line": entry.line,
+                "doc": entry.doc,
+                "message": entry.error_msg,
+                "explanation": entry.explanation,
+                "help_url": entry.help_url,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&entries).expect("Catalog entries are valid JSON values.")
+}
+
+pub fn render_html(catalog: &Catalog) -> String {
+    let mut content = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>LogId Catalog</title>\n</head>\n<body>\n<h1>LogId Catalog</h1>\n",
+    );
+
+    content.push_str("<ul>\n");
+    for entry in catalog.entries() {
+        content.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            anchor(entry),
+            escape_html(&title(entry))
+        ));
+    }
+    content.push_str("</ul>\n");
+
+    for entry in catalog.entries() {
+        content.push_str(&format!(
+            "<section id=\"{}\">\n<h2>{}</h2>\n<dl>\n",
+            anchor(entry),
+            escape_html(&title(entry))
+        ));
+        content.push_str(&format!(
+            "<dt>Level</dt><dd>{}</dd>\n",
+            level_name(entry.level)
+        ));
+        content.push_str(&format!(
+            "<dt>Module</dt><dd><code>{}</code></dd>\n",
+            escape_html(&entry.module_path)
+        ));
+        content.push_str(&format!(
+            "<dt>Defined at</dt><dd><code>{}:{}</code></dd>\n",
+            escape_html(&entry.file),
+            entry.line
+        ));
+        if let Some(msg) = &entry.error_msg {
+            content.push_str(&format!("<dt>Message</dt><dd>{}</dd>\n", escape_html(msg)));
+        }
+        if let Some(url) = &entry.help_url {
+            content.push_str(&format!(
+                "<dt>Help</dt><dd><a href=\"{0}\">{0}</a></dd>\n",
+                escape_html(url)
+            ));
+        }
+        content.push_str("</dl>\n");
+        if !entry.doc.is_empty() {
+            content.push_str(&format!("<pre>{}</pre>\n", escape_html(&entry.doc)));
+        }
+        if let Some(explanation) = &entry.explanation {
+            content.push_str(&format!(
+                "<h3>Explanation</h3>\n<pre>{}</pre>\n",
+                escape_html(explanation)
+            ));
+        }
+        content.push_str("</section>\n");
+    }
+
+    content.push_str("</body>\n</html>\n");
+    content
+}
+
+/// Returns the HTML anchor of an entry, which is unique per module path and identifier.
+fn anchor(entry: &CatalogEntry) -> String {
+    format!("{}::{}", entry.module_path, entry.identifier)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,382 @@
+//! Scanning of Rust sources for derived [`LogId`](logid::log_id::LogId)s and [`new_log_id!`](logid::new_log_id) invocations.
+//!
+//! Attributes, identifiers, and levels are resolved by `logid-attrs`, which is shared with `logid-derive`.
+//! Items only compiled in test builds (e.g. with `#[cfg(test)]`) are skipped.
+
+use std::path::{Path, PathBuf};
+
+use logid::log_id::LogLevel;
+use logid_attrs::{
+    derived_level, doc_comment, error_msg, is_test_only, level_from_name, variant_identifier,
+    LogIdAttrs,
+};
+use syn::{punctuated::Punctuated, spanned::Spanned, visit::Visit, Attribute, LitStr, Token};
+
+use crate::{CatalogEntry, CatalogError};
+
+/// Returns all package directories in the given directory and its subdirectories.
+pub(crate) fn find_packages(root: &Path) -> Result<Vec<PathBuf>, CatalogError> {
+    let mut packages = Vec::new();
+
+    let manifest = root.join("Cargo.toml");
+    if manifest.is_file() && package_name(&manifest)?.is_some() {
+        packages.push(root.to_path_buf());
+    }
+
+    for dir in sub_dirs(root)? {
+        let is_skipped = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name == "target" || name.starts_with('.'))
+            .unwrap_or(true);
+
+        if !is_skipped {
+            packages.append(&mut find_packages(&dir)?);
+        }
+    }
+
+    Ok(packages)
+}
+
+/// Returns all entries of the package in the given directory.
+///
+/// File paths of entries are relative to the given root directory.
+pub(crate) fn scan_package(root: &Path, package: &Path) -> Result<Vec<CatalogEntry>, CatalogError> {
+    let crate_name = package_name(&package.join("Cargo.toml"))?
+        .unwrap_or_default()
+        .replace('-', "_");
+    let src = package.join("src");
+    let mut entries = Vec::new();
+
+    if !src.is_dir() {
+        return Ok(entries);
+    }
+
+    for file in rust_files(&src)? {
+        let source = std::fs::read_to_string(&file)
+            .map_err(|err| CatalogError::Reading(file.clone(), err))?;
+        let relative = file.strip_prefix(&src).unwrap_or(&file);
+        let module_path = module_path(&crate_name, relative);
+        let display_path = file.strip_prefix(root).unwrap_or(&file);
+
+        let mut file_entries = scan_source(
+            &module_path,
+            &display_path.to_string_lossy(),
+            &source,
+            Some(package),
+        )
+        .map_err(|err| CatalogError::Parsing(file.clone(), err.to_string()))?;
+        entries.append(&mut file_entries);
+    }
+
+    Ok(entries)
+}
+
+/// Returns all entries of the given source code of the module at the given module path.
+///
+/// Explanation files are read relative to the given package directory, and ignored if no directory is given.
+pub(crate) fn scan_source(
+    module_path: &str,
+    file: &str,
+    source: &str,
+    package: Option<&Path>,
+) -> syn::Result<Vec<CatalogEntry>> {
+    let syntax = syn::parse_file(source)?;
+    let mut visitor = LogIdVisitor {
+        module_path: vec![module_path.to_string()],
+        file,
+        package,
+        const_attrs: Vec::new(),
+        entries: Vec::new(),
+    };
+
+    visitor.visit_file(&syntax);
+
+    Ok(visitor.entries)
+}
+
+/// Returns the name set in the `[package]` section of the given manifest.
+fn package_name(manifest: &Path) -> Result<Option<String>, CatalogError> {
+    let content = std::fs::read_to_string(manifest)
+        .map_err(|err| CatalogError::Reading(manifest.to_path_buf(), err))?;
+    let mut in_package = false;
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "name" {
+                    return Ok(Some(value.trim().trim_matches('"').to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn sub_dirs(dir: &Path) -> Result<Vec<PathBuf>, CatalogError> {
+    let mut dirs = Vec::new();
+
+    for entry in
+        std::fs::read_dir(dir).map_err(|err| CatalogError::Reading(dir.to_path_buf(), err))?
+    {
+        let path = entry
+            .map_err(|err| CatalogError::Reading(dir.to_path_buf(), err))?
+            .path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn rust_files(dir: &Path) -> Result<Vec<PathBuf>, CatalogError> {
+    let mut files = Vec::new();
+
+    for entry in
+        std::fs::read_dir(dir).map_err(|err| CatalogError::Reading(dir.to_path_buf(), err))?
+    {
+        let path = entry
+            .map_err(|err| CatalogError::Reading(dir.to_path_buf(), err))?
+            .path();
+        if path.is_dir() {
+            files.append(&mut rust_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the module path of the given file path relative to the `src` directory of a package.
+///
+/// Binaries in `src/bin` get their binary name as crate name.
+fn module_path(crate_name: &str, relative: &Path) -> String {
+    let mut parts: Vec<String> = relative
+        .with_extension("")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().replace('-', "_"))
+        .collect();
+
+    if parts.first().is_some_and(|part| part == "bin") {
+        parts.remove(0);
+    } else {
+        parts.insert(0, crate_name.to_string());
+    }
+
+    if parts.len() > 1
+        && parts
+            .last()
+            .is_some_and(|last| last == "mod" || last == "lib" || last == "main")
+    {
+        parts.pop();
+    }
+
+    parts.join("::")
+}
+
+struct LogIdVisitor<'a> {
+    module_path: Vec<String>,
+    file: &'a str,
+    package: Option<&'a Path>,
+    /// Attributes of the `const` or `static` item that is visited, used for the doc comment of `new_log_id!` invocations.
+    const_attrs: Vec<Attribute>,
+    entries: Vec<CatalogEntry>,
+}
+
+impl LogIdVisitor<'_> {
+    fn push_entry(
+        &mut self,
+        identifier: String,
+        level: LogLevel,
+        attrs: &LogIdAttrs,
+        item_attrs: &[Attribute],
+        span: proc_macro2::Span,
+    ) {
+        self.entries.push(CatalogEntry {
+            module_path: self.module_path.join("::"),
+            identifier,
+            level,
+            code: attrs.code.as_ref().map(LitStr::value),
+            file: self.file.to_string(),
+            line: span.start().line as u32,
+            doc: doc_comment(item_attrs),
+            error_msg: error_msg(item_attrs).as_ref().map(LitStr::value),
+            explanation: attrs.explain.as_ref().map(LitStr::value).or_else(|| {
+                let file = attrs.explain_file.as_ref()?;
+                std::fs::read_to_string(self.package?.join(file.value())).ok()
+            }),
+            help_url: attrs.help_url.as_ref().map(LitStr::value),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for LogIdVisitor<'_> {
+    fn visit_item(&mut self, item: &'ast syn::Item) {
+        // Note: Test-only items are not part of the binary, so their log-ids are never logged
+        if !is_test_only(item_attrs(item)) {
+            syn::visit::visit_item(self, item);
+        }
+    }
+
+    fn visit_item_mod(&mut self, item: &'ast syn::ItemMod) {
+        self.module_path.push(item.ident.to_string());
+        syn::visit::visit_item_mod(self, item);
+        self.module_path.pop();
+    }
+
+    fn visit_item_const(&mut self, item: &'ast syn::ItemConst) {
+        self.const_attrs = item.attrs.clone();
+        syn::visit::visit_item_const(self, item);
+        self.const_attrs.clear();
+    }
+
+    fn visit_item_static(&mut self, item: &'ast syn::ItemStatic) {
+        self.const_attrs = item.attrs.clone();
+        syn::visit::visit_item_static(self, item);
+        self.const_attrs.clear();
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast syn::ItemEnum) {
+        // Note: Invalid attributes are skipped, because they are reported by the derive macros
+        if let (Some(derive_level), Ok(type_attrs)) =
+            (derive_level(&item.attrs), LogIdAttrs::parse(&item.attrs))
+        {
+            for variant in &item.variants {
+                if is_test_only(&variant.attrs) {
+                    continue;
+                }
+                let Ok(variant_attrs) = LogIdAttrs::parse(&variant.attrs) else {
+                    continue;
+                };
+                let Some(level) = variant_attrs.level.or(type_attrs.level).or(derive_level) else {
+                    continue;
+                };
+                let identifier = variant_attrs
+                    .name
+                    .as_ref()
+                    .map(LitStr::value)
+                    .unwrap_or_else(|| {
+                        variant_identifier(&item.ident, &variant.ident, &variant.fields)
+                    });
+
+                self.push_entry(
+                    identifier,
+                    level,
+                    &variant_attrs,
+                    &variant.attrs,
+                    variant.ident.span(),
+                );
+            }
+        }
+
+        syn::visit::visit_item_enum(self, item);
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        if let (Some(derive_level), Ok(attrs)) =
+            (derive_level(&item.attrs), LogIdAttrs::parse(&item.attrs))
+        {
+            if let Some(level) = attrs.level.or(derive_level) {
+                let identifier = attrs
+                    .name
+                    .as_ref()
+                    .map(LitStr::value)
+                    .unwrap_or_else(|| item.ident.to_string());
+                self.push_entry(identifier, level, &attrs, &item.attrs, item.ident.span());
+            }
+        }
+
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let is_new_log_id = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "new_log_id");
+
+        if is_new_log_id {
+            if let Ok(args) =
+                mac.parse_body_with(Punctuated::<syn::Expr, Token![,]>::parse_terminated)
+            {
+                let mut args = args.into_iter();
+                let identifier = args.next().and_then(|arg| lit_str(&arg));
+                let level = args.next().and_then(|arg| path_level(&arg));
+                let code = args.next().and_then(|arg| lit_str(&arg));
+
+                if let (Some(identifier), Some(level)) = (identifier, level) {
+                    let attrs = LogIdAttrs {
+                        code,
+                        ..Default::default()
+                    };
+                    let item_attrs = std::mem::take(&mut self.const_attrs);
+                    self.push_entry(
+                        identifier.value(),
+                        level,
+                        &attrs,
+                        &item_attrs,
+                        mac.path.span(),
+                    );
+                    self.const_attrs = item_attrs;
+                }
+            }
+        }
+
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+/// Returns the attributes of the given item.
+fn item_attrs(item: &syn::Item) -> &[Attribute] {
+    match item {
+        syn::Item::Const(item) => &item.attrs,
+        syn::Item::Enum(item) => &item.attrs,
+        syn::Item::Fn(item) => &item.attrs,
+        syn::Item::Impl(item) => &item.attrs,
+        syn::Item::Macro(item) => &item.attrs,
+        syn::Item::Mod(item) => &item.attrs,
+        syn::Item::Static(item) => &item.attrs,
+        syn::Item::Struct(item) => &item.attrs,
+        syn::Item::Trait(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Returns `Some` if a log-id derive is set, with the level set by the derive if it sets one.
+fn derive_level(attrs: &[Attribute]) -> Option<Option<LogLevel>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .find_map(|path| derived_level(&path.segments.last()?.ident.to_string()))
+}
+
+/// Returns the level of an expression like `LogLevel::Error`.
+fn path_level(expr: &syn::Expr) -> Option<LogLevel> {
+    match expr {
+        syn::Expr::Path(path) => level_from_name(&path.path.segments.last()?.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn lit_str(expr: &syn::Expr) -> Option<LitStr> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Some(lit.clone()),
+        _ => None,
+    }
+}
@@ -0,0 +1,159 @@
+use logid::{log_id::LogLevel, ErrLogId};
+use logid_catalog::{Catalog, CatalogEntry, Format};
+
+const SOURCE: &str = r#"
+use logid::{ErrLogId, LogId, new_log_id, log_id::{LogId, LogLevel}};
+
+/// Errors while reading configurations.
+#[derive(Debug, ErrLogId, thiserror::Error)]
+enum ConfigError {
+    /// The configuration file does not exist.
+    #[error("Could not find '{0}'.")]
+    #[log_id(code = "E0001", explain = "Create the file, or set its path via `--config`.")]
+    NotFound(String),
+    #[error("Invalid entry.")]
+    #[log_id(level = "warn", name = "invalid_entry")]
+    InvalidEntry { line: usize },
+}
+
+mod nested {
+    #[derive(LogId)]
+    #[log_id(level = "info")]
+    struct Reloaded;
+}
+
+/// Set once the configuration is loaded.
+const LOADED: LogId = new_log_id!("loaded", LogLevel::Debug, "D0001");
+
+#[cfg(test)]
+mod tests {
+    const TEST_ONLY: LogId = new_log_id!("test_only", LogLevel::Info);
+}
+
+#[cfg(all(test, unix))]
+#[derive(LogId)]
+#[log_id(level = "info")]
+struct TestOnlyStruct;
+"#;
+
+fn scanned() -> Catalog {
+    Catalog::from_source("my_crate::config", "src/config.rs", SOURCE).unwrap()
+}
+
+#[test]
+fn derived_variants_scanned() {
+    let catalog = scanned();
+    let not_found = catalog.find_by_code("E0001").expect("Variant not scanned.");
+
+    assert_eq!(
+        not_found,
+        &CatalogEntry {
+            module_path: "my_crate::config".to_string(),
+            identifier: "ConfigError::NotFound(_)".to_string(),
+            level: LogLevel::Error,
+            code: Some("E0001".to_string()),
+            file: "src/config.rs".to_string(),
+            line: 10,
+            doc: "The configuration file does not exist.".to_string(),
+            error_msg: Some("Could not find '{0}'.".to_string()),
+            explanation: Some("Create the file, or set its path via `--config`.".to_string()),
+            help_url: None,
+        },
+        "Variant scanned wrong."
+    );
+
+    let invalid_entry = catalog
+        .entries()
+        .iter()
+        .find(|entry| entry.identifier == "invalid_entry")
+        .expect("Renamed variant not scanned.");
+    assert_eq!(
+        invalid_entry.level,
+        LogLevel::Warn,
+        "Variant level not scanned."
+    );
+}
+
+#[test]
+fn nested_struct_and_new_log_id_scanned() {
+    let catalog = scanned();
+    let identifiers: Vec<_> = catalog
+        .entries()
+        .iter()
+        .map(|entry| (entry.module_path.as_str(), entry.identifier.as_str()))
+        .collect();
+
+    assert_eq!(
+        identifiers,
+        vec![
+            ("my_crate::config", "ConfigError::NotFound(_)"),
+            ("my_crate::config", "invalid_entry"),
+            ("my_crate::config", "loaded"),
+            ("my_crate::config::nested", "Reloaded"),
+        ],
+        "Not all log-ids scanned, test-only log-ids scanned, or wrongly sorted."
+    );
+    assert_eq!(
+        catalog
+            .find_by_code("D0001")
+            .map(|entry| entry.doc.as_str()),
+        Some("Set once the configuration is loaded."),
+        "Doc comment of constant not used for `new_log_id!`."
+    );
+}
+
+#[test]
+fn catalog_rendered() {
+    let catalog = scanned();
+
+    let markdown = catalog.render(Format::Markdown);
+    assert!(
+        markdown.contains("## E0001: ConfigError::NotFound(_)"),
+        "Entry title missing in Markdown."
+    );
+    assert!(
+        markdown.contains("### Explanation\n\nCreate the file"),
+        "Explanation missing in Markdown."
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&catalog.render(Format::Json)).unwrap();
+    assert_eq!(
+        json[0]["message"], "Could not find '{0}'.",
+        "Message missing in JSON."
+    );
+    assert_eq!(json[3]["level"], "info", "Level missing in JSON.");
+
+    let html = catalog.render(Format::Html);
+    assert!(
+        html.contains("<dt>Message</dt><dd>Could not find '{0}'.</dd>"),
+        "Message missing in HTML."
+    );
+}
+
+/// Error only used to check the registry catalog.
+#[derive(Debug, ErrLogId, thiserror::Error)]
+enum RegistryError {
+    #[error("Registered.")]
+    #[log_id(code = "E9999", explain = "Registered via derive.")]
+    Registered,
+}
+
+#[test]
+fn catalog_from_registry() {
+    let _ = RegistryError::Registered;
+    let catalog = Catalog::from_registry();
+    let registered = catalog
+        .find_by_code("E9999")
+        .expect("Registered log-id missing.");
+
+    assert_eq!(
+        registered.explanation.as_deref(),
+        Some("Registered via derive."),
+        "Explanation of registered log-id missing."
+    );
+    assert_eq!(
+        registered.error_msg.as_deref(),
+        Some("Registered."),
+        "Message of registered log-id missing."
+    );
+}
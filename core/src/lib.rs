@@ -1,5 +1,9 @@
 pub mod log_id;
 pub mod logging;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 // Re-exports
 pub use evident;
@@ -7,5 +11,14 @@ pub use evident;
 #[cfg(feature = "diagnostics")]
 pub use lsp_types;
 
-#[cfg(any(feature = "payloads", feature = "fmt"))]
+#[cfg(any(
+    feature = "payloads",
+    feature = "fmt",
+    feature = "json",
+    feature = "redaction"
+))]
 pub use serde_json;
+
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub use inventory;
@@ -1,12 +1,57 @@
 //! Contains the [`LogId`] struct.
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+use std::sync::RwLock;
+
+/// Identifies log-id events by module path, identifier, and [`LogLevel`].
+///
+/// The code describes a [`LogId`], but is not part of its identity.
+/// Explanation and help URL are stored in the [`registry`](crate::registry) of feature `registry`.
+#[derive(Debug, Default, Clone, Copy)]
 pub struct LogId {
     pub(crate) module_path: &'static str,
 
     pub(crate) identifier: &'static str,
 
     pub(crate) log_level: LogLevel,
+
+    /// Optional stable code (e.g. `E0042`) that does not change if the identifier is renamed.
+    pub(crate) code: Option<&'static str>,
+}
+
+/// Command that prints the explanation of a [`LogId`] if called with its code or identifier.
+static EXPLAIN_COMMAND: RwLock<Option<String>> = RwLock::new(None);
+
+/// Sets the command that prints the explanation of a [`LogId`] if called with its code or identifier (e.g. `myapp --explain`).
+///
+/// The command is shown in the help of events whose [`LogId`] has an explanation.
+/// The command may use [`registry::explain()`](crate::registry::explain) with feature `registry` to look up explanations.
+pub fn set_explain_command(cmd: impl Into<String>) {
+    if let Ok(mut explain_cmd) = EXPLAIN_COMMAND.write() {
+        *explain_cmd = Some(cmd.into());
+    }
+}
+
+#[cfg(feature = "registry")]
+fn explain_command() -> Option<String> {
+    EXPLAIN_COMMAND.read().ok().and_then(|cmd| cmd.clone())
+}
+
+impl PartialEq for LogId {
+    fn eq(&self, other: &Self) -> bool {
+        self.module_path == other.module_path
+            && self.identifier == other.identifier
+            && self.log_level == other.log_level
+    }
+}
+
+impl Eq for LogId {}
+
+impl std::hash::Hash for LogId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.module_path.hash(state);
+        self.identifier.hash(state);
+        self.log_level.hash(state);
+    }
 }
 
 impl evident::event::Id for LogId {}
@@ -48,9 +93,18 @@ impl LogId {
             module_path,
             identifier,
             log_level,
+            code: None,
         }
     }
 
+    /// Sets a stable code for this [`LogId`].
+    ///
+    /// Codes may be used in filters (e.g. `on[#E0042]`), and are printed by the event handlers.
+    pub const fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     pub fn get_module_path(&self) -> &'static str {
         self.module_path
     }
@@ -62,6 +116,48 @@ impl LogId {
     pub fn get_log_level(&self) -> LogLevel {
         self.log_level
     }
+
+    pub fn get_code(&self) -> Option<&'static str> {
+        self.code
+    }
+
+    /// Returns the long-form explanation of this [`LogId`] (e.g. causes and remediation), if one is registered.
+    ///
+    /// **Note:** Always `None` without feature `registry`, because explanations are only stored in the registry.
+    pub fn explanation(&self) -> Option<&'static str> {
+        #[cfg(feature = "registry")]
+        return crate::registry::find(self).and_then(|registered| registered.get_explanation());
+
+        #[cfg(not(feature = "registry"))]
+        None
+    }
+
+    /// Returns a short help on where to find more information about this [`LogId`].
+    ///
+    /// The help refers to the explain command set with [`set_explain_command()`] if this [`LogId`] has a registered explanation,
+    /// and to the registered help URL if one is set.
+    ///
+    /// **Note:** Always `None` without feature `registry`, because explanations and help URLs are only stored in the registry.
+    pub fn help(&self) -> Option<String> {
+        #[cfg(feature = "registry")]
+        {
+            let registered = crate::registry::find(self)?;
+            let explain = registered
+                .get_explanation()
+                .and_then(|_| explain_command())
+                .map(|cmd| format!("run `{} {}`", cmd, self.code.unwrap_or(self.identifier)));
+
+            match (explain, registered.get_help_url()) {
+                (Some(explain), Some(url)) => Some(format!("{explain}, or see {url}")),
+                (Some(explain), None) => Some(explain),
+                (None, Some(url)) => Some(format!("see {url}")),
+                (None, None) => None,
+            }
+        }
+
+        #[cfg(not(feature = "registry"))]
+        None
+    }
 }
 
 impl std::fmt::Display for LogId {
@@ -70,6 +166,44 @@ impl std::fmt::Display for LogId {
     }
 }
 
+impl From<&LogId> for LogId {
+    fn from(value: &LogId) -> Self {
+        *value
+    }
+}
+
+/// Wrapper to convert a referenced value into a [`LogId`] without consuming it.
+///
+/// Used by the logging macros.
+#[doc(hidden)]
+pub struct __ToLogId<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __ViaRef {
+    fn __log_id(&self) -> LogId;
+}
+
+impl<'a, T> __ViaRef for __ToLogId<'a, T>
+where
+    &'a T: Into<LogId>,
+{
+    fn __log_id(&self) -> LogId {
+        self.0.into()
+    }
+}
+
+// Note: Implemented for the reference, so `__ViaRef` is preferred by method resolution if `&T` converts into `LogId`
+#[doc(hidden)]
+pub trait __ViaClone {
+    fn __log_id(&self) -> LogId;
+}
+
+impl<T: Clone + Into<LogId>> __ViaClone for &__ToLogId<'_, T> {
+    fn __log_id(&self) -> LogId {
+        self.0.clone().into()
+    }
+}
+
 /// Log level a [`LogId`] may represent.
 #[derive(Debug, Default, PartialOrd, Ord, PartialEq, Eq, Clone, Copy, std::hash::Hash)]
 pub enum LogLevel {
@@ -95,9 +229,35 @@ impl std::fmt::Display for LogLevel {
     }
 }
 
-/// Macro to create a [`LogId`] with a custom identifier and [`LogLevel`].
+/// Error returned if a [`LogId`] cannot be converted into a type deriving `FromLogId`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromLogIdError {
+    /// The [`LogId`] was created in another module than the type.
+    ForeignModule(LogId),
+    /// The identifier of the [`LogId`] matches no variant of the type.
+    UnknownIdentifier(LogId),
+}
+
+impl std::error::Error for FromLogIdError {}
+
+impl std::fmt::Display for FromLogIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromLogIdError::ForeignModule(log_id) => {
+                write!(f, "Log-id {} was created in a foreign module.", log_id)
+            }
+            FromLogIdError::UnknownIdentifier(log_id) => {
+                write!(f, "Log-id {} has an unknown identifier.", log_id)
+            }
+        }
+    }
+}
+
+/// Macro to create a [`LogId`] with a custom identifier and [`LogLevel`], and an optional stable code.
 ///
-/// **Note:** The identifier must be a string literal.
+/// The [`LogId`] is registered in the [`registry`](crate::registry) if the identifier and code are string literals,
+/// and the [`LogLevel`] is given as path (e.g. `LogLevel::Debug`).
+/// Other arguments may be non-constant, but the resulting [`LogId`] is not registered.
 ///
 /// ## Usage
 ///
@@ -105,12 +265,71 @@ impl std::fmt::Display for LogLevel {
 /// use logid_core::{new_log_id, log_id::LogLevel};
 ///
 /// let id = new_log_id!("custom_ident", LogLevel::Debug);
+/// let coded_id = new_log_id!("coded_ident", LogLevel::Error, "E0042");
+///
+/// // Not registered, because the arguments are not constant
+/// let level = LogLevel::Warn;
+/// let runtime_id = new_log_id!(String::from("runtime_ident").leak(), level);
 /// ```
 #[macro_export]
 macro_rules! new_log_id {
+    ($identifier:literal, $level_head:ident $(:: $level_tail:ident)+) => {{
+        const LOG_ID: $crate::log_id::LogId =
+            $crate::log_id::LogId::new(module_path!(), $identifier, $level_head $(:: $level_tail)+);
+        $crate::__register_log_id!(LOG_ID, "");
+        LOG_ID
+    }};
+    ($identifier:literal, $level_head:ident $(:: $level_tail:ident)+, $code:literal) => {{
+        const LOG_ID: $crate::log_id::LogId =
+            $crate::log_id::LogId::new(module_path!(), $identifier, $level_head $(:: $level_tail)+)
+                .with_code($code);
+        $crate::__register_log_id!(LOG_ID, "");
+        LOG_ID
+    }};
     ($identifier:expr, $log_level:expr) => {
         $crate::log_id::LogId::new(module_path!(), $identifier, $log_level)
     };
+    ($identifier:expr, $log_level:expr, $code:expr) => {
+        $crate::log_id::LogId::new(module_path!(), $identifier, $log_level).with_code($code)
+    };
+}
+
+/// Registers a [`LogId`] in the [`registry`](crate::registry) with the given doc comment,
+/// and optional error message, explanation, and help URL.
+///
+/// Used by [`new_log_id!`] and the derive macros.
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_log_id {
+    ($log_id:expr, $doc:expr) => {
+        $crate::__register_log_id!($log_id, $doc, None, None, None);
+    };
+    ($log_id:expr, $doc:expr, $error_msg:expr, $explanation:expr, $help_url:expr) => {
+        $crate::inventory::submit! {
+            $crate::registry::RegisteredLogId::new(
+                {
+                    const REGISTERED: $crate::log_id::LogId = $log_id;
+                    &REGISTERED
+                },
+                file!(),
+                line!(),
+                $doc,
+            )
+            .with_error_msg($error_msg)
+            .with_explanation($explanation)
+            .with_help_url($help_url)
+        }
+    };
+}
+
+/// Registering is a no-op without feature `registry`.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_log_id {
+    ($log_id:expr, $doc:expr) => {};
+    ($log_id:expr, $doc:expr, $error_msg:expr, $explanation:expr, $help_url:expr) => {};
 }
 
 #[cfg(test)]
@@ -135,5 +354,50 @@ mod tests {
             LogLevel::Debug,
             "Log level was not set correctly using `log_id!()` macro."
         );
+        assert_eq!(log_id.code, None, "Code was set without being given.");
+    }
+
+    #[test]
+    fn create_log_id_with_code() {
+        let log_id = new_log_id!("custom_ident", LogLevel::Error, "E0040");
+
+        assert_eq!(
+            log_id.get_code(),
+            Some("E0040"),
+            "Code was not set correctly using `log_id!()` macro."
+        );
+    }
+
+    #[test]
+    fn create_log_id_with_non_const_args() {
+        let identifier: &'static str = String::from("runtime_ident").leak();
+        let level = LogLevel::Warn;
+
+        assert_eq!(
+            new_log_id!(identifier, level),
+            LogId::new(module_path!(), "runtime_ident", LogLevel::Warn),
+            "Log-id with non-constant arguments not created."
+        );
+        assert_eq!(
+            new_log_id!(identifier, level, "W0040").get_code(),
+            Some("W0040"),
+            "Code not set for log-id with non-constant arguments."
+        );
+    }
+
+    #[test]
+    fn code_not_part_of_identity() {
+        let log_id = new_log_id!("described_ident", LogLevel::Error);
+
+        assert_eq!(
+            log_id.with_code("E0043"),
+            log_id,
+            "Code changed identity of log-id."
+        );
+        assert_ne!(
+            LogId::new(module_path!(), "described_ident", LogLevel::Warn),
+            log_id,
+            "Log-ids with different levels are equal."
+        );
     }
 }
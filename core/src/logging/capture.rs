@@ -0,0 +1,39 @@
+//! Contains the [`CaptureAddons`] trait to add addons from the value an event is logged with.
+
+use super::event_entry::AddonKind;
+
+/// Types that provide addons for events they are logged with.
+///
+/// The derive macros implement this trait if `#[log_id(capture = "infos")]` or `#[log_id(capture = "payload")]` is set.
+/// Fields marked with `#[log_id(skip)]` are not captured.
+pub trait CaptureAddons {
+    /// Returns the addons to add to the event this value is logged with.
+    fn capture_addons(&self) -> Vec<AddonKind>;
+}
+
+/// Wrapper to only capture addons for values implementing [`CaptureAddons`].
+#[doc(hidden)]
+pub struct __Capture<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __ViaCaptureAddons {
+    fn __capture_addons(&self) -> Vec<AddonKind>;
+}
+
+impl<T: CaptureAddons> __ViaCaptureAddons for __Capture<'_, T> {
+    fn __capture_addons(&self) -> Vec<AddonKind> {
+        self.0.capture_addons()
+    }
+}
+
+// Note: Implemented for the reference, so `__ViaCaptureAddons` is preferred by method resolution if `T` implements `CaptureAddons`
+#[doc(hidden)]
+pub trait __ViaNoCapture {
+    fn __capture_addons(&self) -> Vec<AddonKind>;
+}
+
+impl<T> __ViaNoCapture for &__Capture<'_, T> {
+    fn __capture_addons(&self) -> Vec<AddonKind> {
+        Vec::new()
+    }
+}
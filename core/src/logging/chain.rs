@@ -0,0 +1,248 @@
+//! Contains functionality to link log-id events to the events of the errors that caused them.
+//!
+//! Errors logged on a thread are remembered by their type and message, if their event passes the filter.
+//! If an error is logged whose [`source()`](std::error::Error::source) chain contains a remembered error,
+//! the new event gets the event of the nearest remembered source as parent.
+//!
+//! **Note:** Linking is best effort, because only a limited number of errors is remembered per thread,
+//! and errors of the same type with the same message cannot be distinguished.
+//! Errors are not identified by their address, because logged errors are usually moved before they are wrapped.
+
+use std::{any::TypeId, cell::RefCell, collections::VecDeque, error::Error};
+
+use evident::event::finalized::FinalizedEvent;
+
+use crate::log_id::LogId;
+
+use super::{event_entry::AddonKind, intermediary_event::IntermediaryLogEvent};
+
+/// Maximum number of logged errors remembered per thread.
+pub const MAX_REMEMBERED_ERRORS: usize = 128;
+
+thread_local! {
+    /// Recently logged errors on this thread, with the most recent one at the back.
+    static LOGGED_ERRORS: RefCell<VecDeque<(ErrorIdentity, FinalizedEvent<LogId>)>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Identifies a logged error in the source chain of other errors.
+#[derive(Debug, Clone)]
+pub(crate) struct ErrorIdentity {
+    type_id: TypeId,
+    /// Returns `true` if the given error has the type of the logged error.
+    ///
+    /// **Note:** Needed, because the [`TypeId`] of a `dyn Error` is not accessible.
+    is_type: fn(&(dyn Error + 'static)) -> bool,
+    msg: String,
+}
+
+impl ErrorIdentity {
+    pub(crate) fn of<E: Error + 'static>(error: &E) -> Self {
+        ErrorIdentity {
+            type_id: TypeId::of::<E>(),
+            is_type: |other| other.is::<E>(),
+            msg: error.to_string(),
+        }
+    }
+}
+
+impl PartialEq for ErrorIdentity {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id && self.msg == other.msg
+    }
+}
+
+impl Eq for ErrorIdentity {}
+
+/// Remembers the given error as logged by the given event on the current thread.
+pub(crate) fn remember_error(error: ErrorIdentity, event: FinalizedEvent<LogId>) {
+    let _ = LOGGED_ERRORS.try_with(|errors| {
+        let mut errors = errors.borrow_mut();
+        if errors.len() >= MAX_REMEMBERED_ERRORS {
+            errors.pop_front();
+        }
+        errors.push_back((error, event));
+    });
+}
+
+/// Returns the event that logged the nearest source of the given error on the current thread.
+pub fn find_parent<E: Error + ?Sized>(error: &E) -> Option<FinalizedEvent<LogId>> {
+    let mut source = error.source();
+
+    while let Some(err) = source {
+        if let Some(parent) = find_logged_error(err) {
+            return Some(parent);
+        }
+        source = err.source();
+    }
+
+    None
+}
+
+fn find_logged_error(error: &(dyn Error + 'static)) -> Option<FinalizedEvent<LogId>> {
+    LOGGED_ERRORS
+        .try_with(|errors| {
+            // Note: Message is only created if an error of the same type was logged
+            let mut msg = None;
+            errors
+                .borrow()
+                .iter()
+                .rev()
+                .find(|(logged, _)| {
+                    (logged.is_type)(error)
+                        && msg.get_or_insert_with(|| error.to_string()) == &logged.msg
+                })
+                .map(|(_, event)| event.clone())
+        })
+        .ok()
+        .flatten()
+}
+
+/// Information needed to link an event to the event of its source error.
+///
+/// Used by the logging macros.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct __ChainLink {
+    error: Option<ErrorIdentity>,
+    parent: Option<FinalizedEvent<LogId>>,
+}
+
+impl __ChainLink {
+    pub(crate) fn of<E: Error + 'static>(error: &E) -> Self {
+        __ChainLink {
+            error: Some(ErrorIdentity::of(error)),
+            parent: find_parent(error),
+        }
+    }
+
+    /// Sets the parent of the given event, if a logged source was found.
+    ///
+    /// If the event is set for an error, the error is remembered once the event passes the filter.
+    pub fn link(self, event: IntermediaryLogEvent) -> IntermediaryLogEvent {
+        let event = match self.parent {
+            Some(parent) => event.add_addon(AddonKind::Parent(parent)),
+            None => event,
+        };
+
+        match self.error {
+            Some(error) => event.remember_error(error),
+            None => event,
+        }
+    }
+}
+
+/// Wrapper to only create chain links for values implementing [`std::error::Error`].
+#[doc(hidden)]
+pub struct __ErrorChain<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __ViaError {
+    fn __chain_link(&self) -> __ChainLink;
+}
+
+impl<T: Error + 'static> __ViaError for __ErrorChain<'_, T> {
+    fn __chain_link(&self) -> __ChainLink {
+        __ChainLink::of(self.0)
+    }
+}
+
+// Note: Implemented for the reference, so `__ViaError` is preferred by method resolution if `T` implements `Error`
+#[doc(hidden)]
+pub trait __ViaAny {
+    fn __chain_link(&self) -> __ChainLink;
+}
+
+impl<T> __ViaAny for &__ErrorChain<'_, T> {
+    fn __chain_link(&self) -> __ChainLink {
+        __ChainLink::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use evident::event::entry::EventEntry;
+
+    use super::*;
+    use crate::{
+        log_id::LogLevel,
+        logging::{event_entry::LogEventEntry, msg::NO_MSG},
+        new_log_id,
+    };
+
+    #[derive(Debug)]
+    struct WrappingError(std::io::Error);
+
+    impl std::fmt::Display for WrappingError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Wrapping error.")
+        }
+    }
+
+    impl std::error::Error for WrappingError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    fn finalized(log_id: LogId) -> FinalizedEvent<LogId> {
+        let entry = LogEventEntry::new(log_id, NO_MSG, evident::this_origin!());
+        FinalizedEvent::new(log_id, entry.get_entry_id())
+    }
+
+    #[test]
+    fn remembered_source_is_parent() {
+        let log_id = new_log_id!("io_error", LogLevel::Error);
+        let io_event = finalized(log_id);
+        let io_error = std::io::Error::other("Disk full.");
+
+        remember_error(ErrorIdentity::of(&io_error), io_event.clone());
+
+        assert_eq!(
+            find_parent(&WrappingError(io_error)),
+            Some(io_event),
+            "Logged source not found as parent."
+        );
+    }
+
+    #[test]
+    fn source_of_other_type_is_no_parent() {
+        let log_id = new_log_id!("fmt_error", LogLevel::Error);
+        let fmt_error = std::fmt::Error;
+
+        remember_error(ErrorIdentity::of(&fmt_error), finalized(log_id));
+
+        assert_eq!(
+            find_parent(&WrappingError(std::io::Error::other(fmt_error.to_string()))),
+            None,
+            "Source with same message, but other type found as parent."
+        );
+    }
+
+    #[test]
+    fn unknown_source_has_no_parent() {
+        let io_error = std::io::Error::other("Never logged.");
+
+        assert_eq!(
+            find_parent(&WrappingError(io_error)),
+            None,
+            "Parent found for source that was never logged."
+        );
+    }
+
+    // Note: Borrow is needed to resolve the methods the same way as in the logging macros
+    #[allow(clippy::needless_borrow)]
+    #[test]
+    fn chain_link_only_for_errors() {
+        let io_error = std::io::Error::other("Some error.");
+        let log_id = new_log_id!("no_error", LogLevel::Info);
+
+        assert!(
+            (&__ErrorChain(&io_error)).__chain_link().error.is_some(),
+            "Error not remembered."
+        );
+        assert!(
+            (&__ErrorChain(&log_id)).__chain_link().error.is_none(),
+            "Non-error value remembered."
+        );
+    }
+}
@@ -3,7 +3,7 @@ use evident::event::finalized::FinalizedEvent;
 use crate::evident::event::origin::Origin;
 use crate::log_id::{LogId, LogLevel};
 
-use super::msg::LogMsg;
+use super::{field::LogField, msg::LogMsg};
 
 #[cfg(feature = "fmt")]
 use super::msg::FmtMsg;
@@ -31,8 +31,12 @@ pub struct LogEventEntry {
     /// List of additional formatted trace information for this log-id entry
     #[cfg(feature = "fmt")]
     pub(crate) fmt_traces: Vec<FmtMsg>,
+    /// List of structured key-value fields for this log-id entry
+    pub(crate) fields: Vec<LogField>,
     /// List of related log-id event entries
     pub(crate) related: Vec<FinalizedEvent<LogId>>,
+    /// Log-id event that caused this log-id entry
+    pub(crate) parent: Option<FinalizedEvent<LogId>>,
     /// Code position where the log-id entry was created
     pub(crate) origin: Origin,
 
@@ -73,7 +77,9 @@ impl crate::evident::event::entry::EventEntry<LogId, LogMsg> for LogEventEntry {
             infos: Vec::new(),
             debugs: Vec::new(),
             traces: Vec::new(),
+            fields: Vec::new(),
             related: Vec::new(),
+            parent: None,
             origin,
 
             #[cfg(feature = "fmt")]
@@ -125,7 +131,81 @@ impl crate::evident::event::entry::EventEntry<LogId, LogMsg> for LogEventEntry {
     }
 }
 
+impl PartialEq for LogEventEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.event_id == other.event_id && self.entry_id == other.entry_id
+    }
+}
+
+impl Eq for LogEventEntry {}
+
+impl std::hash::Hash for LogEventEntry {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.entry_id.hash(state);
+    }
+}
+
 impl LogEventEntry {
+    /// Restores an entry with its original entry ID and addons, e.g. if entries are read from written logs.
+    ///
+    /// Addons are added without checking any filter, because the filter was already applied when the entry was captured.
+    /// Events that are not yet captured must be created using the [`IntermediaryLogEvent`](super::intermediary_event::IntermediaryLogEvent) instead.
+    pub fn restore(
+        event_id: LogId,
+        entry_id: crate::evident::uuid::Uuid,
+        msg: Option<LogMsg>,
+        origin: Origin,
+        addons: impl IntoIterator<Item = AddonKind>,
+    ) -> Self {
+        let mut entry = <LogEventEntry as crate::evident::event::entry::EventEntry<
+            LogId,
+            LogMsg,
+        >>::new(event_id, msg, origin);
+        entry.entry_id = entry_id;
+        for addon in addons {
+            entry.push_addon(addon);
+        }
+        entry
+    }
+
+    /// Adds the given addon to this entry without checking any filter.
+    pub(crate) fn push_addon(&mut self, kind: AddonKind) {
+        match kind {
+            AddonKind::Info(msg) => self.infos.push(msg),
+            AddonKind::Debug(msg) => self.debugs.push(msg),
+            AddonKind::Trace(msg) => self.traces.push(msg),
+            AddonKind::Field(field) => self.fields.push(field),
+            AddonKind::Related(finalized_event) => self.related.push(finalized_event),
+            AddonKind::Parent(finalized_event) => self.parent = Some(finalized_event),
+
+            #[cfg(feature = "fmt")]
+            AddonKind::FmtInfo(fmt_msg) => self.fmt_infos.push(fmt_msg),
+            #[cfg(feature = "fmt")]
+            AddonKind::FmtDebug(fmt_msg) => self.fmt_debugs.push(fmt_msg),
+            #[cfg(feature = "fmt")]
+            AddonKind::FmtTrace(fmt_msg) => self.fmt_traces.push(fmt_msg),
+
+            #[cfg(feature = "hint_note")]
+            AddonKind::Hint(msg) => self.hints.push(msg),
+            #[cfg(all(feature = "hint_note", feature = "fmt"))]
+            AddonKind::FmtHint(fmt_msg) => self.fmt_hints.push(fmt_msg),
+            #[cfg(feature = "hint_note")]
+            AddonKind::Note(msg) => self.notes.push(msg),
+            #[cfg(all(feature = "hint_note", feature = "fmt"))]
+            AddonKind::FmtNote(fmt_msg) => self.fmt_notes.push(fmt_msg),
+
+            #[cfg(feature = "diagnostics")]
+            AddonKind::Diagnostic(diag) => self.diagnostics.push(diag),
+            #[cfg(all(feature = "diagnostics", feature = "fmt"))]
+            AddonKind::FmtDiagnostic(fmt_diag) => self.fmt_diagnostics.push(fmt_diag),
+
+            #[cfg(feature = "payloads")]
+            AddonKind::Payload(payload) => self.payloads.push(payload),
+            #[cfg(all(feature = "payloads", feature = "fmt"))]
+            AddonKind::FmtPayload(fmt_payload) => self.fmt_payloads.push(fmt_payload),
+        }
+    }
+
     /// Get the level of the log-id of this entry
     pub fn get_level(&self) -> LogLevel {
         self.event_id.log_level
@@ -163,10 +243,22 @@ impl LogEventEntry {
     pub fn get_fmt_traces(&self) -> &Vec<FmtMsg> {
         &self.fmt_traces
     }
+    /// Get the list of structured key-value fields for this log-id entry
+    pub fn get_fields(&self) -> &Vec<LogField> {
+        &self.fields
+    }
+    /// Get the first field with the given key for this log-id entry
+    pub fn get_field(&self, key: &str) -> Option<&LogField> {
+        self.fields.iter().find(|field| field.get_key() == key)
+    }
     /// Get the list of related log-id event entries
     pub fn get_related(&self) -> &Vec<FinalizedEvent<LogId>> {
         &self.related
     }
+    /// Get the log-id event that caused this log-id entry
+    pub fn get_parent(&self) -> Option<&FinalizedEvent<LogId>> {
+        self.parent.as_ref()
+    }
 
     #[cfg(feature = "hint_note")]
     pub fn get_hints(&self) -> &Vec<String> {
@@ -211,7 +303,11 @@ pub enum AddonKind {
     Info(String),
     Debug(String),
     Trace(String),
+    Field(LogField),
     Related(FinalizedEvent<LogId>),
+    /// Sets the log-id event that caused this event.
+    /// Setting a parent again replaces the previous one.
+    Parent(FinalizedEvent<LogId>),
 
     #[cfg(feature = "fmt")]
     FmtInfo(FmtMsg),
@@ -259,6 +355,11 @@ impl FmtDiagnostics {
     pub fn get_data(&self) -> &crate::lsp_types::Diagnostic {
         &self.data
     }
+
+    #[cfg(feature = "redaction")]
+    pub(crate) fn redact(&mut self, redaction: &super::redaction::Redaction) {
+        self.data.message = redaction.redact_str(&self.data.message);
+    }
 }
 
 #[cfg(all(feature = "diagnostics", feature = "fmt"))]
@@ -321,6 +422,24 @@ macro_rules! trace_addon {
     };
 }
 
+/// Macro to create a structured key-value field addon.
+///
+/// ## Usage
+///
+/// ```
+/// use logid_core::field;
+///
+/// let addon = field!("user_id", 42);
+/// ```
+#[macro_export]
+macro_rules! field {
+    ($key:expr, $value:expr) => {
+        $crate::logging::event_entry::AddonKind::Field($crate::logging::field::LogField::new(
+            $key, $value,
+        ))
+    };
+}
+
 #[cfg(feature = "hint_note")]
 #[macro_export]
 macro_rules! hint_addon {
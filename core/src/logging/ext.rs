@@ -0,0 +1,206 @@
+//! Contains extension traits to log [`Result`]s and [`Option`]s without interrupting method chains.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid_core::{
+//!     log_id::{LogId, LogLevel},
+//!     logging::ext::{LogOptionExt, LogResultExt},
+//!     new_log_id,
+//! };
+//!
+//! const MISSING_CONFIG: LogId = new_log_id!("MissingConfig", LogLevel::Warn);
+//! const INVALID_PORT: LogId = new_log_id!("InvalidPort", LogLevel::Error);
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct InvalidPort;
+//!
+//! impl std::fmt::Display for InvalidPort {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//!         write!(f, "Port must be a number.")
+//!     }
+//! }
+//!
+//! impl std::error::Error for InvalidPort {}
+//!
+//! impl From<&InvalidPort> for LogId {
+//!     fn from(_value: &InvalidPort) -> Self {
+//!         INVALID_PORT
+//!     }
+//! }
+//!
+//! fn parse_port(port: &str) -> Result<u16, InvalidPort> {
+//!     port.parse::<u16>().map_err(|_| InvalidPort).log_err()
+//! }
+//!
+//! let config: Option<&str> = None;
+//! let port = config.log_none(MISSING_CONFIG).unwrap_or("8080");
+//! assert_eq!(parse_port(port), Ok(8080));
+//! ~~~
+//!
+//! **Note:** Events are set at the caller location, but with the module path of the [`LogId`](crate::log_id::LogId),
+//! because the module path of the caller is not available at runtime.
+//! Messages and addons are only created if the filter allows the event.
+//! Addons of [`CaptureAddons`](super::capture::CaptureAddons) are not captured, because the traits are generic over the logged type.
+
+use std::panic::Location;
+
+use evident::event::{intermediary::IntermediaryEvent, origin::Origin};
+
+use crate::log_id::LogId;
+
+use super::{
+    chain::__ChainLink, event_entry::AddonKind, filter::allow_event_for,
+    intermediary_event::IntermediaryLogEvent, msg::LogMsg,
+};
+
+/// Extension trait to log the error of a [`Result`].
+pub trait LogResultExt<T, E> {
+    /// Logs the error, if any, and returns the result unchanged.
+    ///
+    /// The [`LogId`] is converted from a reference to the error, and the message is the error message.
+    /// If an error of the source chain of the error was logged before on the same thread,
+    /// the event of the nearest logged source is set as parent.
+    fn log_err(self) -> Self;
+
+    /// Logs the error like [`log_err`](LogResultExt::log_err), and adds the addons returned by the given closure.
+    ///
+    /// The closure is only called if the result is an error, and the filter allows the event.
+    fn log_err_with<F, A>(self, addons: F) -> Self
+    where
+        F: FnOnce(&E) -> A,
+        A: IntoIterator<Item = AddonKind>;
+
+    /// Logs the log-id returned by the given closure, if the result is ok, and returns the result unchanged.
+    ///
+    /// The message of the event is the [`Display`](std::fmt::Display) output of the returned value.
+    fn inspect_log<F, L>(self, f: F) -> Self
+    where
+        F: FnOnce(&T) -> L,
+        L: std::fmt::Display,
+        for<'a> &'a L: Into<LogId>;
+}
+
+impl<T, E> LogResultExt<T, E> for Result<T, E>
+where
+    E: std::error::Error + 'static,
+    for<'a> &'a E: Into<LogId>,
+{
+    #[track_caller]
+    fn log_err(self) -> Self {
+        self.log_err_with(|_| [])
+    }
+
+    #[track_caller]
+    fn log_err_with<F, A>(self, addons: F) -> Self
+    where
+        F: FnOnce(&E) -> A,
+        A: IntoIterator<Item = AddonKind>,
+    {
+        if let Err(error) = &self {
+            let id: LogId = error.into();
+            if let Some(origin) = allowed_origin(id, Location::caller()) {
+                log_error(error, id, addons(error), origin);
+            }
+        }
+        self
+    }
+
+    #[track_caller]
+    fn inspect_log<F, L>(self, f: F) -> Self
+    where
+        F: FnOnce(&T) -> L,
+        L: std::fmt::Display,
+        for<'a> &'a L: Into<LogId>,
+    {
+        if let Ok(value) = &self {
+            log_value(&f(value), Location::caller());
+        }
+        self
+    }
+}
+
+/// Extension trait to log missing values of an [`Option`].
+pub trait LogOptionExt<T> {
+    /// Logs the given log-id, if the option is `None`, and returns the option unchanged.
+    ///
+    /// The message of the event is the [`Display`](std::fmt::Display) output of the given value.
+    fn log_none<L>(self, id: L) -> Self
+    where
+        L: std::fmt::Display,
+        for<'a> &'a L: Into<LogId>;
+
+    /// Logs the log-id returned by the given closure, if the option is `Some`, and returns the option unchanged.
+    ///
+    /// The message of the event is the [`Display`](std::fmt::Display) output of the returned value.
+    fn inspect_log<F, L>(self, f: F) -> Self
+    where
+        F: FnOnce(&T) -> L,
+        L: std::fmt::Display,
+        for<'a> &'a L: Into<LogId>;
+}
+
+impl<T> LogOptionExt<T> for Option<T> {
+    #[track_caller]
+    fn log_none<L>(self, id: L) -> Self
+    where
+        L: std::fmt::Display,
+        for<'a> &'a L: Into<LogId>,
+    {
+        if self.is_none() {
+            log_value(&id, Location::caller());
+        }
+        self
+    }
+
+    #[track_caller]
+    fn inspect_log<F, L>(self, f: F) -> Self
+    where
+        F: FnOnce(&T) -> L,
+        L: std::fmt::Display,
+        for<'a> &'a L: Into<LogId>,
+    {
+        if let Some(value) = &self {
+            log_value(&f(value), Location::caller());
+        }
+        self
+    }
+}
+
+/// Returns the origin of an event of the given [`LogId`] set at the given location,
+/// or `None` if the filter of the [`LOGGER`](super::LOGGER) rejects the event.
+///
+/// The module path of the [`LogId`] is used, because the module path of the caller is not available at runtime.
+fn allowed_origin(id: LogId, location: &Location<'static>) -> Option<Origin> {
+    let origin = Origin::new(id.get_module_path(), location.file(), location.line());
+    allow_event_for(None, id, &origin).then_some(origin)
+}
+
+/// Logs the given error with the given origin, which must be allowed by [`allowed_origin()`].
+///
+/// **Note:** The origin is checked first, so filtered out errors are not formatted and not linked to their sources.
+fn log_error<E>(error: &E, id: LogId, addons: impl IntoIterator<Item = AddonKind>, origin: Origin)
+where
+    E: std::error::Error + 'static,
+{
+    let msg = error.to_string();
+    let mut event = __ChainLink::of(error).link(IntermediaryLogEvent::new(id, Some(msg), origin));
+
+    for addon in addons {
+        event = event.add_addon(addon);
+    }
+
+    event.finalize();
+}
+
+fn log_value<L>(value: &L, location: &Location<'static>)
+where
+    L: std::fmt::Display,
+    for<'a> &'a L: Into<LogId>,
+{
+    let id: LogId = value.into();
+    if let Some(origin) = allowed_origin(id, location) {
+        let msg: LogMsg = value.to_string().into();
+        IntermediaryLogEvent::new(id, Some(msg), origin).finalize();
+    }
+}
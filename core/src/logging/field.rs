@@ -0,0 +1,207 @@
+//! Contains the [`LogField`] struct used to add structured key-value data to log-id events.
+
+/// A structured key-value field that may be added to an [`EventEntry`](crate::evident::event::entry::EventEntry).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogField {
+    key: String,
+    value: FieldValue,
+}
+
+impl LogField {
+    pub fn new(key: impl Into<String>, value: impl Into<FieldValue>) -> Self {
+        LogField {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn get_value(&self) -> &FieldValue {
+        &self.value
+    }
+
+    #[cfg(feature = "redaction")]
+    pub(crate) fn redact(&mut self, redaction: &super::redaction::Redaction) {
+        if let FieldValue::Str(value) = &mut self.value {
+            *value = redaction.redact_str(value);
+        }
+    }
+}
+
+impl std::fmt::Display for LogField {
+    /// Formats the field as `<key>=<value>`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+/// Typed value of a [`LogField`].
+#[derive(Debug, Clone)]
+pub enum FieldValue {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    Duration(std::time::Duration),
+    Timestamp(std::time::SystemTime),
+    Bytes(Vec<u8>),
+}
+
+impl FieldValue {
+    /// Returns the value as `f64` if it is numeric.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::Int(v) => Some(*v as f64),
+            FieldValue::UInt(v) => Some(*v as f64),
+            FieldValue::Float(v) => Some(*v),
+            FieldValue::Duration(v) => Some(v.as_secs_f64()),
+            FieldValue::Timestamp(v) => Some(unix_secs(v)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as `&str` if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FieldValue::Str(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Int(v) => write!(f, "{v}"),
+            FieldValue::UInt(v) => write!(f, "{v}"),
+            FieldValue::Float(v) => write!(f, "{v}"),
+            FieldValue::Bool(v) => write!(f, "{v}"),
+            FieldValue::Str(v) => write!(f, "{v:?}"),
+            FieldValue::Duration(v) => write!(f, "{v:?}"),
+            FieldValue::Timestamp(v) => write!(f, "{:.6}", unix_secs(v)),
+            FieldValue::Bytes(v) => {
+                write!(f, "0x")?;
+                for byte in v {
+                    write!(f, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PartialEq for FieldValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FieldValue::Int(a), FieldValue::Int(b)) => a == b,
+            (FieldValue::UInt(a), FieldValue::UInt(b)) => a == b,
+            // Note: Comparing bits to keep `Eq` valid for `NaN`
+            (FieldValue::Float(a), FieldValue::Float(b)) => a.to_bits() == b.to_bits(),
+            (FieldValue::Bool(a), FieldValue::Bool(b)) => a == b,
+            (FieldValue::Str(a), FieldValue::Str(b)) => a == b,
+            (FieldValue::Duration(a), FieldValue::Duration(b)) => a == b,
+            (FieldValue::Timestamp(a), FieldValue::Timestamp(b)) => a == b,
+            (FieldValue::Bytes(a), FieldValue::Bytes(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FieldValue {}
+
+/// Returns seconds since [`UNIX_EPOCH`](std::time::UNIX_EPOCH), being negative for earlier timestamps.
+fn unix_secs(timestamp: &std::time::SystemTime) -> f64 {
+    match timestamp.duration_since(std::time::UNIX_EPOCH) {
+        Ok(since_epoch) => since_epoch.as_secs_f64(),
+        Err(err) => -err.duration().as_secs_f64(),
+    }
+}
+
+macro_rules! impl_from_for_field_value {
+    ($variant:ident, $target:ty, $($t:ty),+) => {
+        $(
+            impl From<$t> for FieldValue {
+                fn from(value: $t) -> Self {
+                    FieldValue::$variant(value as $target)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_for_field_value!(Int, i64, i8, i16, i32, i64, isize);
+impl_from_for_field_value!(UInt, u64, u8, u16, u32, u64, usize);
+impl_from_for_field_value!(Float, f64, f32, f64);
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        FieldValue::Str(value)
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(value: &str) -> Self {
+        FieldValue::Str(value.to_string())
+    }
+}
+
+impl From<std::time::Duration> for FieldValue {
+    fn from(value: std::time::Duration) -> Self {
+        FieldValue::Duration(value)
+    }
+}
+
+impl From<std::time::SystemTime> for FieldValue {
+    fn from(value: std::time::SystemTime) -> Self {
+        FieldValue::Timestamp(value)
+    }
+}
+
+impl From<Vec<u8>> for FieldValue {
+    fn from(value: Vec<u8>) -> Self {
+        FieldValue::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for FieldValue {
+    fn from(value: &[u8]) -> Self {
+        FieldValue::Bytes(value.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_displayed_as_key_value() {
+        assert_eq!(LogField::new("user_id", 42).to_string(), "user_id=42");
+        assert_eq!(LogField::new("name", "bob").to_string(), "name=\"bob\"");
+        assert_eq!(
+            LogField::new("data", vec![0x0a, 0xff]).to_string(),
+            "data=0x0aff"
+        );
+    }
+
+    #[test]
+    fn field_value_types_from_primitives() {
+        assert_eq!(FieldValue::from(-1i32), FieldValue::Int(-1));
+        assert_eq!(FieldValue::from(1u8), FieldValue::UInt(1));
+        assert_eq!(FieldValue::from(1.5f32), FieldValue::Float(1.5));
+        assert_eq!(FieldValue::from(true), FieldValue::Bool(true));
+        assert_eq!(
+            FieldValue::from(std::time::Duration::from_millis(5)),
+            FieldValue::Duration(std::time::Duration::from_millis(5))
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use std::{any::Any, sync::Arc};
+
+use evident::event::entry::EventEntry;
+
+use crate::{
+    log_id::LogId,
+    logging::{event_entry::LogEventEntry, field::FieldValue, msg::LogMsg},
+};
+
+use super::FilterError;
+
+/// Filter on the content of an event entry.
+///
+/// Content filters are evaluated once an event is finalized, because only then all information of the entry is available.
+/// Multiple content filters set for the same rule must all match for the rule to allow an event.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContentFilter {
+    /// Predicate parsed from a filter string (e.g. `{status >= 500}`).
+    Predicate(ContentPredicate),
+    /// Custom predicate set via closure.
+    Custom(EntryPredicate),
+}
+
+impl ContentFilter {
+    /// Creates a content filter from a custom predicate.
+    pub fn custom(predicate: impl Fn(&LogEventEntry) -> bool + Send + Sync + 'static) -> Self {
+        ContentFilter::Custom(EntryPredicate(Arc::new(predicate)))
+    }
+
+    pub fn matches(&self, entry: &LogEventEntry) -> bool {
+        match self {
+            ContentFilter::Predicate(predicate) => predicate.matches(entry),
+            ContentFilter::Custom(predicate) => (predicate.0)(entry),
+        }
+    }
+}
+
+impl TryFrom<&str> for ContentFilter {
+    type Error = FilterError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(ContentFilter::Predicate(ContentPredicate::try_from(value)?))
+    }
+}
+
+impl IntoIterator for ContentFilter {
+    type Item = Self;
+
+    type IntoIter = std::iter::Once<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+/// Wrapper around a closure used as [`ContentFilter`].
+///
+/// **Note:** Two predicates are only equal if they point to the same closure.
+#[derive(Clone)]
+pub struct EntryPredicate(Arc<dyn Fn(&LogEventEntry) -> bool + Send + Sync>);
+
+impl EntryPredicate {
+    fn addr(&self) -> usize {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+impl std::fmt::Debug for EntryPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EntryPredicate({:#x})", self.addr())
+    }
+}
+
+impl PartialEq for EntryPredicate {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for EntryPredicate {}
+
+impl PartialOrd for EntryPredicate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EntryPredicate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+/// Content an [`ContentPredicate`] is evaluated on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContentTarget {
+    /// The main message of the entry.
+    Msg,
+    /// A field with the given key, or a member of a payload object.
+    /// Payloads may also be accessed using a JSON pointer (e.g. `/response/status`).
+    Key(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompareOp {
+    Exists,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+}
+
+/// Predicate on the content of an entry of the form `<target> <op> <value>`.
+///
+/// Supported operators are `==` (or `=`), `!=`, `>`, `>=`, `<`, `<=`, and `~` (contains).
+/// Only the target may be set, to check if a field or payload member exists.
+///
+/// Values containing separators like `,`, `&`, or `}` must be quoted (e.g. `{msg ~ "a, b"}`).
+/// Inside quotes, `\"` and `\\` are used for `"` and `\`.
+///
+/// **Note:** Numeric comparisons are done if both sides are numbers, otherwise values are compared as strings.
+/// Strings are compared lexicographically by bytes, so `"10" < "9"` and `"B" < "a"`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ContentPredicate {
+    target: ContentTarget,
+    op: CompareOp,
+    value: String,
+}
+
+impl ContentPredicate {
+    pub fn matches(&self, entry: &LogEventEntry) -> bool {
+        match &self.target {
+            ContentTarget::Msg => match entry.get_msg() {
+                Some(msg) => self.compare(&ContentValue::Str(msg.to_string())),
+                None => false,
+            },
+            ContentTarget::Key(key) => {
+                if let Some(field) = entry.get_field(key) {
+                    return self.compare(&ContentValue::from(field.get_value()));
+                }
+
+                #[cfg(feature = "payloads")]
+                for payload in entry.get_payloads() {
+                    let member = if key.starts_with('/') {
+                        payload.pointer(key)
+                    } else {
+                        payload.get(key)
+                    };
+
+                    if let Some(value) = member {
+                        return self.compare(&ContentValue::from(value));
+                    }
+                }
+
+                false
+            }
+        }
+    }
+
+    fn compare(&self, actual: &ContentValue) -> bool {
+        if self.op == CompareOp::Exists {
+            return true;
+        }
+
+        if let (ContentValue::Num(actual), Ok(expected)) = (actual, self.value.parse::<f64>()) {
+            return match self.op {
+                CompareOp::Eq => *actual == expected,
+                CompareOp::Ne => *actual != expected,
+                CompareOp::Gt => *actual > expected,
+                CompareOp::Ge => *actual >= expected,
+                CompareOp::Lt => *actual < expected,
+                CompareOp::Le => *actual <= expected,
+                CompareOp::Contains => actual.to_string().contains(&self.value),
+                CompareOp::Exists => true,
+            };
+        }
+
+        let actual = actual.to_string();
+        match self.op {
+            CompareOp::Eq => actual == self.value,
+            CompareOp::Ne => actual != self.value,
+            CompareOp::Gt => actual.as_str() > self.value.as_str(),
+            CompareOp::Ge => actual.as_str() >= self.value.as_str(),
+            CompareOp::Lt => actual.as_str() < self.value.as_str(),
+            CompareOp::Le => actual.as_str() <= self.value.as_str(),
+            CompareOp::Contains => actual.contains(&self.value),
+            CompareOp::Exists => true,
+        }
+    }
+}
+
+/// Returns the content of a quoted value without escapes, given the value without its opening quote.
+///
+/// Returns `None` if the closing quote is missing, or followed by more characters.
+fn unquote(quoted: &str) -> Option<String> {
+    let mut unquoted = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.push(chars.next()?),
+            '"' => return chars.as_str().is_empty().then_some(unquoted),
+            c => unquoted.push(c),
+        }
+    }
+
+    None
+}
+
+impl TryFrom<&str> for ContentPredicate {
+    type Error = FilterError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let predicate = value.trim();
+        let op_start = predicate.find(['=', '!', '<', '>', '~']);
+
+        let (target_part, op, value_part) = match op_start {
+            Some(start) => {
+                let rest = &predicate[start..];
+                let (op, op_len) = if rest.starts_with(">=") {
+                    (CompareOp::Ge, 2)
+                } else if rest.starts_with("<=") {
+                    (CompareOp::Le, 2)
+                } else if rest.starts_with("!=") {
+                    (CompareOp::Ne, 2)
+                } else if rest.starts_with("==") {
+                    (CompareOp::Eq, 2)
+                } else if rest.starts_with('=') {
+                    (CompareOp::Eq, 1)
+                } else if rest.starts_with('>') {
+                    (CompareOp::Gt, 1)
+                } else if rest.starts_with('<') {
+                    (CompareOp::Lt, 1)
+                } else if rest.starts_with('~') {
+                    (CompareOp::Contains, 1)
+                } else {
+                    return Err(FilterError::ParsingContent(value.to_string()));
+                };
+
+                (&predicate[..start], op, &rest[op_len..])
+            }
+            None => (predicate, CompareOp::Exists, ""),
+        };
+
+        let target = match target_part.trim() {
+            "" => return Err(FilterError::ParsingContent(value.to_string())),
+            "msg" => ContentTarget::Msg,
+            key => ContentTarget::Key(key.to_string()),
+        };
+
+        let value_part = value_part.trim();
+        let value_part = match value_part.strip_prefix('"') {
+            Some(quoted) => {
+                unquote(quoted).ok_or(FilterError::ParsingContent(value.to_string()))?
+            }
+            None if value_part.contains('"') => {
+                return Err(FilterError::ParsingContent(value.to_string()))
+            }
+            None => value_part.to_string(),
+        };
+
+        if op != CompareOp::Exists && value_part.is_empty() {
+            return Err(FilterError::ParsingContent(value.to_string()));
+        }
+
+        Ok(ContentPredicate {
+            target,
+            op,
+            value: value_part,
+        })
+    }
+}
+
+enum ContentValue {
+    Num(f64),
+    Str(String),
+}
+
+impl std::fmt::Display for ContentValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentValue::Num(v) => write!(f, "{v}"),
+            ContentValue::Str(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl From<&FieldValue> for ContentValue {
+    fn from(value: &FieldValue) -> Self {
+        match value {
+            FieldValue::Str(v) => ContentValue::Str(v.clone()),
+            _ => match value.as_f64() {
+                Some(v) => ContentValue::Num(v),
+                None => ContentValue::Str(value.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "payloads")]
+impl From<&crate::serde_json::Value> for ContentValue {
+    fn from(value: &crate::serde_json::Value) -> Self {
+        match value {
+            crate::serde_json::Value::Number(v) => match v.as_f64() {
+                Some(v) => ContentValue::Num(v),
+                None => ContentValue::Str(v.to_string()),
+            },
+            crate::serde_json::Value::String(v) => ContentValue::Str(v.clone()),
+            _ => ContentValue::Str(value.to_string()),
+        }
+    }
+}
+
+/// Returns `true` if all given content filters match the given entry.
+///
+/// **Note:** If the entry is not a [`LogEventEntry`], only an empty list of content filters matches.
+pub(super) fn content_matches(
+    filters: &[ContentFilter],
+    entry: &impl EventEntry<LogId, LogMsg>,
+) -> bool {
+    if filters.is_empty() {
+        return true;
+    }
+
+    match (entry as &dyn Any).downcast_ref::<LogEventEntry>() {
+        Some(log_entry) => filters.iter().all(|filter| filter.matches(log_entry)),
+        None => false,
+    }
+}
+
+/// Extracts content filters set between `{}`, and removes them from the given string.
+///
+/// Content filters are separated by `&`.
+/// An error is returned if any content filter is invalid, because ignoring it would allow more events than intended.
+pub(super) fn get_content_filters(s: &mut String) -> Result<Vec<ContentFilter>, FilterError> {
+    let mut filters = Vec::new();
+
+    let Some(content_start) = find_unquoted(s, '{') else {
+        return Ok(filters);
+    };
+    let Some(content_end) = find_unquoted(&s[content_start..], '}').map(|end| content_start + end)
+    else {
+        return Err(FilterError::ParsingContent(s[content_start..].to_string()));
+    };
+
+    for predicate_part in split_unquoted(&s[(content_start + 1)..content_end], '&') {
+        filters.push(ContentFilter::try_from(predicate_part)?);
+    }
+
+    s.replace_range(content_start..(content_end + 1), "");
+    Ok(filters)
+}
+
+/// Returns the position of the first given character that is not inside a quoted value.
+pub(super) fn find_unquoted(s: &str, c: char) -> Option<usize> {
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, curr) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if quoted && curr == '\\' {
+            escaped = true;
+        } else if curr == '"' {
+            quoted = !quoted;
+        } else if !quoted && curr == c {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+/// Splits the given string at the given separator, ignoring separators inside quoted values and brackets.
+pub(super) fn split_unquoted(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut part_start = 0;
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, curr) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if quoted {
+            match curr {
+                '\\' => escaped = true,
+                '"' => quoted = false,
+                _ => {}
+            }
+        } else {
+            match curr {
+                '"' => quoted = true,
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' => depth = depth.saturating_sub(1),
+                curr if curr == sep && depth == 0 => {
+                    parts.push(&s[part_start..i]);
+                    part_start = i + curr.len_utf8();
+                }
+                _ => {}
+            }
+        }
+    }
+    parts.push(&s[part_start..]);
+
+    parts
+}
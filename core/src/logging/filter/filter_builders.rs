@@ -1,12 +1,13 @@
-use crate::log_id::LogLevel;
+use crate::{log_id::LogLevel, logging::event_entry::LogEventEntry};
 
-use super::{AddonFilter, FilterConfig, LogIdAddonFilter, LogIdModuleFilter};
+use super::{AddonFilter, ContentFilter, FilterConfig, LogIdAddonFilter, LogIdModuleFilter};
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FilterConfigBuilder {
     general_logging_enabled: bool,
     general_level: LogLevel,
     general_addons: Vec<AddonFilter>,
+    general_content_filters: Vec<ContentFilter>,
     /// LogIds set with `on[LogId]`
     allowed_global_ids: Vec<LogIdAddonFilter>,
     allowed_modules: Vec<LogIdModuleFilter>,
@@ -39,6 +40,25 @@ impl FilterConfigBuilder {
         self
     }
 
+    /// Add content filters that must match for events to be allowed by the general log level.
+    pub fn content_filters<I>(mut self, filters: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ContentFilter>,
+    {
+        self.general_content_filters
+            .extend(filters.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add a closure that must return `true` for events to be allowed by the general log level.
+    pub fn content_predicate(
+        self,
+        predicate: impl Fn(&LogEventEntry) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.content_filters(ContentFilter::custom(predicate))
+    }
+
     /// Add LogIDs allowed by the filter.
     pub fn global_ids<I>(mut self, global_ids: I) -> Self
     where
@@ -67,6 +87,7 @@ impl FilterConfigBuilder {
             general_logging_enabled: self.general_logging_enabled,
             general_level: self.general_level,
             general_addons: self.general_addons,
+            general_content_filters: self.general_content_filters,
             allowed_global_ids: self.allowed_global_ids,
             allowed_modules: self.allowed_modules,
         }
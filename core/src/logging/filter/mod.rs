@@ -1,13 +1,18 @@
 use std::sync::{Arc, RwLock};
 
-use evident::event::origin::Origin;
+use evident::event::{entry::EventEntry, origin::Origin};
 
 use crate::log_id::{LogId, LogLevel};
 
-use super::{event_entry::AddonKind, msg::LogMsg};
+use super::{
+    event_entry::{AddonKind, LogEventEntry},
+    msg::LogMsg,
+};
 
+mod content_filter;
 mod filter_builders;
 
+pub use content_filter::*;
 pub use filter_builders::*;
 
 #[derive(Default, Debug)]
@@ -40,7 +45,13 @@ impl LogFilter {
         }
     }
 
-    pub fn set_filter(&self, filter_config: FilterConfig) -> Result<(), FilterError> {
+    /// Replaces the filter configuration, or returns an error if the given filter is invalid.
+    pub fn set_filter<T>(&self, into_filter: T) -> Result<(), FilterError>
+    where
+        T: IntoFilterConfig,
+    {
+        let filter_config = into_filter.into_filter_config()?;
+
         match self.filter.write() {
             Ok(mut locked_filter) => {
                 locked_filter.replace(filter_config);
@@ -55,18 +66,53 @@ impl LogFilter {
     }
 
     pub fn allow_addon(&self, id: LogId, origin: &Origin, addon: &AddonKind) -> bool {
+        self.allow_addon_filter(id, origin, &AddonFilter::from(addon))
+    }
+
+    /// Returns `true` if addons of the given kind are allowed for the given log-id and origin.
+    ///
+    /// Allows to check addons before they are created.
+    pub fn allow_addon_filter(&self, id: LogId, origin: &Origin, addon: &AddonFilter) -> bool {
+        if !allow_level(id.log_level) {
+            return false;
+        }
+
+        if *addon == AddonFilter::Debugs && !allow_level(LogLevel::Debug)
+            || *addon == AddonFilter::Traces && !allow_level(LogLevel::Trace)
+        {
+            return false;
+        }
+
+        match self.filter.read() {
+            Ok(locked_filter) => locked_filter.allow_addon_filter(id, origin, addon),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns `false` if events for the given log-id and origin are rejected regardless of their content.
+    ///
+    /// Used to skip building messages and addons of events that would be filtered out.
+    pub fn allow_event(&self, id: LogId, origin: &Origin) -> bool {
         if !allow_level(id.log_level) {
             return false;
-        } else if let Ok(addon_filter) = AddonFilter::try_from(addon) {
-            if addon_filter == AddonFilter::Debugs && !allow_level(LogLevel::Debug)
-                || addon_filter == AddonFilter::Traces && !allow_level(LogLevel::Trace)
-            {
-                return false;
-            }
         }
 
         match self.filter.read() {
-            Ok(locked_filter) => locked_filter.allow_addon(id, origin, addon),
+            Ok(locked_filter) => locked_filter.allow_event(id, origin),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns `true` if the given entry passes the filter, including its content filters and predicates.
+    pub fn allow_log_entry(&self, entry: &impl EventEntry<LogId, LogMsg>) -> bool {
+        if !allow_level(entry.get_event_id().log_level) {
+            return false;
+        }
+
+        match self.filter.read() {
+            Ok(locked_filter) => {
+                evident::event::filter::Filter::allow_entry(&*locked_filter, entry)
+            }
             Err(_) => false,
         }
     }
@@ -97,27 +143,45 @@ fn filter_config() -> String {
     }
 }
 
+/// Returns `false` if the filter of the [`LOGGER`](crate::logging::LOGGER) and the filters of its recorders
+/// reject events for the given log-id and origin regardless of their content.
+pub fn allow_event(id: LogId, origin: &Origin) -> bool {
+    allow_event_for(None, id, origin)
+}
+
+/// Returns `false` if the filter of the given logger, or of the [`LOGGER`](crate::logging::LOGGER) if no logger is given,
+/// and the filters of its recorders reject events for the given log-id and origin regardless of their content.
+pub fn allow_event_for(
+    target: Option<&crate::logging::logger::Logger>,
+    id: LogId,
+    origin: &Origin,
+) -> bool {
+    let allowed = match crate::logging::logger::target_filter(target) {
+        Some(filter) => filter.allow_event(id, origin),
+        None => true,
+    };
+
+    allowed || crate::logging::logger::recorders_allow_event(target, id, origin)
+}
+
 pub fn set_filter<T>(into_filter: T) -> Result<(), crate::logging::filter::FilterError>
 where
-    T: Into<FilterConfig>,
+    T: IntoFilterConfig,
 {
     if let Some(filter) = crate::logging::LOGGER.get_filter() {
-        filter.set_filter(into_filter.into())
+        filter.set_filter(into_filter)
     } else {
         Err(crate::logging::filter::FilterError::SettingFilter)
     }
 }
 
+/// Only checks the log-id and origin of entries, because entries are checked with [`LogFilter::allow_log_entry()`] before they are captured.
+///
+/// **Note:** Content filters and predicates are not evaluated again on capture,
+/// so they run once per event, and redacted entries are not rejected because of their changed content.
 impl evident::event::filter::Filter<LogId, LogMsg> for LogFilter {
     fn allow_entry(&self, entry: &impl evident::event::entry::EventEntry<LogId, LogMsg>) -> bool {
-        if !allow_level(entry.get_event_id().log_level) {
-            return false;
-        }
-
-        match self.filter.read() {
-            Ok(locked_filter) => locked_filter.allow_entry(entry),
-            Err(_) => false,
-        }
+        self.allow_event(*entry.get_event_id(), entry.get_origin())
     }
 }
 
@@ -129,6 +193,7 @@ pub enum AddonFilter {
     Infos,
     Debugs,
     Traces,
+    Fields,
     Related,
     AllAllowed,
 
@@ -150,7 +215,8 @@ impl From<&AddonKind> for AddonFilter {
             AddonKind::Info(_) => AddonFilter::Infos,
             AddonKind::Debug(_) => AddonFilter::Debugs,
             AddonKind::Trace(_) => AddonFilter::Traces,
-            AddonKind::Related(_) => AddonFilter::Related,
+            AddonKind::Field(_) => AddonFilter::Fields,
+            AddonKind::Related(_) | AddonKind::Parent(_) => AddonFilter::Related,
 
             #[cfg(feature = "fmt")]
             AddonKind::FmtInfo(_) => AddonFilter::Infos,
@@ -191,6 +257,7 @@ impl TryFrom<&str> for AddonFilter {
             "infos" => AddonFilter::Infos,
             "debugs" => AddonFilter::Debugs,
             "traces" => AddonFilter::Traces,
+            "fields" => AddonFilter::Fields,
             "related" => AddonFilter::Related,
             "all" => AddonFilter::AllAllowed,
 
@@ -228,11 +295,16 @@ impl IntoIterator for AddonFilter {
 pub struct LogIdFilter {
     module_path: String,
     identifier: String,
+    /// Code set with `#<code>` that is matched instead of module path and identifier.
+    code: Option<String>,
 }
 
 impl PartialEq<LogId> for LogIdFilter {
     fn eq(&self, other: &LogId) -> bool {
-        self.module_path == other.module_path && self.identifier == other.identifier
+        match &self.code {
+            Some(code) => other.code == Some(code.as_str()),
+            None => self.module_path == other.module_path && self.identifier == other.identifier,
+        }
     }
 }
 
@@ -240,6 +312,18 @@ impl TryFrom<&str> for LogIdFilter {
     type Error = FilterError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // Form: #<code>
+        if let Some(code) = value.trim().strip_prefix('#') {
+            if code.is_empty() || code.contains(char::is_whitespace) {
+                return Err(FilterError::ParsingLogId(value.to_string()));
+            }
+
+            return Ok(LogIdFilter {
+                code: Some(code.to_string()),
+                ..Default::default()
+            });
+        }
+
         let parts = value.split("::");
         let len = parts.clone().count();
 
@@ -275,6 +359,7 @@ impl TryFrom<&str> for LogIdFilter {
         Ok(LogIdFilter {
             module_path: module_path.to_string(),
             identifier: identifier.to_string(),
+            code: None,
         })
     }
 }
@@ -283,6 +368,51 @@ impl TryFrom<&str> for LogIdFilter {
 pub struct LogIdAddonFilter {
     log_id: LogIdFilter,
     allowed_addons: Vec<AddonFilter>,
+    content_filters: Vec<ContentFilter>,
+}
+
+impl LogIdAddonFilter {
+    /// Creates a filter allowing events of the given [`LogId`].
+    pub fn new(log_id: LogId) -> Self {
+        LogIdAddonFilter {
+            log_id: LogIdFilter {
+                module_path: log_id.module_path.to_string(),
+                identifier: log_id.identifier.to_string(),
+                code: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Add addons allowed for the [`LogId`].
+    pub fn allowed_addons<I>(mut self, addons: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<AddonFilter>,
+    {
+        self.allowed_addons
+            .extend(addons.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add content filters that must match for events of the [`LogId`] to be allowed.
+    pub fn content_filters<I>(mut self, filters: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ContentFilter>,
+    {
+        self.content_filters
+            .extend(filters.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add a closure that must return `true` for events of the [`LogId`] to be allowed.
+    pub fn content_predicate(
+        self,
+        predicate: impl Fn(&LogEventEntry) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.content_filters(ContentFilter::custom(predicate))
+    }
 }
 
 impl IntoIterator for LogIdAddonFilter {
@@ -300,11 +430,13 @@ impl TryFrom<&str> for LogIdAddonFilter {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut stripped_id = value.to_string();
+        let content_filters = get_content_filters(&mut stripped_id)?;
         let addons = get_addons(&mut stripped_id);
 
         Ok(LogIdAddonFilter {
             log_id: LogIdFilter::try_from(stripped_id.as_str())?,
             allowed_addons: addons,
+            content_filters,
         })
     }
 }
@@ -316,6 +448,7 @@ pub struct LogIdModuleFilter {
     level: LogLevel,
     allowed_ids: Vec<LogIdAddonFilter>,
     allowed_addons: Vec<AddonFilter>,
+    content_filters: Vec<ContentFilter>,
 }
 
 impl IntoIterator for LogIdModuleFilter {
@@ -342,6 +475,18 @@ impl LogIdModuleFilter {
             || id_allowed(&self.allowed_ids, id)
     }
 
+    /// Returns `true` if the given entry is allowed by this module filter, including all content filters.
+    pub fn entry_allowed(&self, entry: &impl EventEntry<LogId, LogMsg>) -> bool {
+        if !self.origin_in_module(entry.get_origin()) {
+            return false;
+        }
+
+        (!self.no_general_logging
+            && self.level <= entry.get_event_id().log_level
+            && content_matches(&self.content_filters, entry))
+            || entry_allowed(&self.allowed_ids, entry)
+    }
+
     pub fn addon_allowed(&self, id: LogId, origin: &Origin, addon: &AddonFilter) -> bool {
         if !self.origin_in_module(origin) {
             return false;
@@ -357,6 +502,7 @@ impl LogIdModuleFilter {
         s: &str,
         ids: Vec<LogIdAddonFilter>,
         addons: Vec<AddonFilter>,
+        content_filters: Vec<ContentFilter>,
     ) -> Result<Self, FilterError> {
         let mut module_filter = s.split('=');
         let len = module_filter.clone().count();
@@ -379,6 +525,7 @@ impl LogIdModuleFilter {
                 origin_module_path: module.to_string(),
                 allowed_ids: ids,
                 allowed_addons: addons,
+                content_filters,
                 ..Default::default()
             });
         }
@@ -397,6 +544,7 @@ impl LogIdModuleFilter {
             level,
             allowed_ids: ids,
             allowed_addons: addons,
+            content_filters,
         })
     }
 }
@@ -406,70 +554,116 @@ pub struct FilterConfig {
     general_logging_enabled: bool,
     general_level: LogLevel,
     general_addons: Vec<AddonFilter>,
+    general_content_filters: Vec<ContentFilter>,
     /// LogIds set with `on[LogId]`
     allowed_global_ids: Vec<LogIdAddonFilter>,
     allowed_modules: Vec<LogIdModuleFilter>,
 }
 
 impl FilterConfig {
+    /// Creates a filter configuration from the given filter string.
+    ///
+    /// **Note:** If a content filter is invalid, the whole configuration is rejected and logging is turned off,
+    /// because ignoring the content filter would allow more events than intended.
+    /// The parsing error is written to stderr in this case.
+    /// Use [`FilterConfig::try_new()`] to get the parsing error instead.
     pub fn new(filter: &str) -> Self {
+        FilterConfig::try_new(filter).unwrap_or_else(|err| {
+            eprintln!("logid: {err} Logging is turned off.");
+
+            FilterConfig {
+                general_logging_enabled: false,
+                ..Default::default()
+            }
+        })
+    }
+
+    /// Creates a filter configuration from the given filter string, or returns an error if a content filter is invalid.
+    pub fn try_new(filter: &str) -> Result<Self, FilterError> {
         if filter.trim().is_empty() || filter.to_lowercase() == "off" {
-            return FilterConfig {
+            return Ok(FilterConfig {
                 general_logging_enabled: false,
                 ..Default::default()
-            };
+            });
         }
 
         let mut log_filter = FilterConfig {
             general_logging_enabled: false,
             general_level: LogLevel::Error,
             general_addons: Vec::new(),
+            general_content_filters: Vec::new(),
             allowed_global_ids: Vec::new(),
             allowed_modules: Vec::new(),
         };
 
-        for filter_part in filter.split(',') {
+        for filter_part in split_unquoted(filter, ',') {
             let mut stripped_filter_part = filter_part.to_string();
 
-            let mut ids = get_ids(&mut stripped_filter_part);
+            let mut ids = get_ids(&mut stripped_filter_part)?;
 
             if stripped_filter_part.starts_with("on") && !ids.is_empty() {
                 log_filter.allowed_global_ids.append(&mut ids);
             } else {
+                let content_filters = get_content_filters(&mut stripped_filter_part)?;
                 let addons = get_addons(&mut stripped_filter_part);
 
                 if let Some(general_level) = try_into_log_level(stripped_filter_part.trim()) {
                     log_filter.general_logging_enabled = true;
                     log_filter.general_level = general_level;
                     log_filter.general_addons = addons;
+                    log_filter.general_content_filters = content_filters;
                 } else if let Ok(module_filter) =
-                    LogIdModuleFilter::try_from(&stripped_filter_part, ids, addons)
+                    LogIdModuleFilter::try_from(&stripped_filter_part, ids, addons, content_filters)
                 {
                     log_filter.allowed_modules.push(module_filter);
                 }
             }
         }
 
-        log_filter
+        Ok(log_filter)
     }
 
     fn replace(&mut self, other: Self) {
         self.allowed_global_ids = other.allowed_global_ids;
         self.allowed_modules = other.allowed_modules;
         self.general_addons = other.general_addons;
+        self.general_content_filters = other.general_content_filters;
         self.general_level = other.general_level;
         self.general_logging_enabled = other.general_logging_enabled;
     }
 
+    /// Returns `false` if events for the given log-id and origin are rejected regardless of their content.
+    ///
+    /// Content filters are not checked, because they need the event entry.
+    pub fn allow_event(&self, id: LogId, origin: &Origin) -> bool {
+        // Note: Must accept at least all events accepted by `allow_entry()`
+        if origin.module_path.starts_with("logid::event_handler") {
+            return true;
+        }
+
+        if self.general_logging_enabled && self.general_level <= id.log_level {
+            return true;
+        }
+
+        id_allowed(&self.allowed_global_ids, id)
+            || self
+                .allowed_modules
+                .iter()
+                .any(|module| module.event_allowed(id, origin))
+    }
+
     pub fn allow_addon(&self, id: LogId, origin: &Origin, addon: &AddonKind) -> bool {
-        let addon_filter = AddonFilter::from(addon);
+        self.allow_addon_filter(id, origin, &AddonFilter::from(addon))
+    }
 
-        if self.general_logging_enabled && self.general_addons.contains(&addon_filter) {
+    /// Returns `true` if addons of the given kind are allowed for the given log-id and origin.
+    pub fn allow_addon_filter(&self, id: LogId, origin: &Origin, addon: &AddonFilter) -> bool {
+        if self.general_logging_enabled && self.general_addons.contains(addon) {
             return true;
         }
 
-        addon_allowed(&self.allowed_global_ids, id, &addon_filter)
-            || addon_allowed_in_origin(&self.allowed_modules, id, origin, &addon_filter)
+        addon_allowed(&self.allowed_global_ids, id, addon)
+            || addon_allowed_in_origin(&self.allowed_modules, id, origin, addon)
     }
 
     pub fn show_origin_info(&self, id: LogId, origin: &Origin) -> bool {
@@ -511,16 +705,15 @@ impl evident::event::filter::Filter<LogId, LogMsg> for FilterConfig {
         }
 
         // Note: `Trace` starts at `0`
-        if self.general_logging_enabled && self.general_level <= entry.get_event_id().log_level {
+        if self.general_logging_enabled
+            && self.general_level <= entry.get_event_id().log_level
+            && content_matches(&self.general_content_filters, entry)
+        {
             return true;
         }
 
-        id_allowed(&self.allowed_global_ids, *entry.get_event_id())
-            || id_allowed_in_origin(
-                &self.allowed_modules,
-                *entry.get_event_id(),
-                entry.get_origin(),
-            )
+        entry_allowed(&self.allowed_global_ids, entry)
+            || entry_allowed_in_origin(&self.allowed_modules, entry)
     }
 }
 
@@ -533,11 +726,50 @@ where
     }
 }
 
+/// Conversion into a [`FilterConfig`] that fails for invalid filter strings.
+pub trait IntoFilterConfig {
+    fn into_filter_config(self) -> Result<FilterConfig, FilterError>;
+}
+
+impl IntoFilterConfig for FilterConfig {
+    fn into_filter_config(self) -> Result<FilterConfig, FilterError> {
+        Ok(self)
+    }
+}
+
+impl<I> IntoFilterConfig for (LogLevel, I)
+where
+    I: IntoIterator<Item = AddonFilter>,
+{
+    fn into_filter_config(self) -> Result<FilterConfig, FilterError> {
+        Ok(self.into())
+    }
+}
+
+impl IntoFilterConfig for &str {
+    fn into_filter_config(self) -> Result<FilterConfig, FilterError> {
+        FilterConfig::try_new(self)
+    }
+}
+
+impl IntoFilterConfig for &String {
+    fn into_filter_config(self) -> Result<FilterConfig, FilterError> {
+        FilterConfig::try_new(self)
+    }
+}
+
+impl IntoFilterConfig for String {
+    fn into_filter_config(self) -> Result<FilterConfig, FilterError> {
+        FilterConfig::try_new(&self)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum FilterError {
     ParsingLogId(String),
     ParsingAddons(String),
     ParsingModule(String),
+    ParsingContent(String),
     SettingFilter,
 }
 
@@ -555,6 +787,9 @@ impl std::fmt::Display for FilterError {
             FilterError::ParsingModule(bad_module) => {
                 write!(f, "Could not parse module '{}'.", bad_module)
             }
+            FilterError::ParsingContent(bad_content) => {
+                write!(f, "Could not parse content filter '{}'.", bad_content)
+            }
             FilterError::SettingFilter => {
                 write!(f, "Could not set the new filter configuration.")
             }
@@ -572,9 +807,24 @@ fn id_allowed(ids: &Vec<LogIdAddonFilter>, id: LogId) -> bool {
     false
 }
 
-fn id_allowed_in_origin(modules: &Vec<LogIdModuleFilter>, id: LogId, origin: &Origin) -> bool {
+fn entry_allowed(ids: &Vec<LogIdAddonFilter>, entry: &impl EventEntry<LogId, LogMsg>) -> bool {
+    for allowed_id in ids {
+        if allowed_id.log_id == *entry.get_event_id()
+            && content_matches(&allowed_id.content_filters, entry)
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn entry_allowed_in_origin(
+    modules: &Vec<LogIdModuleFilter>,
+    entry: &impl EventEntry<LogId, LogMsg>,
+) -> bool {
     for module in modules {
-        if module.event_allowed(id, origin) {
+        if module.entry_allowed(entry) {
             return true;
         }
     }
@@ -624,6 +874,7 @@ fn get_addons(s: &mut String) -> Vec<AddonFilter> {
                         addons.push(AddonFilter::Infos);
                         addons.push(AddonFilter::Debugs);
                         addons.push(AddonFilter::Traces);
+                        addons.push(AddonFilter::Fields);
                         addons.push(AddonFilter::Related);
 
                         #[cfg(feature = "hint_note")]
@@ -649,18 +900,23 @@ fn get_addons(s: &mut String) -> Vec<AddonFilter> {
     addons
 }
 
-fn get_ids(s: &mut String) -> Vec<LogIdAddonFilter> {
+/// Extracts log-ids set between `[]`, and removes them from the given string.
+///
+/// Invalid log-ids are ignored, but an error is returned if a content filter of a log-id is invalid.
+fn get_ids(s: &mut String) -> Result<Vec<LogIdAddonFilter>, FilterError> {
     let mut ids = Vec::new();
 
-    if let (Some(ids_start), Some(ids_end)) = (s.find('['), s.find(']')) {
+    if let (Some(ids_start), Some(ids_end)) = (find_unquoted(s, '['), find_unquoted(s, ']')) {
         if ids_start >= ids_end {
-            return ids;
+            return Ok(ids);
         }
 
         if let Some(ids_part) = s.get((ids_start + 1)..ids_end) {
-            for id_part in ids_part.split('|') {
-                if let Ok(id) = LogIdAddonFilter::try_from(id_part.trim()) {
-                    ids.push(id);
+            for id_part in split_unquoted(ids_part, '|') {
+                match LogIdAddonFilter::try_from(id_part.trim()) {
+                    Ok(id) => ids.push(id),
+                    Err(err @ FilterError::ParsingContent(_)) => return Err(err),
+                    Err(_) => {}
                 }
             }
         }
@@ -668,7 +924,7 @@ fn get_ids(s: &mut String) -> Vec<LogIdAddonFilter> {
         s.replace_range(ids_start..(ids_end + 1), "");
     }
 
-    ids
+    Ok(ids)
 }
 
 fn try_into_log_level(s: &str) -> Option<LogLevel> {
@@ -743,6 +999,26 @@ mod tests {
     fn invalid_log_id_filter_with_empty_identifier() {
         let log_id_filter = LogIdFilter::try_from("my_crate::");
 
+        assert!(
+            log_id_filter.is_err(),
+            "Parsing invalid LogIdFilter did not result in error."
+        );
+    }
+    #[test]
+    fn valid_log_id_filter_with_code() {
+        let log_id_filter = LogIdFilter::try_from("#E0042").unwrap();
+
+        assert_eq!(
+            log_id_filter.code,
+            Some("E0042".to_string()),
+            "Code extraction was not correct"
+        );
+    }
+
+    #[test]
+    fn invalid_log_id_filter_with_empty_code() {
+        let log_id_filter = LogIdFilter::try_from("#");
+
         assert!(
             log_id_filter.is_err(),
             "Parsing invalid LogIdFilter did not result in error."
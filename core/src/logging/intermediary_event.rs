@@ -1,10 +1,15 @@
-use evident::event::{entry::EventEntry, origin::Origin};
+use evident::event::{entry::EventEntry, finalized::FinalizedEvent, origin::Origin};
 
 use crate::log_id::LogId;
 
 use super::{
+    chain::{remember_error, ErrorIdentity},
     event_entry::{AddonKind, LogEventEntry},
+    filter::{AddonFilter, LogFilter},
+    logger::{recorders_of, target_filter, Logger},
     msg::LogMsg,
+    scope::scoped_addons,
+    span::relate_to_spans,
     LOGGER,
 };
 
@@ -13,15 +18,21 @@ use super::{
 pub struct IntermediaryLogEvent {
     /// [`EventEntry`] storing all event information.
     pub(crate) entry: LogEventEntry,
+    /// Logger capturing this event, or `None` to capture it with the [`LOGGER`].
+    target: Option<Logger>,
+    /// Error that is remembered as logged by this event, if the event passes the filter.
+    logged_error: Option<ErrorIdentity>,
+    /// `true` for copies of events captured by recorders of the logger the event was set for.
+    recorded: bool,
+    /// `true` once the entry passed the filter of its target logger.
+    approved: bool,
 }
 
 impl evident::event::intermediary::IntermediaryEvent<LogId, LogMsg, LogEventEntry>
     for IntermediaryLogEvent
 {
     fn new(event_id: LogId, msg: Option<impl Into<LogMsg>>, origin: Origin) -> Self {
-        IntermediaryLogEvent {
-            entry: LogEventEntry::new(event_id, msg, origin),
-        }
+        IntermediaryLogEvent::with_target(None, event_id, msg, origin)
     }
 
     fn get_entry(&self) -> &LogEventEntry {
@@ -29,6 +40,30 @@ impl evident::event::intermediary::IntermediaryEvent<LogId, LogMsg, LogEventEntr
     }
 
     fn take_entry(&mut self) -> LogEventEntry {
+        // Note: Spans only relate events that pass the filter, so filtered out events are not referenced
+        if self.approved {
+            // Note: Recorded copies are the same event, so only the original event is related and remembered
+            if !self.recorded {
+                relate_to_spans(&self.entry);
+
+                if let Some(error) = self.logged_error.take() {
+                    remember_error(
+                        error,
+                        FinalizedEvent::new(self.entry.event_id, self.entry.entry_id),
+                    );
+                }
+            }
+
+            // Note: Entries are taken once they are captured, so no handler receives unredacted entries
+            #[cfg(feature = "redaction")]
+            super::redaction::redact_entry(&mut self.entry);
+
+            #[cfg(any(test, feature = "testing"))]
+            if !self.recorded {
+                crate::testing::record(&self.entry);
+            }
+        }
+
         std::mem::take(&mut self.entry)
     }
 }
@@ -43,7 +78,66 @@ impl std::fmt::Debug for IntermediaryLogEvent {
     }
 }
 
+impl Drop for IntermediaryLogEvent {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.capture_for_recorders();
+        }
+
+        // Note: The entry is checked once before capturing, because content filters and predicates may be costly,
+        // and capturing only checks the log-id and origin again
+        if !self.approved {
+            if !entry_allowed_for(self.target.as_ref(), &self.entry) {
+                return;
+            }
+            self.approved = true;
+        }
+
+        // Note: Target stays set while capturing, because capturing checks the filter of the target
+        match self.target.clone() {
+            Some(logger) => logger._capture(self),
+            None => LOGGER._capture(self),
+        }
+    }
+}
+
+impl From<IntermediaryLogEvent> for LogId {
+    fn from(intermed_event: IntermediaryLogEvent) -> Self {
+        intermed_event.finalize().into_event_id()
+    }
+}
+
 impl IntermediaryLogEvent {
+    /// Creates an event that is captured by the given logger, or by the [`LOGGER`] if no logger is given.
+    ///
+    /// The filter of the target logger decides which addons are added to the event.
+    pub fn with_target(
+        target: Option<&Logger>,
+        event_id: LogId,
+        msg: Option<impl Into<LogMsg>>,
+        origin: Origin,
+    ) -> Self {
+        let mut event = IntermediaryLogEvent {
+            entry: LogEventEntry::new(event_id, msg, origin),
+            target: target.cloned(),
+            logged_error: None,
+            recorded: false,
+            approved: false,
+        };
+
+        for kind in scoped_addons() {
+            event.push_addon(kind);
+        }
+
+        event
+    }
+
+    pub fn finalize(self) -> FinalizedEvent<LogId> {
+        evident::event::intermediary::IntermediaryEvent::<LogId, LogMsg, LogEventEntry>::finalize(
+            self,
+        )
+    }
+
     /// Returns the [`LogId`] of this log-id event
     pub fn get_event_id(&self) -> LogId {
         self.entry.event_id
@@ -55,44 +149,129 @@ impl IntermediaryLogEvent {
     }
 
     pub fn add_addon(mut self, kind: AddonKind) -> Self {
-        if let Some(filter) = LOGGER.get_filter() {
-            if !filter.allow_addon(self.get_event_id(), &self.entry.origin, &kind) {
-                return self;
-            }
+        self.push_addon(kind);
+        self
+    }
+
+    /// Adds the addon returned by the given closure, if the filter allows addons of the given kind for this event.
+    ///
+    /// The closure is not called if the addon would be filtered out,
+    /// which avoids creating expensive addons like payloads for nothing.
+    ///
+    /// **Note:** The returned addon is checked again by the filter, so a wrong kind only leads to unnecessary calls.
+    pub fn add_addon_with<F>(mut self, addon: AddonFilter, f: F) -> Self
+    where
+        F: FnOnce() -> AddonKind,
+    {
+        if self.allow_addon(&addon) {
+            self.push_addon(f());
         }
+        self
+    }
 
-        match kind {
-            AddonKind::Info(msg) => self.entry.infos.push(msg),
-            AddonKind::Debug(msg) => self.entry.debugs.push(msg),
-            AddonKind::Trace(msg) => self.entry.traces.push(msg),
-            AddonKind::Related(finalized_event) => self.entry.related.push(finalized_event),
-
-            #[cfg(feature = "fmt")]
-            AddonKind::FmtInfo(fmt_msg) => self.entry.fmt_infos.push(fmt_msg),
-            #[cfg(feature = "fmt")]
-            AddonKind::FmtDebug(fmt_msg) => self.entry.fmt_debugs.push(fmt_msg),
-            #[cfg(feature = "fmt")]
-            AddonKind::FmtTrace(fmt_msg) => self.entry.fmt_traces.push(fmt_msg),
-
-            #[cfg(feature = "hint_note")]
-            AddonKind::Hint(msg) => self.entry.hints.push(msg),
-            #[cfg(all(feature = "hint_note", feature = "fmt"))]
-            AddonKind::FmtHint(fmt_msg) => self.entry.fmt_hints.push(fmt_msg),
-            #[cfg(feature = "hint_note")]
-            AddonKind::Note(msg) => self.entry.notes.push(msg),
-            #[cfg(all(feature = "hint_note", feature = "fmt"))]
-            AddonKind::FmtNote(fmt_msg) => self.entry.fmt_notes.push(fmt_msg),
-
-            #[cfg(feature = "diagnostics")]
-            AddonKind::Diagnostic(diag) => self.entry.diagnostics.push(diag),
-            #[cfg(all(feature = "diagnostics", feature = "fmt"))]
-            AddonKind::FmtDiagnostic(fmt_diag) => self.entry.fmt_diagnostics.push(fmt_diag),
-
-            #[cfg(feature = "payloads")]
-            AddonKind::Payload(payload) => self.entry.payloads.push(payload),
-            #[cfg(all(feature = "payloads", feature = "fmt"))]
-            AddonKind::FmtPayload(fmt_payload) => self.entry.fmt_payloads.push(fmt_payload),
+    fn allow_addon(&self, addon: &AddonFilter) -> bool {
+        match self.filter() {
+            Some(filter) => {
+                filter.allow_addon_filter(self.get_event_id(), &self.entry.origin, addon)
+            }
+            None => true,
         }
+    }
+
+    /// Remembers the given error as logged by this event, once the event passes the filter.
+    pub(crate) fn remember_error(mut self, error: ErrorIdentity) -> Self {
+        self.logged_error = Some(error);
         self
     }
+
+    /// Captures a copy of this event with every recorder of the target logger whose filter allows the entry.
+    fn capture_for_recorders(&self) {
+        // Note: Recorders are cloned, so capturing does not hold the lock
+        let recorders: Vec<Logger> = match recorders_of(self.target.as_ref()).read() {
+            Ok(recorders) => recorders
+                .iter()
+                .filter(|recorder| entry_allowed_for(Some(recorder), &self.entry))
+                .cloned()
+                .collect(),
+            Err(_) => return,
+        };
+
+        for recorder in recorders {
+            drop(IntermediaryLogEvent {
+                entry: self.entry.clone(),
+                target: Some(recorder),
+                logged_error: None,
+                recorded: true,
+                approved: true,
+            });
+        }
+    }
+
+    fn filter(&self) -> Option<&LogFilter> {
+        target_filter(self.target.as_ref())
+    }
+
+    fn push_addon(&mut self, kind: AddonKind) {
+        if let Some(filter) = self.filter() {
+            if !filter.allow_addon(self.get_event_id(), &self.entry.origin, &kind) {
+                return;
+            }
+        }
+
+        self.entry.push_addon(kind);
+    }
+}
+
+/// Returns `true` if the given logger, or the [`LOGGER`] if no logger is given, captures the given entry.
+fn entry_allowed_for(target: Option<&Logger>, entry: &LogEventEntry) -> bool {
+    let publisher = match target {
+        Some(logger) => &**logger,
+        None => &*LOGGER,
+    };
+
+    if evident::publisher::is_control_id(&entry.event_id) {
+        return true;
+    }
+
+    if !publisher.is_capturing() {
+        return false;
+    }
+
+    match publisher.get_filter() {
+        Some(filter) => filter.allow_log_entry(entry),
+        None => true,
+    }
+}
+
+/// Addons that may be added to an [`IntermediaryLogEvent`].
+///
+/// Implemented for [`AddonKind`] and [`LazyAddon`], so both may be used with `add:` in the logging macros.
+pub trait EventAddon {
+    fn add_to(self, event: IntermediaryLogEvent) -> IntermediaryLogEvent;
+}
+
+impl EventAddon for AddonKind {
+    fn add_to(self, event: IntermediaryLogEvent) -> IntermediaryLogEvent {
+        event.add_addon(self)
+    }
+}
+
+/// Addon that is only created if the filter allows addons of its kind.
+///
+/// See [`IntermediaryLogEvent::add_addon_with`].
+pub struct LazyAddon<F> {
+    addon: AddonFilter,
+    f: F,
+}
+
+impl<F: FnOnce() -> AddonKind> LazyAddon<F> {
+    pub fn new(addon: AddonFilter, f: F) -> Self {
+        LazyAddon { addon, f }
+    }
+}
+
+impl<F: FnOnce() -> AddonKind> EventAddon for LazyAddon<F> {
+    fn add_to(self, event: IntermediaryLogEvent) -> IntermediaryLogEvent {
+        event.add_addon_with(self.addon, self.f)
+    }
 }
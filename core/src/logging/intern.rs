@@ -0,0 +1,54 @@
+//! Contains the interner for strings of events that are read from written logs.
+//!
+//! Log-ids and origins require static strings, so strings read from logs are leaked once per distinct string.
+//! All readers share one interner, and the size of interned strings is bounded,
+//! so reading many logs with distinct strings cannot grow memory without limit.
+
+use std::{collections::HashSet, sync::Mutex};
+
+/// Maximum number of bytes of all interned strings.
+pub const MAX_INTERNED_BYTES: usize = 16 * 1024 * 1024;
+
+/// Interned strings, and the number of their bytes.
+static INTERNED: Mutex<Option<(HashSet<&'static str>, usize)>> = Mutex::new(None);
+
+/// Returns the interned static string equal to the given string.
+///
+/// Returns `None` if the string is not interned yet, and interning it would exceed [`MAX_INTERNED_BYTES`].
+pub fn intern(s: &str) -> Option<&'static str> {
+    let mut interned = match INTERNED.lock() {
+        Ok(interned) => interned,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let (strings, bytes) = interned.get_or_insert_with(Default::default);
+
+    if let Some(interned) = strings.get(s) {
+        return Some(interned);
+    }
+    if *bytes + s.len() > MAX_INTERNED_BYTES {
+        return None;
+    }
+
+    let leaked: &'static str = Box::leak(s.to_string().into_boxed_str());
+    strings.insert(leaked);
+    *bytes += leaked.len();
+    Some(leaked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_strings_interned_once() {
+        let first = intern(&String::from("my_crate::intern_test")).unwrap();
+        let second = intern(&String::from("my_crate::intern_test")).unwrap();
+
+        assert!(std::ptr::eq(first, second));
+    }
+
+    #[test]
+    fn too_large_string_not_interned() {
+        assert_eq!(intern(&"x".repeat(MAX_INTERNED_BYTES + 1)), None);
+    }
+}
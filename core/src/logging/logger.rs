@@ -0,0 +1,265 @@
+//! Contains the [`Logger`] that captures log-id events independently of the default [`LOGGER`].
+//!
+//! Each logger has its own filter, capture and subscription channels, and subscribers.
+//! This allows libraries and tests to isolate their logging, or components in one process to use different filters.
+//!
+//! Events are captured by the [`LOGGER`], unless a logger is given as target,
+//! e.g. with `log!(target: logger, ...)` of the `logid` crate.
+//!
+//! Loggers may also be added as recorders of another logger with [`add_recorder()`],
+//! to capture events with their own filter, regardless of the filter of the other logger.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid_core::{
+//!     log_id::{LogId, LogLevel},
+//!     logging::{
+//!         filter::FilterConfig, intermediary_event::IntermediaryLogEvent, logger::Logger,
+//!         msg::NO_MSG,
+//!     },
+//!     evident::this_origin,
+//!     new_log_id,
+//! };
+//!
+//! const CONNECTION_LOST: LogId = new_log_id!("ConnectionLost", LogLevel::Warn);
+//!
+//! let logger = Logger::builder().filter(FilterConfig::new("warn")).build();
+//! let recv = logger.subscribe(CONNECTION_LOST).unwrap();
+//!
+//! IntermediaryLogEvent::with_target(Some(&logger), CONNECTION_LOST, NO_MSG, this_origin!()).finalize();
+//!
+//! let event = recv
+//!     .get_receiver()
+//!     .recv_timeout(std::time::Duration::from_millis(100))
+//!     .unwrap();
+//! assert_eq!(event.get_event_id(), &CONNECTION_LOST);
+//! ~~~
+
+use std::sync::{Arc, RwLock};
+
+use evident::{
+    event::origin::Origin,
+    publisher::{CaptureMode, EventTimestampKind},
+};
+
+use crate::log_id::LogId;
+
+use super::{
+    filter::{FilterConfig, FilterError, IntoFilterConfig, LogFilter},
+    LogPublisher, CAPTURE_CHANNEL_BOUND, LOGGER, SUBSCRIPTION_CHANNEL_BOUND,
+};
+
+/// Logger capturing log-id events independently of the default [`LOGGER`].
+///
+/// Dereferences to its [`LogPublisher`], so subscriptions and capture control work like for the [`LOGGER`].
+/// Clones share the same publisher, and the publisher is dropped with the last clone.
+#[derive(Clone)]
+pub struct Logger {
+    publisher: Arc<LogPublisher>,
+    /// Loggers recording events set for this logger, see [`add_recorder()`].
+    recorders: Arc<RwLock<Vec<Logger>>>,
+}
+
+impl Logger {
+    /// Creates a logger with the same settings the [`LOGGER`] is created with.
+    pub fn new() -> Self {
+        LoggerBuilder::new().build()
+    }
+
+    pub fn builder() -> LoggerBuilder {
+        LoggerBuilder::new()
+    }
+
+    pub fn set_filter<T>(&self, into_filter: T) -> Result<(), FilterError>
+    where
+        T: IntoFilterConfig,
+    {
+        match self.publisher.get_filter() {
+            Some(filter) => filter.set_filter(into_filter),
+            None => Err(FilterError::SettingFilter),
+        }
+    }
+
+    /// Returns `false` if the filter of this logger rejects events for the given log-id and origin regardless of their content.
+    pub fn allow_event(&self, id: LogId, origin: &Origin) -> bool {
+        match self.publisher.get_filter() {
+            Some(filter) => filter.allow_event(id, origin),
+            None => true,
+        }
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Logger::new()
+    }
+}
+
+impl std::ops::Deref for Logger {
+    type Target = LogPublisher;
+
+    fn deref(&self) -> &Self::Target {
+        &self.publisher
+    }
+}
+
+impl AsRef<Logger> for Logger {
+    fn as_ref(&self) -> &Logger {
+        self
+    }
+}
+
+impl PartialEq for Logger {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.publisher, &other.publisher)
+    }
+}
+
+impl Eq for Logger {}
+
+impl std::fmt::Debug for Logger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Logger")
+            .field("capture_mode", &self.publisher.get_capture_mode())
+            .field("is_capturing", &self.publisher.is_capturing())
+            .finish()
+    }
+}
+
+/// Loggers recording events set for the [`LOGGER`], see [`add_recorder()`].
+static LOGGER_RECORDERS: RwLock<Vec<Logger>> = RwLock::new(Vec::new());
+
+/// Adds the given recorder to the given logger, or to the [`LOGGER`] if no logger is given.
+///
+/// A recorder captures a copy of every event set for the logger that passes the filter of the recorder,
+/// regardless of the filter of the logger. This allows to keep events that are not emitted, e.g. to dump them once an error occurs.
+///
+/// **Note:** Recorded events only contain addons allowed by the filter of the logger the events are set for.
+pub fn add_recorder(target: Option<&Logger>, recorder: &Logger) {
+    if let Ok(mut recorders) = recorders_of(target).write() {
+        recorders.push(recorder.clone());
+    }
+}
+
+/// Removes the given recorder from the given logger, or from the [`LOGGER`] if no logger is given.
+pub fn remove_recorder(target: Option<&Logger>, recorder: &Logger) {
+    if let Ok(mut recorders) = recorders_of(target).write() {
+        recorders.retain(|added| added != recorder);
+    }
+}
+
+/// Returns the recorders of the given logger, or of the [`LOGGER`] if no logger is given.
+pub(crate) fn recorders_of(target: Option<&Logger>) -> &RwLock<Vec<Logger>> {
+    match target {
+        Some(logger) => &logger.recorders,
+        None => &LOGGER_RECORDERS,
+    }
+}
+
+/// Returns `true` if a recorder of the given logger, or of the [`LOGGER`] if no logger is given,
+/// may allow events for the given log-id and origin.
+pub(crate) fn recorders_allow_event(target: Option<&Logger>, id: LogId, origin: &Origin) -> bool {
+    match recorders_of(target).read() {
+        Ok(recorders) => recorders
+            .iter()
+            .any(|recorder| recorder.allow_event(id, origin)),
+        Err(_) => false,
+    }
+}
+
+/// Returns the filter of the given logger, or the filter of the [`LOGGER`] if no logger is given.
+pub fn target_filter(target: Option<&Logger>) -> Option<&LogFilter> {
+    match target {
+        Some(logger) => logger.get_filter().as_ref(),
+        None => LOGGER.get_filter().as_ref(),
+    }
+}
+
+/// Builder for a [`Logger`].
+///
+/// Settings that are not set explicitly are the same the [`LOGGER`] is created with.
+#[derive(Debug)]
+pub struct LoggerBuilder {
+    filter: Option<FilterConfig>,
+    capture_mode: CaptureMode,
+    capture_channel_bound: usize,
+    subscription_channel_bound: usize,
+    timestamp_kind: EventTimestampKind,
+}
+
+impl LoggerBuilder {
+    pub fn new() -> Self {
+        LoggerBuilder {
+            filter: None,
+            capture_mode: CaptureMode::Blocking,
+            capture_channel_bound: CAPTURE_CHANNEL_BOUND,
+            subscription_channel_bound: SUBSCRIPTION_CHANNEL_BOUND,
+            timestamp_kind: EventTimestampKind::Captured,
+        }
+    }
+
+    /// Sets the initial filter of the logger.
+    ///
+    /// Without a filter, the logger is configured like the [`LOGGER`], e.g. using the `LOGID_FILTER` environment variable.
+    pub fn filter<T: Into<FilterConfig>>(mut self, into_filter: T) -> Self {
+        self.filter = Some(into_filter.into());
+        self
+    }
+
+    pub fn capture_mode(mut self, mode: CaptureMode) -> Self {
+        self.capture_mode = mode;
+        self
+    }
+
+    pub fn capture_channel_bound(mut self, bound: usize) -> Self {
+        self.capture_channel_bound = bound;
+        self
+    }
+
+    pub fn subscription_channel_bound(mut self, bound: usize) -> Self {
+        self.subscription_channel_bound = bound;
+        self
+    }
+
+    pub fn timestamp_kind(mut self, kind: EventTimestampKind) -> Self {
+        self.timestamp_kind = kind;
+        self
+    }
+
+    pub fn build(self) -> Logger {
+        let filter = LogFilter::new();
+        if let Some(config) = self.filter {
+            // Note: Setting the filter of a new `LogFilter` cannot fail
+            let _ = filter.set_filter(config);
+        }
+
+        // Note: The capture thread only holds a weak reference, so the publisher is dropped with the last logger clone.
+        let publisher = Arc::new_cyclic(|weak: &std::sync::Weak<LogPublisher>| {
+            let weak = weak.clone();
+            LogPublisher::with(
+                move |event| {
+                    if let Some(publisher) = weak.upgrade() {
+                        publisher.on_event(event);
+                    }
+                },
+                filter,
+                self.capture_mode,
+                self.capture_channel_bound,
+                self.subscription_channel_bound,
+                self.timestamp_kind,
+            )
+        });
+
+        Logger {
+            publisher,
+            recorders: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+}
+
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        LoggerBuilder::new()
+    }
+}
@@ -1,28 +1,46 @@
+use evident::{
+    once_cell::sync::Lazy,
+    publisher::{CaptureMode, EventTimestampKind, EvidentPublisher},
+};
+
 use crate::log_id::LogId;
 
-use self::{
-    event_entry::LogEventEntry, filter::LogFilter, intermediary_event::IntermediaryLogEvent,
-    msg::LogMsg,
-};
+use self::{event_entry::LogEventEntry, filter::LogFilter, msg::LogMsg};
 
+pub mod capture;
+pub mod chain;
 pub mod event_entry;
+pub mod ext;
+pub mod field;
 pub mod filter;
 pub mod intermediary_event;
+pub mod intern;
+pub mod logger;
 pub mod msg;
+#[cfg(feature = "redaction")]
+pub mod redaction;
+pub mod scope;
+pub mod span;
 
 #[cfg(test)]
 pub mod tests;
 
-evident::create_static_publisher!(
-    pub LOGGER,
-    id_type = LogId,
-    msg_type = LogMsg,
-    entry_type = LogEventEntry,
-    interm_event_type = IntermediaryLogEvent,
-    filter_type = LogFilter,
-    filter = LogFilter::new(),
-    capture_channel_bound = 1000,
-    subscription_channel_bound = 1000,
-    capture_mode = evident::publisher::CaptureMode::Blocking,
-    timestamp_kind = evident::publisher::EventTimestampKind::Captured
-);
+/// Publisher capturing log-id events, and sending them to subscribers.
+pub type LogPublisher = EvidentPublisher<LogId, LogMsg, LogEventEntry, LogFilter>;
+
+pub(crate) const CAPTURE_CHANNEL_BOUND: usize = 1000;
+pub(crate) const SUBSCRIPTION_CHANNEL_BOUND: usize = 1000;
+
+/// The default publisher capturing all events that are not set for a specific [`Logger`](logger::Logger).
+pub static LOGGER: Lazy<LogPublisher> = Lazy::new(|| {
+    LogPublisher::with(
+        |event| {
+            LOGGER.on_event(event);
+        },
+        LogFilter::new(),
+        CaptureMode::Blocking,
+        CAPTURE_CHANNEL_BOUND,
+        SUBSCRIPTION_CHANNEL_BOUND,
+        EventTimestampKind::Captured,
+    )
+});
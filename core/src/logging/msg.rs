@@ -15,6 +15,18 @@ impl LogMsg {
     }
 }
 
+#[cfg(feature = "redaction")]
+impl LogMsg {
+    pub(crate) fn redact(&mut self, redaction: &super::redaction::Redaction) {
+        #[cfg(feature = "fmt")]
+        if let Some(fmt) = self.fmt.as_mut() {
+            fmt.redact(redaction);
+        }
+
+        self.msg = redaction.redact_str(&self.msg);
+    }
+}
+
 impl evident::event::Msg for LogMsg {}
 
 impl std::fmt::Display for LogMsg {
@@ -135,6 +147,11 @@ impl FmtMsg {
     pub fn get_data(&self) -> &crate::serde_json::Value {
         &self.data
     }
+
+    #[cfg(feature = "redaction")]
+    pub(crate) fn redact(&mut self, redaction: &super::redaction::Redaction) {
+        redaction.redact_value(&mut self.data);
+    }
 }
 
 #[cfg(feature = "fmt")]
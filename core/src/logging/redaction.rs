@@ -0,0 +1,283 @@
+//! Contains functionality to redact sensitive data from log-id events before they are captured.
+//!
+//! Redaction is applied when an event passed the filter and is captured by the [`LOGGER`](super::LOGGER),
+//! so handlers and subscribers never receive the raw values, and filtered out events are not redacted.
+//! Content filters therefore see the raw values.
+//!
+//! - Regex rules replace matches in messages, string addons, string fields, and all strings inside JSON data
+//! - JSON pointer rules replace values in payloads and the data of formatted messages
+//! - [`Redacted`] wraps single values, so they are rendered as [`REDACTED`] wherever they are formatted or serialized
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid_core::logging::redaction::{set_redaction, Redacted, Redaction};
+//!
+//! set_redaction(
+//!     Redaction::new()
+//!         .pointer("/user/email")
+//!         .pattern(r"token=\w+")
+//!         .unwrap(),
+//! );
+//!
+//! assert_eq!(format!("Password: {}", Redacted("secret")), "Password: ***");
+//! ~~~
+
+use std::sync::RwLock;
+
+use crate::serde_json::Value;
+
+use super::event_entry::LogEventEntry;
+
+/// Replacement for redacted values.
+pub const REDACTED: &str = "***";
+
+/// Redaction rules currently applied to captured events.
+static REDACTION: RwLock<Option<Redaction>> = RwLock::new(None);
+
+/// Sets the redaction rules applied to all events captured from now on.
+///
+/// Setting empty rules disables redaction.
+pub fn set_redaction(redaction: Redaction) {
+    if let Ok(mut current) = REDACTION.write() {
+        *current = (!redaction.is_empty()).then_some(redaction);
+    }
+}
+
+/// Applies the current redaction rules to the given entry.
+pub(crate) fn redact_entry(entry: &mut LogEventEntry) {
+    if let Ok(current) = REDACTION.read() {
+        if let Some(redaction) = current.as_ref() {
+            redaction.redact_entry(entry);
+        }
+    }
+}
+
+/// Wrapper for sensitive values that are rendered as [`REDACTED`].
+///
+/// The wrapped value is still accessible in code, but never shown by `Display`, `Debug`, or serialization.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Redacted<T>(pub T);
+
+impl<T> Redacted<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::fmt::Display for Redacted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", REDACTED)
+    }
+}
+
+impl<T> std::fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", REDACTED)
+    }
+}
+
+impl<T> serde::Serialize for Redacted<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<T> From<Redacted<T>> for super::field::FieldValue {
+    fn from(_value: Redacted<T>) -> Self {
+        super::field::FieldValue::Str(REDACTED.to_string())
+    }
+}
+
+/// Rules to redact sensitive data from log-id events.
+#[derive(Debug, Default, Clone)]
+pub struct Redaction {
+    pointers: Vec<String>,
+    patterns: Vec<regex::Regex>,
+}
+
+impl Redaction {
+    pub fn new() -> Self {
+        Redaction::default()
+    }
+
+    /// Adds a JSON pointer (e.g. `/user/email`) whose value is replaced in payloads and the data of formatted messages.
+    pub fn pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.pointers.push(pointer.into());
+        self
+    }
+
+    /// Adds a regex whose matches are replaced in messages, string addons, string fields, and strings inside JSON data.
+    pub fn pattern(mut self, pattern: &str) -> Result<Self, RedactionError> {
+        let regex = regex::Regex::new(pattern)
+            .map_err(|err| RedactionError::InvalidPattern(pattern.to_string(), err.to_string()))?;
+        self.patterns.push(regex);
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pointers.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Returns the given string with all matches of the regex rules replaced.
+    pub fn redact_str(&self, s: &str) -> String {
+        let mut redacted = s.to_string();
+        for pattern in &self.patterns {
+            if pattern.is_match(&redacted) {
+                redacted = pattern.replace_all(&redacted, REDACTED).into_owned();
+            }
+        }
+        redacted
+    }
+
+    /// Replaces values at the JSON pointers, and matches of the regex rules in all strings of the given value.
+    pub fn redact_value(&self, value: &mut Value) {
+        for pointer in &self.pointers {
+            if let Some(target) = value.pointer_mut(pointer) {
+                *target = Value::String(REDACTED.to_string());
+            }
+        }
+
+        if !self.patterns.is_empty() {
+            self.redact_json_strings(value);
+        }
+    }
+
+    fn redact_json_strings(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.redact_str(s),
+            Value::Array(values) => values
+                .iter_mut()
+                .for_each(|value| self.redact_json_strings(value)),
+            Value::Object(map) => map
+                .values_mut()
+                .for_each(|value| self.redact_json_strings(value)),
+            _ => {}
+        }
+    }
+
+    fn redact_strings(&self, strings: &mut [String]) {
+        for s in strings {
+            *s = self.redact_str(s);
+        }
+    }
+
+    /// Applies all rules to the message and addons of the given entry.
+    pub fn redact_entry(&self, entry: &mut LogEventEntry) {
+        if let Some(msg) = entry.msg.as_mut() {
+            msg.redact(self);
+        }
+
+        self.redact_strings(&mut entry.infos);
+        self.redact_strings(&mut entry.debugs);
+        self.redact_strings(&mut entry.traces);
+
+        for field in &mut entry.fields {
+            field.redact(self);
+        }
+
+        #[cfg(feature = "fmt")]
+        for fmt_msg in entry
+            .fmt_infos
+            .iter_mut()
+            .chain(entry.fmt_debugs.iter_mut())
+            .chain(entry.fmt_traces.iter_mut())
+        {
+            fmt_msg.redact(self);
+        }
+
+        #[cfg(feature = "hint_note")]
+        {
+            self.redact_strings(&mut entry.hints);
+            self.redact_strings(&mut entry.notes);
+        }
+        #[cfg(all(feature = "hint_note", feature = "fmt"))]
+        for fmt_msg in entry.fmt_hints.iter_mut().chain(entry.fmt_notes.iter_mut()) {
+            fmt_msg.redact(self);
+        }
+
+        #[cfg(feature = "diagnostics")]
+        for diagnostic in &mut entry.diagnostics {
+            diagnostic.message = self.redact_str(&diagnostic.message);
+        }
+        #[cfg(all(feature = "diagnostics", feature = "fmt"))]
+        for fmt_diagnostic in &mut entry.fmt_diagnostics {
+            fmt_diagnostic.redact(self);
+        }
+
+        #[cfg(feature = "payloads")]
+        for payload in &mut entry.payloads {
+            self.redact_value(payload);
+        }
+        #[cfg(all(feature = "payloads", feature = "fmt"))]
+        for fmt_payload in &mut entry.fmt_payloads {
+            fmt_payload.redact(self);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedactionError {
+    InvalidPattern(String, String),
+}
+
+impl std::error::Error for RedactionError {}
+
+impl std::fmt::Display for RedactionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RedactionError::InvalidPattern(pattern, err) => {
+                write!(f, "Invalid redaction pattern '{}': {}", pattern, err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointer_and_pattern_rules() {
+        let redaction = Redaction::new()
+            .pointer("/user/email")
+            .pattern(r"token=\w+")
+            .unwrap();
+
+        let mut value = crate::serde_json::json!({
+            "user": { "email": "jane@example.com", "name": "Jane" },
+            "request": ["GET /?token=abc123"],
+        });
+        redaction.redact_value(&mut value);
+
+        assert_eq!(
+            value,
+            crate::serde_json::json!({
+                "user": { "email": "***", "name": "Jane" },
+                "request": ["GET /?***"],
+            })
+        );
+        assert_eq!(redaction.redact_str("token=xyz failed"), "*** failed");
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        assert!(matches!(
+            Redaction::new().pattern("("),
+            Err(RedactionError::InvalidPattern(..))
+        ));
+    }
+
+    #[test]
+    fn redacted_value_is_hidden() {
+        let secret = Redacted("secret".to_string());
+
+        assert_eq!(secret.to_string(), REDACTED);
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(
+            crate::serde_json::to_value(&secret).unwrap(),
+            crate::serde_json::json!(REDACTED)
+        );
+        assert_eq!(secret.into_inner(), "secret");
+    }
+}
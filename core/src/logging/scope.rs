@@ -0,0 +1,258 @@
+//! Contains the scope API to attach addons to all log-id events created on a thread while a scope is active.
+//!
+//! ## Usage
+//!
+//! ```
+//! use logid_core::logging::scope::scope;
+//!
+//! let _guard = scope().with_field("request_id", 1234).enter();
+//!
+//! // Every event created on this thread now carries `request_id=1234`
+//! ```
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use super::{event_entry::AddonKind, field::LogField};
+
+thread_local! {
+    /// Stack of active scopes on this thread.
+    static SCOPE_STACK: RefCell<Vec<ActiveScope>> = const { RefCell::new(Vec::new()) };
+}
+
+struct ActiveScope {
+    id: usize,
+    addons: Arc<[AddonKind]>,
+}
+
+fn next_scope_id() -> usize {
+    static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Creates a new [`ScopeBuilder`] to set addons that are added to all events created while the scope is active.
+pub fn scope() -> ScopeBuilder {
+    ScopeBuilder::default()
+}
+
+/// Builder to set addons for a scope.
+///
+/// Scope addons are added to events at creation, before addons set at the call site.
+/// Addons are only added if they are allowed by the addon filter for the event.
+#[derive(Debug, Default, Clone)]
+pub struct ScopeBuilder {
+    addons: Vec<AddonKind>,
+}
+
+impl ScopeBuilder {
+    pub fn with_info(self, msg: impl Into<String>) -> Self {
+        self.with_addon(AddonKind::Info(msg.into()))
+    }
+
+    pub fn with_debug(self, msg: impl Into<String>) -> Self {
+        self.with_addon(AddonKind::Debug(msg.into()))
+    }
+
+    pub fn with_trace(self, msg: impl Into<String>) -> Self {
+        self.with_addon(AddonKind::Trace(msg.into()))
+    }
+
+    pub fn with_field(
+        self,
+        key: impl Into<String>,
+        value: impl Into<crate::logging::field::FieldValue>,
+    ) -> Self {
+        self.with_addon(AddonKind::Field(LogField::new(key, value)))
+    }
+
+    pub fn with_addon(mut self, kind: AddonKind) -> Self {
+        self.addons.push(kind);
+        self
+    }
+
+    /// Activates the scope on the current thread until the returned guard is dropped.
+    pub fn enter(self) -> ScopeGuard {
+        let id = next_scope_id();
+        push_scope(ActiveScope {
+            id,
+            addons: self.addons.into(),
+        });
+
+        ScopeGuard {
+            id,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Wraps the given future so that the scope is active whenever the future is polled.
+    ///
+    /// In contrast to [`enter()`](Self::enter), the scope follows the future if it is moved between threads.
+    pub fn in_scope<F: Future>(self, future: F) -> Scoped<F> {
+        Scoped {
+            addons: self.addons.into(),
+            future: Box::pin(future),
+        }
+    }
+}
+
+/// Guard keeping a scope active on the current thread.
+///
+/// The scope is left once the guard is dropped.
+#[derive(Debug)]
+#[must_use = "the scope is left immediately if the guard is not kept"]
+pub struct ScopeGuard {
+    id: usize,
+    // Guard must be dropped on the thread that entered the scope
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        pop_scope(self.id);
+    }
+}
+
+/// Future that activates its scope whenever it is polled.
+///
+/// Created via [`ScopeBuilder::in_scope()`].
+pub struct Scoped<F: Future> {
+    addons: Arc<[AddonKind]>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let id = next_scope_id();
+        push_scope(ActiveScope {
+            id,
+            addons: self.addons.clone(),
+        });
+
+        // Note: Leaving the scope via guard, so the scope is also left if polling panics
+        let _guard = ScopeGuard {
+            id,
+            _not_send: PhantomData,
+        };
+
+        self.future.as_mut().poll(cx)
+    }
+}
+
+impl<F: Future> std::fmt::Debug for Scoped<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scoped")
+            .field("addons", &self.addons)
+            .finish()
+    }
+}
+
+fn push_scope(scope: ActiveScope) {
+    SCOPE_STACK.with(|stack| stack.borrow_mut().push(scope));
+}
+
+fn pop_scope(id: usize) {
+    // `try_with`, because guards may be dropped during thread-local destruction
+    let _ = SCOPE_STACK.try_with(|stack| {
+        let mut stack = stack.borrow_mut();
+        // Note: Searching from the end, because guards might not be dropped in reverse order
+        if let Some(pos) = stack.iter().rposition(|scope| scope.id == id) {
+            stack.remove(pos);
+        }
+    });
+}
+
+/// Returns all addons of active scopes on the current thread, starting with the outermost scope.
+pub(crate) fn scoped_addons() -> Vec<AddonKind> {
+    SCOPE_STACK
+        .try_with(|stack| {
+            stack
+                .borrow()
+                .iter()
+                .flat_map(|scope| scope.addons.iter().cloned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_scopes_collect_addons_in_order() {
+        let _outer = scope().with_info("outer").enter();
+
+        {
+            let _inner = scope().with_field("request_id", 1234).enter();
+
+            assert_eq!(
+                scoped_addons(),
+                vec![
+                    AddonKind::Info("outer".to_string()),
+                    AddonKind::Field(LogField::new("request_id", 1234))
+                ],
+                "Addons of nested scopes not collected in order."
+            );
+        }
+
+        assert_eq!(
+            scoped_addons(),
+            vec![AddonKind::Info("outer".to_string())],
+            "Addons of left scope still collected."
+        );
+    }
+
+    #[test]
+    fn scope_not_visible_on_other_thread() {
+        let _guard = scope().with_info("main").enter();
+
+        let addons = std::thread::spawn(scoped_addons).join().unwrap();
+
+        assert!(addons.is_empty(), "Scope leaked to other thread.");
+    }
+
+    #[test]
+    fn scope_active_while_future_is_polled() {
+        let mut scoped = scope()
+            .with_info("task")
+            .in_scope(async { scoped_addons() });
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        match Pin::new(&mut scoped).poll(&mut cx) {
+            Poll::Ready(addons) => assert_eq!(
+                addons,
+                vec![AddonKind::Info("task".to_string())],
+                "Scope not active while polling future."
+            ),
+            Poll::Pending => panic!("Future without await returned pending."),
+        }
+
+        assert!(scoped_addons().is_empty(), "Scope active after polling.");
+    }
+
+    #[test]
+    fn scope_left_if_future_panics() {
+        let mut scoped = scope()
+            .with_info("task")
+            .in_scope(async { panic!("Task failed.") });
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = Pin::new(&mut scoped).poll(&mut cx);
+        }));
+
+        assert!(res.is_err(), "Polling did not panic.");
+        assert!(
+            scoped_addons().is_empty(),
+            "Scope still active after polling panicked."
+        );
+    }
+}
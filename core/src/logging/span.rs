@@ -0,0 +1,216 @@
+//! Contains the [`Span`] struct to create timed log-id events.
+//!
+//! A span measures the time between its creation and its end.
+//! Once a span ends, one event is finalized that contains the elapsed duration, the start and end timestamps,
+//! and all events created on the same thread while the span was active as related entries.
+//!
+//! **Note:** Only the latest [`MAX_RELATED_ENTRIES`] events are related to a span,
+//! so long-lived spans do not grow without bound.
+//! The number of events that were not related is stored in the [`RELATED_DROPPED_FIELD`] field.
+
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    marker::PhantomData,
+    rc::Rc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use evident::event::{finalized::FinalizedEvent, origin::Origin};
+
+use crate::log_id::LogId;
+
+use super::{
+    event_entry::{AddonKind, LogEventEntry},
+    field::LogField,
+    intermediary_event::IntermediaryLogEvent,
+    logger::Logger,
+    msg::LogMsg,
+};
+
+/// Key of the field storing the elapsed duration of a span.
+pub const ELAPSED_FIELD: &str = "elapsed";
+/// Key of the field storing the start timestamp of a span.
+pub const START_FIELD: &str = "start";
+/// Key of the field storing the end timestamp of a span.
+pub const END_FIELD: &str = "end";
+/// Key of the field storing the number of events that were not related to a span, because it already had [`MAX_RELATED_ENTRIES`] related entries.
+pub const RELATED_DROPPED_FIELD: &str = "related_dropped";
+
+/// Maximum number of related entries kept per span.
+pub const MAX_RELATED_ENTRIES: usize = 1024;
+
+thread_local! {
+    /// Stack of active spans on this thread.
+    static SPAN_FRAMES: RefCell<Vec<SpanFrame>> = const { RefCell::new(Vec::new()) };
+}
+
+struct SpanFrame {
+    id: usize,
+    related: Rc<RefCell<RelatedEntries>>,
+}
+
+/// Latest events related to a span.
+#[derive(Default)]
+struct RelatedEntries {
+    entries: VecDeque<FinalizedEvent<LogId>>,
+    dropped: usize,
+}
+
+impl RelatedEntries {
+    fn push(&mut self, event: FinalizedEvent<LogId>) {
+        if self.entries.len() == MAX_RELATED_ENTRIES {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+
+        self.entries.push_back(event);
+    }
+}
+
+fn next_span_id() -> usize {
+    static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Guard of a timed log-id event.
+///
+/// The event is finalized on [`finish()`](Self::finish), or when the span is dropped.
+///
+/// **Note:** Use the `span!()` macro to create a span.
+#[must_use = "the span ends immediately if it is not kept"]
+pub struct Span {
+    frame_id: usize,
+    event_id: LogId,
+    msg: Option<LogMsg>,
+    origin: Origin,
+    /// Logger capturing the span event, or `None` to capture it with the [`LOGGER`](super::LOGGER).
+    target: Option<Logger>,
+    start: SystemTime,
+    start_instant: Instant,
+    related: Rc<RefCell<RelatedEntries>>,
+    finished: bool,
+    // Span must end on the thread it was started on
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Span {
+    /// Starts a new span for the given [`LogId`].
+    pub fn new(event_id: LogId, msg: Option<impl Into<LogMsg>>, origin: Origin) -> Self {
+        Span::with_target(None, event_id, msg, origin)
+    }
+
+    /// Starts a new span whose event is captured by the given logger, or by the [`LOGGER`](super::LOGGER) if no logger is given.
+    pub fn with_target(
+        target: Option<&Logger>,
+        event_id: LogId,
+        msg: Option<impl Into<LogMsg>>,
+        origin: Origin,
+    ) -> Self {
+        let frame_id = next_span_id();
+        let related = Rc::new(RefCell::new(RelatedEntries::default()));
+
+        SPAN_FRAMES.with(|frames| {
+            frames.borrow_mut().push(SpanFrame {
+                id: frame_id,
+                related: related.clone(),
+            })
+        });
+
+        Span {
+            frame_id,
+            event_id,
+            msg: msg.map(Into::into),
+            origin,
+            target: target.cloned(),
+            start: SystemTime::now(),
+            start_instant: Instant::now(),
+            related,
+            finished: false,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the [`LogId`] of this span.
+    pub fn get_event_id(&self) -> LogId {
+        self.event_id
+    }
+
+    /// Returns the duration since the span was started.
+    pub fn elapsed(&self) -> Duration {
+        self.start_instant.elapsed()
+    }
+
+    /// Ends the span, and finalizes its event.
+    pub fn finish(mut self) -> FinalizedEvent<LogId> {
+        self.end()
+    }
+
+    fn end(&mut self) -> FinalizedEvent<LogId> {
+        self.finished = true;
+        let elapsed = self.elapsed();
+        let end = self.start + elapsed;
+
+        // Note: Frame must be removed before creating the span event to not relate the span event to itself
+        let frame_id = self.frame_id;
+        let _ = SPAN_FRAMES.try_with(|frames| {
+            frames.borrow_mut().retain(|frame| frame.id != frame_id);
+        });
+
+        let event = IntermediaryLogEvent::with_target(
+            self.target.as_ref(),
+            self.event_id,
+            self.msg.take(),
+            self.origin.clone(),
+        );
+
+        let mut event = event
+            .add_addon(AddonKind::Field(LogField::new(ELAPSED_FIELD, elapsed)))
+            .add_addon(AddonKind::Field(LogField::new(START_FIELD, self.start)))
+            .add_addon(AddonKind::Field(LogField::new(END_FIELD, end)));
+
+        let related = self.related.take();
+        if related.dropped > 0 {
+            event = event.add_addon(AddonKind::Field(LogField::new(
+                RELATED_DROPPED_FIELD,
+                related.dropped,
+            )));
+        }
+
+        for entry in related.entries {
+            event = event.add_addon(AddonKind::Related(entry));
+        }
+
+        event.finalize()
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.end();
+        }
+    }
+}
+
+impl std::fmt::Debug for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Span")
+            .field("event_id", &self.event_id)
+            .field("origin", &self.origin)
+            .field("start", &self.start)
+            .finish()
+    }
+}
+
+/// Adds the given event as related entry to all active spans on the current thread.
+pub(crate) fn relate_to_spans(event: &LogEventEntry) {
+    let _ = SPAN_FRAMES.try_with(|frames| {
+        for frame in frames.borrow().iter() {
+            frame
+                .related
+                .borrow_mut()
+                .push(FinalizedEvent::new(event.event_id, event.entry_id));
+        }
+    });
+}
@@ -216,3 +216,27 @@ fn allow_general_level_with_infos_addon() {
         "Info addon not allowed by filter."
     );
 }
+
+#[test]
+fn allow_single_id_with_fields_addon() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let filter = FilterConfig::new(&format!(
+        "on[{}::{}(fields)]",
+        log_id.get_module_path(),
+        log_id.get_identifier()
+    ));
+
+    assert!(
+        filter.allow_addon(log_id, &this_origin!(), &crate::field!("user_id", 42)),
+        "Field addon not allowed by filter."
+    );
+
+    assert!(
+        !filter.allow_addon(
+            log_id,
+            &this_origin!(),
+            &AddonKind::Info("Some info".to_string())
+        ),
+        "Info addon allowed by filter."
+    );
+}
@@ -0,0 +1,247 @@
+use crate::{
+    log_id::LogLevel,
+    logging::{
+        event_entry::LogEventEntry,
+        field::LogField,
+        filter::{FilterConfig, FilterConfigBuilder, LogIdAddonFilter},
+    },
+    new_log_id,
+};
+use evident::{
+    event::{entry::EventEntry, filter::Filter},
+    this_origin,
+};
+
+#[test]
+fn general_level_with_msg_contains() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let filter = FilterConfig::new("info{msg ~ timeout}");
+
+    let matching_entry =
+        LogEventEntry::new(log_id, Some("Request timeout reached."), this_origin!());
+    let other_entry = LogEventEntry::new(log_id, Some("Request handled."), this_origin!());
+
+    assert!(
+        filter.allow_entry(&matching_entry),
+        "Entry with matching message not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&other_entry),
+        "Entry with non-matching message allowed by filter."
+    );
+}
+
+#[test]
+fn single_id_with_numeric_field_comparison() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let filter = FilterConfig::new(&format!(
+        "on[{}::{}{{status >= 500 & route}}]",
+        log_id.get_module_path(),
+        log_id.get_identifier()
+    ));
+
+    let mut server_error = LogEventEntry::new(log_id, Some("Request failed."), this_origin!());
+    server_error.fields.push(LogField::new("status", 503));
+    server_error.fields.push(LogField::new("route", "/users"));
+
+    let mut success = LogEventEntry::new(log_id, Some("Request handled."), this_origin!());
+    success.fields.push(LogField::new("status", 200));
+    success.fields.push(LogField::new("route", "/users"));
+
+    let mut no_route = LogEventEntry::new(log_id, Some("Request failed."), this_origin!());
+    no_route.fields.push(LogField::new("status", 500));
+
+    assert!(
+        filter.allow_entry(&server_error),
+        "Entry with matching fields not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&success),
+        "Entry with status below threshold allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&no_route),
+        "Entry without required field allowed by filter."
+    );
+}
+
+#[test]
+fn single_id_with_content_does_not_restrict_addons() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let filter = FilterConfig::new(&format!(
+        "on[{}::{}(infos){{status = 500}}]",
+        log_id.get_module_path(),
+        log_id.get_identifier()
+    ));
+
+    assert!(
+        filter.allow_addon(
+            log_id,
+            &this_origin!(),
+            &crate::logging::event_entry::AddonKind::Info("Some info".to_string())
+        ),
+        "Info addon not allowed by filter with content filter."
+    );
+}
+
+#[test]
+fn module_level_with_field_string_comparison() {
+    let log_id = new_log_id!("log_id", LogLevel::Warn);
+    let filter = FilterConfig::new(&format!("{}{{user = bob}} = warn", module_path!()));
+
+    let mut bob_entry = LogEventEntry::new(log_id, Some("Login failed."), this_origin!());
+    bob_entry.fields.push(LogField::new("user", "bob"));
+
+    let mut alice_entry = LogEventEntry::new(log_id, Some("Login failed."), this_origin!());
+    alice_entry.fields.push(LogField::new("user", "alice"));
+
+    assert!(
+        filter.allow_entry(&bob_entry),
+        "Entry with matching field not allowed by module filter."
+    );
+    assert!(
+        !filter.allow_entry(&alice_entry),
+        "Entry with non-matching field allowed by module filter."
+    );
+}
+
+#[test]
+fn invalid_content_filter_rejects_filter() {
+    let log_id = new_log_id!("log_id", LogLevel::Error);
+
+    for invalid in ["info{>= 500}", "error{msg ~ x & >}", "error{msg ~ \"x}"] {
+        assert!(
+            FilterConfig::try_new(invalid).is_err(),
+            "Invalid content filter '{}' was accepted.",
+            invalid
+        );
+        assert!(
+            !FilterConfig::new(invalid).allow_entry(&LogEventEntry::new(
+                log_id,
+                Some("msg"),
+                this_origin!()
+            )),
+            "Invalid content filter '{}' widened the allowed entries.",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn quoted_content_values_keep_separators() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let filter =
+        FilterConfig::try_new(r#"info{msg ~ "a, b & {c}" & route = "/x\"y"}, other_crate = warn"#)
+            .unwrap();
+
+    let mut matching_entry =
+        LogEventEntry::new(log_id, Some("Values a, b & {c} found."), this_origin!());
+    matching_entry.fields.push(LogField::new("route", "/x\"y"));
+    let mut other_entry = LogEventEntry::new(log_id, Some("Values a found."), this_origin!());
+    other_entry.fields.push(LogField::new("route", "/x\"y"));
+
+    assert!(
+        filter.allow_entry(&matching_entry),
+        "Entry matching quoted values not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&other_entry),
+        "Quoted value was split at its separators."
+    );
+}
+
+#[test]
+fn closure_predicates_set_via_builder() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let other_id = new_log_id!("other_id", LogLevel::Debug);
+    let filter = FilterConfigBuilder::new(LogLevel::Info)
+        .content_predicate(|entry| entry.get_field("tenant").is_some())
+        .global_ids(LogIdAddonFilter::new(other_id).content_predicate(|entry| {
+            entry
+                .get_msg()
+                .map(|msg| msg.to_string().starts_with("retry"))
+                .unwrap_or(false)
+        }))
+        .build();
+
+    let mut tenant_entry = LogEventEntry::new(log_id, Some("msg"), this_origin!());
+    tenant_entry.fields.push(LogField::new("tenant", "acme"));
+
+    assert!(
+        filter.allow_entry(&tenant_entry),
+        "Entry matching general predicate not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&LogEventEntry::new(log_id, Some("msg"), this_origin!())),
+        "Entry not matching general predicate allowed by filter."
+    );
+    assert!(
+        filter.allow_entry(&LogEventEntry::new(
+            other_id,
+            Some("retry 2"),
+            this_origin!()
+        )),
+        "Entry matching id predicate not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&LogEventEntry::new(other_id, Some("done"), this_origin!())),
+        "Entry not matching id predicate allowed by filter."
+    );
+}
+
+#[cfg(feature = "payloads")]
+#[test]
+fn single_id_with_payload_pointer() {
+    let log_id = new_log_id!("log_id", LogLevel::Info);
+    let filter = FilterConfig::new(&format!(
+        "on[{}::{}{{/response/status > 499}}]",
+        log_id.get_module_path(),
+        log_id.get_identifier()
+    ));
+
+    let mut failed = LogEventEntry::new(log_id, Some("msg"), this_origin!());
+    failed
+        .payloads
+        .push(crate::serde_json::json!({ "response": { "status": 502 } }));
+
+    let mut handled = LogEventEntry::new(log_id, Some("msg"), this_origin!());
+    handled
+        .payloads
+        .push(crate::serde_json::json!({ "response": { "status": 204 } }));
+
+    assert!(
+        filter.allow_entry(&failed),
+        "Entry with matching payload member not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&handled),
+        "Entry with non-matching payload member allowed by filter."
+    );
+}
+
+#[test]
+fn event_precheck_ignores_content() {
+    let debug_id = new_log_id!("debug_id", LogLevel::Debug);
+    let filter = FilterConfig::new(&format!(
+        "warn{{msg ~ disk}},on[{}::{}]",
+        debug_id.get_module_path(),
+        debug_id.get_identifier()
+    ));
+
+    let warn_id = new_log_id!("warn_id", LogLevel::Warn);
+    assert!(
+        filter.allow_event(warn_id, &this_origin!()),
+        "Event rejected before its content is known."
+    );
+
+    let info_id = new_log_id!("info_id", LogLevel::Info);
+    assert!(
+        !filter.allow_event(info_id, &this_origin!()),
+        "Info level event allowed by pre-check."
+    );
+
+    assert!(
+        filter.allow_event(debug_id, &this_origin!()),
+        "Globally allowed LogId rejected by pre-check."
+    );
+}
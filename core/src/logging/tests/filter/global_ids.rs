@@ -59,3 +59,27 @@ fn invalid_ids_syntax() {
         "Invalid filter syntax allowed LogId by filter."
     );
 }
+
+#[test]
+fn allow_single_id_by_code() {
+    let coded_id = new_log_id!("coded_id", LogLevel::Info, "E0042");
+    let other_id = new_log_id!("other_id", LogLevel::Info, "E0043");
+    let filter = FilterConfig::new("on[#E0042(infos)]");
+
+    assert!(
+        filter.allow_entry(&test_entry(coded_id, this_origin!())),
+        "LogId allowed by code not allowed by filter."
+    );
+    assert!(
+        !filter.allow_entry(&test_entry(other_id, this_origin!())),
+        "LogId with other code allowed by filter."
+    );
+    assert!(
+        filter.allow_addon(
+            coded_id,
+            &this_origin!(),
+            &crate::logging::event_entry::AddonKind::Info("Some info".to_string())
+        ),
+        "Info addon not allowed for LogId allowed by code."
+    );
+}
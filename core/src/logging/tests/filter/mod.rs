@@ -6,6 +6,7 @@ use crate::{
 };
 
 pub mod addons;
+pub mod content;
 pub mod global_ids;
 pub mod only_general;
 pub mod only_module;
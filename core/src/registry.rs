@@ -0,0 +1,296 @@
+//! Contains the registry of all [`LogId`]s defined via the derive macros or [`new_log_id!`](crate::new_log_id).
+//!
+//! [`LogId`]s are collected at link time, so all [`LogId`]s of all linked crates are available
+//! without constructing them first.
+//!
+//! **Note:** Only available with feature `registry`.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::log_id::LogId;
+
+/// A [`LogId`] together with the location it is defined at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisteredLogId {
+    log_id: &'static LogId,
+    file: &'static str,
+    line: u32,
+    doc: &'static str,
+    error_msg: Option<&'static str>,
+    /// Long-form explanation of the [`LogId`] (e.g. causes and remediation).
+    explanation: Option<&'static str>,
+    /// URL to documentation about the [`LogId`].
+    help_url: Option<&'static str>,
+}
+
+inventory::collect!(RegisteredLogId);
+
+impl RegisteredLogId {
+    #[doc(hidden)]
+    pub const fn new(
+        log_id: &'static LogId,
+        file: &'static str,
+        line: u32,
+        doc: &'static str,
+    ) -> Self {
+        RegisteredLogId {
+            log_id,
+            file,
+            line,
+            doc,
+            error_msg: None,
+            explanation: None,
+            help_url: None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_error_msg(mut self, error_msg: Option<&'static str>) -> Self {
+        self.error_msg = error_msg;
+        self
+    }
+
+    #[doc(hidden)]
+    pub const fn with_explanation(mut self, explanation: Option<&'static str>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+
+    #[doc(hidden)]
+    pub const fn with_help_url(mut self, help_url: Option<&'static str>) -> Self {
+        self.help_url = help_url;
+        self
+    }
+
+    pub fn get_log_id(&self) -> LogId {
+        *self.log_id
+    }
+
+    pub fn get_module_path(&self) -> &'static str {
+        self.log_id.module_path
+    }
+
+    pub fn get_identifier(&self) -> &'static str {
+        self.log_id.identifier
+    }
+
+    pub fn get_log_level(&self) -> crate::log_id::LogLevel {
+        self.log_id.log_level
+    }
+
+    pub fn get_code(&self) -> Option<&'static str> {
+        self.log_id.code
+    }
+
+    /// Returns the source file the [`LogId`] is defined in.
+    pub fn get_file(&self) -> &'static str {
+        self.file
+    }
+
+    /// Returns the line in the source file the [`LogId`] is defined at.
+    pub fn get_line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the doc comment of the enum variant or struct the [`LogId`] is derived for.
+    ///
+    /// **Note:** The doc comment is empty for [`LogId`]s created with [`new_log_id!`](crate::new_log_id).
+    pub fn get_doc(&self) -> &'static str {
+        self.doc
+    }
+
+    /// Returns the message set via `#[error("...")]` on the enum variant or struct the [`LogId`] is derived for.
+    ///
+    /// **Note:** Format arguments are not resolved (e.g. `Could not read {path}.`).
+    pub fn get_error_msg(&self) -> Option<&'static str> {
+        self.error_msg
+    }
+
+    /// Returns the long-form explanation set via `#[log_id(explain = "...")]` or `#[log_id(explain_file = "...")]`.
+    pub fn get_explanation(&self) -> Option<&'static str> {
+        self.explanation
+    }
+
+    /// Returns the URL to documentation set via `#[log_id(help_url = "...")]`.
+    pub fn get_help_url(&self) -> Option<&'static str> {
+        self.help_url
+    }
+}
+
+/// Returns all registered [`LogId`]s sorted by module path and identifier.
+pub fn all_ids() -> Vec<&'static RegisteredLogId> {
+    let mut ids: Vec<_> = inventory::iter::<RegisteredLogId>.into_iter().collect();
+    ids.sort_by(|a, b| {
+        (a.get_module_path(), a.get_identifier()).cmp(&(b.get_module_path(), b.get_identifier()))
+    });
+    ids
+}
+
+/// Registered entries by [`LogId`], and by code.
+struct Index {
+    by_id: HashMap<LogId, &'static RegisteredLogId>,
+    by_code: HashMap<&'static str, &'static RegisteredLogId>,
+}
+
+/// Returns the index of all registered entries, which is created on first use.
+fn index() -> &'static Index {
+    static INDEX: OnceLock<Index> = OnceLock::new();
+
+    INDEX.get_or_init(|| {
+        let mut index = Index {
+            by_id: HashMap::new(),
+            by_code: HashMap::new(),
+        };
+
+        for registered in inventory::iter::<RegisteredLogId> {
+            index.by_id.insert(*registered.log_id, registered);
+            if let Some(code) = registered.log_id.code {
+                index.by_code.insert(code, registered);
+            }
+        }
+
+        index
+    })
+}
+
+/// Returns the registered entry of the given [`LogId`], if it is registered.
+pub fn find(log_id: &LogId) -> Option<&'static RegisteredLogId> {
+    index().by_id.get(log_id).copied()
+}
+
+/// Returns the registered entry with the given code, if one is registered.
+pub fn find_by_code(code: &str) -> Option<&'static RegisteredLogId> {
+    index().by_code.get(code).copied()
+}
+
+/// Returns all codes that are used by more than one registered [`LogId`], together with the entries using them.
+///
+/// The derive macros only reject codes used twice by the same type,
+/// so codes used by different types or crates are only detected at runtime (e.g. in a test of the application).
+pub fn duplicate_codes() -> Vec<(&'static str, Vec<&'static RegisteredLogId>)> {
+    let mut by_code: HashMap<&'static str, Vec<&'static RegisteredLogId>> = HashMap::new();
+
+    for registered in all_ids() {
+        if let Some(code) = registered.log_id.code {
+            let entries = by_code.entry(code).or_default();
+            if !entries
+                .iter()
+                .any(|entry| entry.log_id == registered.log_id)
+            {
+                entries.push(registered);
+            }
+        }
+    }
+
+    let mut duplicates: Vec<_> = by_code
+        .into_iter()
+        .filter(|(_, entries)| entries.len() > 1)
+        .collect();
+    duplicates.sort_by_key(|(code, _)| *code);
+    duplicates
+}
+
+/// Returns the explanation of the registered [`LogId`] with the given code or identifier, if any.
+///
+/// Meant to implement commands like `myapp --explain E0042`.
+pub fn explain(code_or_identifier: &str) -> Option<&'static str> {
+    find_by_code(code_or_identifier)
+        .or_else(|| {
+            inventory::iter::<RegisteredLogId>
+                .into_iter()
+                .find(|registered| registered.log_id.identifier == code_or_identifier)
+        })
+        .and_then(|registered| registered.get_explanation())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{log_id::LogLevel, new_log_id};
+
+    const REGISTERED_ID: LogId = new_log_id!("registered_id", LogLevel::Warn, "W0001");
+    const EXPLAINED_ID: LogId =
+        LogId::new(module_path!(), "explained_id", LogLevel::Error).with_code("E0041");
+
+    crate::__register_log_id!(
+        EXPLAINED_ID,
+        "",
+        None,
+        Some("Some long explanation."),
+        Some("https://example.com/E0041")
+    );
+
+    #[test]
+    fn new_log_id_registered() {
+        let registered = find(&REGISTERED_ID).expect("LogId not registered.");
+
+        assert_eq!(
+            registered.get_file(),
+            file!(),
+            "Wrong file set for registered LogId."
+        );
+        assert!(
+            all_ids().contains(&registered),
+            "Registered LogId not returned by `all_ids()`."
+        );
+        assert_eq!(
+            find_by_code("W0001"),
+            Some(registered),
+            "Registered LogId not found by code."
+        );
+    }
+
+    #[test]
+    fn duplicate_codes_found() {
+        const FIRST_ID: LogId = new_log_id!("first_coded_id", LogLevel::Warn, "D0001");
+        const SECOND_ID: LogId = new_log_id!("second_coded_id", LogLevel::Warn, "D0001");
+
+        let duplicates = duplicate_codes();
+        let (_, entries) = duplicates
+            .iter()
+            .find(|(code, _)| *code == "D0001")
+            .expect("Duplicate code not found.");
+        let ids: Vec<_> = entries.iter().map(|entry| entry.get_log_id()).collect();
+
+        assert_eq!(
+            ids,
+            vec![FIRST_ID, SECOND_ID],
+            "Wrong log-ids found for code."
+        );
+        assert!(
+            !duplicates.iter().any(|(code, _)| *code == "W0001"),
+            "Code used once reported as duplicate."
+        );
+    }
+
+    #[test]
+    fn help_refers_to_explain_command_and_url() {
+        assert_eq!(
+            find_by_code("E0041").and_then(RegisteredLogId::get_explanation),
+            Some("Some long explanation."),
+            "Explanation not registered."
+        );
+        assert_eq!(
+            EXPLAINED_ID.explanation(),
+            Some("Some long explanation."),
+            "Explanation not found for log-id."
+        );
+        assert_eq!(
+            REGISTERED_ID.explanation(),
+            None,
+            "Explanation found for log-id without explanation."
+        );
+
+        crate::log_id::set_explain_command("myapp --explain");
+        assert_eq!(
+            EXPLAINED_ID.help(),
+            Some("run `myapp --explain E0041`, or see https://example.com/E0041".to_string()),
+            "Help does not refer to explain command and URL."
+        );
+        assert_eq!(
+            REGISTERED_ID.help(),
+            None,
+            "Help set without explanation or URL."
+        );
+    }
+}
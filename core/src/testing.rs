@@ -0,0 +1,175 @@
+//! Contains helpers to assert on log-id events in tests.
+//!
+//! The [`LOGGER`](crate::logging::LOGGER) is shared by all tests of a test binary,
+//! so subscriptions also receive events of tests running in parallel.
+//! [`capture_events()`] instead only collects events set on the current thread while the returned guard is alive.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid_core::{
+//!     evident::{event::intermediary::IntermediaryEvent, this_origin},
+//!     log_id::{LogId, LogLevel},
+//!     logging::{intermediary_event::IntermediaryLogEvent, msg::NO_MSG},
+//!     new_log_id,
+//!     testing::capture_events,
+//! };
+//!
+//! const LOADED: LogId = new_log_id!("Loaded", LogLevel::Info);
+//!
+//! let cap = capture_events();
+//! IntermediaryLogEvent::new(LOADED, NO_MSG, this_origin!()).finalize();
+//!
+//! cap.assert_logged(LOADED);
+//! cap.assert_no_errors();
+//! ~~~
+//!
+//! **Note:** Events are only collected if they pass the filter of their target, and are set on the thread that created the guard.
+//! Helpers are only available with feature `testing`, so release builds do not record events.
+
+use std::{cell::RefCell, rc::Rc};
+
+use evident::event::entry::EventEntry;
+
+use crate::{
+    log_id::{LogId, LogLevel},
+    logging::event_entry::LogEventEntry,
+};
+
+type Collector = Rc<RefCell<Vec<LogEventEntry>>>;
+
+thread_local! {
+    /// Collectors of all active capture guards on this thread.
+    static COLLECTORS: RefCell<Vec<Collector>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Starts collecting all events set on the current thread until the returned guard is dropped.
+pub fn capture_events() -> CapturedEvents {
+    let collector = Collector::default();
+    let _ = COLLECTORS.try_with(|collectors| collectors.borrow_mut().push(collector.clone()));
+
+    CapturedEvents { collector }
+}
+
+/// Adds the given entry to all active collectors of the current thread.
+pub(crate) fn record(entry: &LogEventEntry) {
+    let _ = COLLECTORS.try_with(|collectors| {
+        for collector in collectors.borrow().iter() {
+            collector.borrow_mut().push(entry.clone());
+        }
+    });
+}
+
+/// Guard collecting events set on the current thread.
+///
+/// Collecting stops once the guard is dropped.
+#[derive(Debug)]
+pub struct CapturedEvents {
+    collector: Collector,
+}
+
+impl CapturedEvents {
+    /// Returns all collected entries in the order their events were captured.
+    pub fn entries(&self) -> Vec<LogEventEntry> {
+        self.collector.borrow().clone()
+    }
+
+    /// Returns all collected entries set with the given log-id.
+    pub fn entries_of(&self, id: impl Into<LogId>) -> Vec<LogEventEntry> {
+        let id = id.into();
+        self.collector
+            .borrow()
+            .iter()
+            .filter(|entry| *entry.get_event_id() == id)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns `true` if an event with the given log-id was collected.
+    pub fn logged(&self, id: impl Into<LogId>) -> bool {
+        !self.entries_of(id).is_empty()
+    }
+
+    /// Removes all collected entries.
+    pub fn clear(&self) {
+        self.collector.borrow_mut().clear();
+    }
+
+    /// Panics if no event with the given log-id was collected.
+    #[track_caller]
+    pub fn assert_logged(&self, id: impl Into<LogId>) {
+        let id = id.into();
+        assert!(
+            self.logged(id),
+            "No event with log-id '{}' was logged. Logged: [{}]",
+            id,
+            self.logged_ids()
+        );
+    }
+
+    /// Panics if an event with the given log-id was collected.
+    #[track_caller]
+    pub fn assert_not_logged(&self, id: impl Into<LogId>) {
+        let id = id.into();
+        assert!(
+            !self.logged(id),
+            "Event with log-id '{}' was logged unexpectedly.",
+            id
+        );
+    }
+
+    /// Panics if no event with the given log-id has an info addon containing the given text.
+    #[track_caller]
+    pub fn assert_info_contains(&self, id: impl Into<LogId>, text: &str) {
+        let id = id.into();
+        let entries = self.entries_of(id);
+        assert!(
+            entries
+                .iter()
+                .any(|entry| entry.get_infos().iter().any(|info| info.contains(text))),
+            "No event with log-id '{}' has an info containing '{}'. Infos: {:?}",
+            id,
+            text,
+            entries
+                .iter()
+                .flat_map(|entry| entry.get_infos())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Panics if an event with level [`LogLevel::Error`] was collected.
+    #[track_caller]
+    pub fn assert_no_errors(&self) {
+        let errors: Vec<_> = self
+            .collector
+            .borrow()
+            .iter()
+            .filter(|entry| entry.get_level() == LogLevel::Error)
+            .map(|entry| entry.get_event_id().to_string())
+            .collect();
+        assert!(
+            errors.is_empty(),
+            "Errors were logged: [{}]",
+            errors.join(", ")
+        );
+    }
+
+    fn logged_ids(&self) -> String {
+        self.collector
+            .borrow()
+            .iter()
+            .map(|entry| entry.get_event_id().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Drop for CapturedEvents {
+    fn drop(&mut self) {
+        let _ = COLLECTORS.try_with(|collectors| {
+            collectors
+                .borrow_mut()
+                .retain(|collector| !Rc::ptr_eq(collector, &self.collector));
+        });
+    }
+}
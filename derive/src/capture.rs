@@ -0,0 +1,77 @@
+//! Generation of `CaptureAddons` implementations for `#[log_id(capture = "...")]`.
+
+use logid_attrs::{CaptureMode, LogIdAttrs};
+use quote::{format_ident, quote_spanned};
+
+/// Returns the pattern binding all captured fields, and the expression creating the addons for them.
+///
+/// `path` is the path used in the pattern (e.g. `Self::Variant` or `Self`).
+pub(crate) fn capture_arm(
+    path: proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    mode: CaptureMode,
+    span: proc_macro2::Span,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let mut names = Vec::new();
+    let mut bindings = Vec::new();
+    let mut pattern_parts = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let skip =
+            LogIdAttrs::parse_field(&field.attrs)?.skip.is_some() || mode == CaptureMode::Off;
+
+        match &field.ident {
+            Some(ident) => {
+                if !skip {
+                    let binding = format_ident!("__log_id_{}", ident);
+                    pattern_parts.push(quote_spanned! {span=> #ident: #binding });
+                    names.push(ident.to_string());
+                    bindings.push(binding);
+                }
+            }
+            None => {
+                if skip {
+                    pattern_parts.push(quote_spanned! {span=> _ });
+                } else {
+                    let binding = format_ident!("__log_id_{}", i);
+                    pattern_parts.push(quote_spanned! {span=> #binding });
+                    names.push(i.to_string());
+                    bindings.push(binding);
+                }
+            }
+        }
+    }
+
+    let pattern = match fields {
+        syn::Fields::Named(_) => quote_spanned! {span=> #path { #(#pattern_parts,)* .. } },
+        syn::Fields::Unnamed(_) => quote_spanned! {span=> #path ( #(#pattern_parts),* ) },
+        syn::Fields::Unit => quote_spanned! {span=> #path },
+    };
+
+    let body = if bindings.is_empty() {
+        quote_spanned! {span=> Vec::new() }
+    } else {
+        match mode {
+            CaptureMode::Infos => quote_spanned! {span=>
+                vec![
+                    #(logid::logging::event_entry::AddonKind::Info(format!("{}: {:?}", #names, #bindings)),)*
+                ]
+            },
+            CaptureMode::Payload => quote_spanned! {span=>
+                {
+                    let mut payload = logid::serde_json::Map::new();
+                    #(
+                        payload.insert(
+                            #names.to_string(),
+                            logid::serde_json::to_value(#bindings).unwrap_or(logid::serde_json::Value::Null),
+                        );
+                    )*
+                    vec![logid::logging::event_entry::AddonKind::Payload(logid::serde_json::Value::Object(payload))]
+                }
+            },
+            CaptureMode::Off => quote_spanned! {span=> Vec::new() },
+        }
+    };
+
+    Ok((pattern, body))
+}
@@ -0,0 +1,28 @@
+//! Checks that codes set via `#[log_id(code = "...")]` are unique.
+//!
+//! **Note:** Only codes of the same type are compared at compile time.
+//! Use `logid::registry::duplicate_codes()` with feature `registry` to find codes used by different types or crates.
+
+use std::collections::HashMap;
+
+use syn::LitStr;
+
+/// Returns an error if a code is used more than once by the given type.
+pub(crate) fn check_unique_codes(codes: &[(LitStr, String)]) -> syn::Result<()> {
+    let mut type_codes: HashMap<String, &str> = HashMap::new();
+
+    for (code, owner) in codes {
+        if let Some(other_owner) = type_codes.insert(code.value(), owner) {
+            return Err(syn::Error::new(
+                code.span(),
+                format!(
+                    "duplicate log-id code `{}`, already used by `{}`",
+                    code.value(),
+                    other_owner
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
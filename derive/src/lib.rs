@@ -3,45 +3,115 @@ use proc_macro::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(ErrLogId)]
+mod capture;
+mod codes;
+
+use capture::capture_arm;
+use codes::check_unique_codes;
+use logid_attrs::{
+    doc_comment, error_msg, require_level, variant_identifier, CaptureMode, LogIdAttrs,
+};
+
+/// Derives conversion into [`LogId`](logid_core::log_id::LogId) with the level set via `#[log_id(level = "<level>")]`.
+///
+/// The level may be set on the type, and overwritten for single enum variants.
+/// The identifier of a variant or struct may be customized via `#[log_id(name = "<identifier>")]`, or `#[log_id(id = "<identifier>")]`.
+/// Generic enums and structs are supported.
+/// A long-form explanation may be set via `#[log_id(explain = "<text>")]`, or `#[log_id(explain_file = "<path>")]`
+/// with a path relative to the package root, and a help URL via `#[log_id(help_url = "<url>")]`.
+/// Both are stored in the registry of feature `registry`, and are not part of the [`LogId`](logid_core::log_id::LogId).
+#[proc_macro_derive(LogId, attributes(log_id))]
+pub fn derive_leveled_log_id(input: TokenStream) -> TokenStream {
+    derive_log_id(input, None)
+}
+
+#[proc_macro_derive(ErrLogId, attributes(log_id))]
 pub fn derive_err_log_id(input: TokenStream) -> TokenStream {
-    derive_log_id(input, LogLevel::Error)
+    derive_log_id(input, Some(LogLevel::Error))
 }
 
-#[proc_macro_derive(WarnLogId)]
+#[proc_macro_derive(WarnLogId, attributes(log_id))]
 pub fn derive_warn_log_id(input: TokenStream) -> TokenStream {
-    derive_log_id(input, LogLevel::Warn)
+    derive_log_id(input, Some(LogLevel::Warn))
 }
 
-#[proc_macro_derive(InfoLogId)]
+#[proc_macro_derive(InfoLogId, attributes(log_id))]
 pub fn derive_info_log_id(input: TokenStream) -> TokenStream {
-    derive_log_id(input, LogLevel::Info)
+    derive_log_id(input, Some(LogLevel::Info))
 }
 
-#[proc_macro_derive(DbgLogId)]
+#[proc_macro_derive(DbgLogId, attributes(log_id))]
 pub fn derive_dbg_log_id(input: TokenStream) -> TokenStream {
-    derive_log_id(input, LogLevel::Debug)
+    derive_log_id(input, Some(LogLevel::Debug))
 }
 
-#[proc_macro_derive(TraceLogId)]
+#[proc_macro_derive(TraceLogId, attributes(log_id))]
 pub fn derive_trace_log_id(input: TokenStream) -> TokenStream {
-    derive_log_id(input, LogLevel::Trace)
+    derive_log_id(input, Some(LogLevel::Trace))
 }
 
-fn derive_log_id(input: TokenStream, log_level: LogLevel) -> TokenStream {
+fn derive_log_id(input: TokenStream, log_level: Option<LogLevel>) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let ident_name = input.ident;
 
-    let log_token = log_level_as_tokenstream(log_level);
+    match try_derive_log_id(input, log_level) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_log_id(
+    input: DeriveInput,
+    log_level: Option<LogLevel>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let type_attrs = LogIdAttrs::parse(&input.attrs)?;
+    type_attrs.deny_field_only()?;
+    let log_level = type_attrs.level.or(log_level);
+    let ident_name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     match input.data {
         syn::Data::Enum(enum_data) => {
+            type_attrs.deny_variant_only()?;
             let span = ident_name.span();
 
             let mut field_identifiers = Vec::new();
+            let mut codes = Vec::new();
+            let mut capture_arms = Vec::new();
+            let mut captures = false;
+            let mut registrations = Vec::new();
 
             for variant in enum_data.variants {
+                let variant_attrs = LogIdAttrs::parse(&variant.attrs)?;
+                variant_attrs.deny_field_only()?;
+                let variant_level = require_level(variant_attrs.level.or(log_level), &variant)?;
+
+                let capture_mode = variant_attrs
+                    .capture
+                    .or(type_attrs.capture)
+                    .unwrap_or(CaptureMode::Off);
+                captures |= capture_mode != CaptureMode::Off;
+                let variant_ident = &variant.ident;
+                let (capture_pattern, capture_body) = capture_arm(
+                    quote_spanned! {span=> Self::#variant_ident },
+                    &variant.fields,
+                    capture_mode,
+                    span,
+                )?;
+                capture_arms.push(quote_spanned! {span=>
+                    #capture_pattern => #capture_body,
+                });
+
                 let field_name = variant.ident;
+                let full_field_name_str = variant_attrs.name.clone().unwrap_or_else(|| {
+                    syn::LitStr::new(
+                        &variant_identifier(&ident_name, &field_name, &variant.fields),
+                        span,
+                    )
+                });
+                if let Some(code) = &variant_attrs.code {
+                    codes.push((code.clone(), format!("{ident_name}::{field_name}")));
+                }
+
                 let full_field_name = match variant.fields {
                     syn::Fields::Named(_) => {
                         quote_spanned! {span=>
@@ -50,120 +120,322 @@ fn derive_log_id(input: TokenStream, log_level: LogLevel) -> TokenStream {
                     }
                     syn::Fields::Unnamed(_) => {
                         quote_spanned! {span=>
-                            #ident_name::#field_name(_)
+                            #ident_name::#field_name(..)
                         }
                     }
                     _ => quote_spanned! {span=>
                         #ident_name::#field_name
                     },
                 };
-                let full_field_name_str =
-                    syn::LitStr::new(&full_field_name.to_string().replace(' ', ""), span);
+                let log_id =
+                    log_id_tokens(&full_field_name_str, variant_level, &variant_attrs, span);
+                registrations.push(register_tokens(
+                    &log_id,
+                    &variant_attrs,
+                    &variant.attrs,
+                    field_name.span(),
+                )?);
                 field_identifiers.push(quote_spanned! {span=>
-                    #full_field_name => #full_field_name_str,
+                    #full_field_name => #log_id,
                 });
             }
 
-            let from_enum = quote_spanned! {span=>
-                impl From<#ident_name> for logid::log_id::LogId {
-                    fn from(value: #ident_name) -> Self {
-                        let field_name = match value {
+            check_unique_codes(&codes)?;
+
+            let capture_impl = captures.then(|| {
+                quote_spanned! {span=>
+                    impl #impl_generics logid::logging::capture::CaptureAddons for #ident_name #ty_generics #where_clause {
+                        fn capture_addons(&self) -> Vec<logid::logging::event_entry::AddonKind> {
+                            match self {
+                                #(#capture_arms)*
+                            }
+                        }
+                    }
+                }
+            });
+
+            Ok(quote_spanned! {span=>
+                impl #impl_generics From<&#ident_name #ty_generics> for logid::log_id::LogId #where_clause {
+                    fn from(value: &#ident_name #ty_generics) -> Self {
+                        match value {
                             #(#field_identifiers)*
-                        };
+                        }
+                    }
+                }
 
-                        logid::log_id::LogId::new(
-                            module_path!(),
-                            field_name,
-                            #log_token,
-                        )
+                impl #impl_generics From<#ident_name #ty_generics> for logid::log_id::LogId #where_clause {
+                    fn from(value: #ident_name #ty_generics) -> Self {
+                        (&value).into()
                     }
                 }
-            };
 
-            TokenStream::from(from_enum)
+                #capture_impl
+
+                #(#registrations)*
+            })
         }
         syn::Data::Struct(struct_data) => {
             let span = struct_data.struct_token.span;
-            from_struct_or_union(ident_name, log_token, span)
+            let log_level = require_level(log_level, &ident_name)?;
+
+            let capture_mode = type_attrs.capture.unwrap_or(CaptureMode::Off);
+            let (capture_pattern, capture_body) = capture_arm(
+                quote_spanned! {span=> Self },
+                &struct_data.fields,
+                capture_mode,
+                span,
+            )?;
+            let capture_impl = (capture_mode != CaptureMode::Off).then(|| {
+                quote_spanned! {span=>
+                    impl #impl_generics logid::logging::capture::CaptureAddons for #ident_name #ty_generics #where_clause {
+                        fn capture_addons(&self) -> Vec<logid::logging::event_entry::AddonKind> {
+                            let #capture_pattern = self;
+                            #capture_body
+                        }
+                    }
+                }
+            });
+
+            let from = from_struct_or_union(
+                &ident_name,
+                &input.generics,
+                type_attrs,
+                &input.attrs,
+                log_level,
+                span,
+            )?;
+            Ok(quote_spanned! {span=>
+                #from
+
+                #capture_impl
+            })
         }
         syn::Data::Union(union_data) => {
             let span = union_data.union_token.span;
-            from_struct_or_union(ident_name, log_token, span)
+            if let Some(CaptureMode::Infos | CaptureMode::Payload) = type_attrs.capture {
+                return Err(syn::Error::new(span, "fields of unions cannot be captured"));
+            }
+            let log_level = require_level(log_level, &ident_name)?;
+            from_struct_or_union(
+                &ident_name,
+                &input.generics,
+                type_attrs,
+                &input.attrs,
+                log_level,
+                span,
+            )
         }
     }
 }
 
+/// Returns the expression to create a [`LogId`](logid_core::log_id::LogId) in the module the derive is used in.
+///
+/// The code is set if given in the attributes.
+fn log_id_tokens(
+    identifier: &syn::LitStr,
+    log_level: LogLevel,
+    attrs: &LogIdAttrs,
+    span: proc_macro2::Span,
+) -> proc_macro2::TokenStream {
+    let log_token = log_level_as_tokenstream(log_level);
+    let with_code = attrs
+        .code
+        .as_ref()
+        .map(|code| quote_spanned! {span=> .with_code(#code) });
+
+    quote_spanned! {span=>
+        logid::log_id::LogId::new(
+            module_path!(),
+            #identifier,
+            #log_token,
+        )#with_code
+    }
+}
+
+/// Returns the registration of the given [`LogId`](logid_core::log_id::LogId) in the registry of feature `registry`.
+///
+/// Explanation and help URL are taken from the given `log_id` attributes,
+/// and doc comment and error message from the given attributes of the variant or struct.
+/// The span is used for the source location of the registered [`LogId`](logid_core::log_id::LogId).
+fn register_tokens(
+    log_id: &proc_macro2::TokenStream,
+    log_id_attrs: &LogIdAttrs,
+    attrs: &[syn::Attribute],
+    span: proc_macro2::Span,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let doc = doc_comment(attrs);
+    let error_msg = match error_msg(attrs) {
+        Some(msg) => quote_spanned! {span=> Some(#msg) },
+        None => quote_spanned! {span=> None },
+    };
+    let explanation = match (&log_id_attrs.explain, &log_id_attrs.explain_file) {
+        (Some(_), Some(file)) => {
+            return Err(syn::Error::new(
+                file.span(),
+                "only one of `explain` and `explain_file` may be set",
+            ))
+        }
+        (Some(explain), None) => quote_spanned! {span=> Some(#explain) },
+        (None, Some(file)) => quote_spanned! {span=>
+            Some(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #file)))
+        },
+        (None, None) => quote_spanned! {span=> None },
+    };
+    let help_url = match &log_id_attrs.help_url {
+        Some(url) => quote_spanned! {span=> Some(#url) },
+        None => quote_spanned! {span=> None },
+    };
+
+    Ok(quote_spanned! {span=>
+        logid::__register_log_id!(#log_id, #doc, #error_msg, #explanation, #help_url);
+    })
+}
+
 fn from_struct_or_union(
-    ident_name: proc_macro2::Ident,
-    log_token: proc_macro2::TokenStream,
+    ident_name: &proc_macro2::Ident,
+    generics: &syn::Generics,
+    attrs: LogIdAttrs,
+    type_attrs: &[syn::Attribute],
+    log_level: LogLevel,
     span: proc_macro2::Span,
-) -> TokenStream {
-    let ident_name_str = syn::LitStr::new(&ident_name.to_string(), span);
-
-    let from = quote_spanned! {span=>
-        impl From<#ident_name> for logid::log_id::LogId {
-            fn from(value: #ident_name) -> Self {
-                logid::log_id::LogId::new(
-                    module_path!(),
-                    #ident_name_str,
-                    #log_token,
-                )
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ident_name_str = attrs
+        .name
+        .clone()
+        .unwrap_or_else(|| syn::LitStr::new(&ident_name.to_string(), span));
+
+    let codes: Vec<_> = attrs
+        .code
+        .iter()
+        .map(|code| (code.clone(), ident_name.to_string()))
+        .collect();
+    check_unique_codes(&codes)?;
+
+    let log_id = log_id_tokens(&ident_name_str, log_level, &attrs, span);
+    let registration = register_tokens(&log_id, &attrs, type_attrs, ident_name.span())?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote_spanned! {span=>
+        impl #impl_generics From<&#ident_name #ty_generics> for logid::log_id::LogId #where_clause {
+            fn from(_value: &#ident_name #ty_generics) -> Self {
+                #log_id
             }
         }
-    };
 
-    TokenStream::from(from)
+        impl #impl_generics From<#ident_name #ty_generics> for logid::log_id::LogId #where_clause {
+            fn from(value: #ident_name #ty_generics) -> Self {
+                (&value).into()
+            }
+        }
+
+        #registration
+    })
 }
 
-#[proc_macro_derive(FromLogId)]
+/// Derives conversion from [`LogId`](logid_core::log_id::LogId) into an enum via `TryFrom`.
+///
+/// Fields of variants are set to their default value, or to the expression set via `#[log_id(default = "<expr>")]`.
+/// For generic enums, `Default` bounds are added for all field types without a set expression.
+#[proc_macro_derive(FromLogId, attributes(log_id))]
 pub fn derive_from_log_id(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let enum_name = input.ident;
 
-    match input.data {
-        syn::Data::Enum(enum_data) => {
-            let span = enum_name.span();
+    match try_derive_from_log_id(input) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
 
-            let mut from_fields = Vec::new();
+fn try_derive_from_log_id(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let enum_name = input.ident;
+    let mut generics = input.generics;
 
-            for variant in enum_data.variants {
-                let field_name = variant.ident;
-                let full_field_name = quote_spanned! {span=>
-                    #enum_name::#field_name
-                };
-                let full_field_name_str =
-                    syn::LitStr::new(&full_field_name.to_string().replace(' ', ""), span);
-                from_fields.push(quote_spanned! {span=>
-                    v if v == #full_field_name_str => #full_field_name,
-                });
-            }
+    let syn::Data::Enum(enum_data) = input.data else {
+        return Err(syn::Error::new(
+            enum_name.span(),
+            "derive `FromLogId` is only implemented for enumerations",
+        ));
+    };
+    let span = enum_name.span();
 
-            let from_log_id = quote_spanned! {span=>
-                impl From<logid::log_id::LogId> for #enum_name {
-                    fn from(value: logid::log_id::LogId) -> Self {
-                        if value.get_module_path() != module_path!() {
+    let mut from_fields = Vec::new();
 
-                            return Self::default();
-                        }
+    for variant in enum_data.variants {
+        let variant_attrs = LogIdAttrs::parse(&variant.attrs)?;
+        variant_attrs.deny_field_only()?;
 
-                        match value.get_identifier() {
-                            #(#from_fields)*
-                            _ => Self::default(),
-                        }
-                    }
-                }
+        let field_name = variant.ident;
+        let full_field_name_str = variant_attrs.name.unwrap_or_else(|| {
+            syn::LitStr::new(
+                &variant_identifier(&enum_name, &field_name, &variant.fields),
+                span,
+            )
+        });
 
-                impl From<logid::logging::intermediary_event::IntermediaryLogEvent> for #enum_name {
-                    fn from(value: logid::logging::intermediary_event::IntermediaryLogEvent) -> Self {
-                        value.finalize().into_event_id().into()
+        let mut field_values = Vec::new();
+        for field in variant.fields.iter() {
+            let value = match LogIdAttrs::parse_field(&field.attrs)?.default {
+                Some(default) => quote_spanned! {span=> #default },
+                None => {
+                    // Note: Bound is only needed if the field type may depend on generic parameters
+                    if generics.type_params().next().is_some() {
+                        let ty = &field.ty;
+                        generics
+                            .make_where_clause()
+                            .predicates
+                            .push(syn::parse_quote!(#ty: ::core::default::Default));
                     }
+                    quote_spanned! {span=> ::core::default::Default::default() }
                 }
             };
-
-            TokenStream::from(from_log_id)
+            field_values.push(match &field.ident {
+                Some(ident) => quote_spanned! {span=> #ident: #value },
+                None => value,
+            });
         }
-        _ => panic!("Derive `FromLogId` is only implemented for enumerations where no item has fields (e.g. SomeEnum::Item(Field) is **not** allowed)."),
+
+        let variant_value = match variant.fields {
+            syn::Fields::Named(_) => quote_spanned! {span=>
+                #enum_name::#field_name { #(#field_values),* }
+            },
+            syn::Fields::Unnamed(_) => quote_spanned! {span=>
+                #enum_name::#field_name ( #(#field_values),* )
+            },
+            syn::Fields::Unit => quote_spanned! {span=>
+                #enum_name::#field_name
+            },
+        };
+        from_fields.push(quote_spanned! {span=>
+            v if v == #full_field_name_str => Ok(#variant_value),
+        });
     }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote_spanned! {span=>
+        impl #impl_generics TryFrom<logid::log_id::LogId> for #enum_name #ty_generics #where_clause {
+            type Error = logid::log_id::FromLogIdError;
+
+            fn try_from(value: logid::log_id::LogId) -> Result<Self, Self::Error> {
+                if value.get_module_path() != module_path!() {
+                    return Err(logid::log_id::FromLogIdError::ForeignModule(value));
+                }
+
+                match value.get_identifier() {
+                    #(#from_fields)*
+                    _ => Err(logid::log_id::FromLogIdError::UnknownIdentifier(value)),
+                }
+            }
+        }
+
+        impl #impl_generics TryFrom<logid::logging::intermediary_event::IntermediaryLogEvent> for #enum_name #ty_generics #where_clause {
+            type Error = logid::log_id::FromLogIdError;
+
+            fn try_from(value: logid::logging::intermediary_event::IntermediaryLogEvent) -> Result<Self, Self::Error> {
+                value.finalize().into_event_id().try_into()
+            }
+        }
+    })
 }
 
 fn log_level_as_tokenstream(level: LogLevel) -> proc_macro2::TokenStream {
@@ -0,0 +1,1084 @@
+//! Contains a compact binary log format, the [`BinaryWriter`] to write captured events in this format,
+//! and the [`BinaryReader`] to read written events and convert them to JSON or terminal output.
+//!
+//! Writing binary logs avoids formatting events on the handler thread, and results in smaller logs than JSON or text output.
+//!
+//! **Format:**
+//!
+//! A log starts with the header `LOGIDBIN` followed by one byte for the format version.
+//! The header is followed by records, each consisting of one tag byte, the varint length of the record content, and the content.
+//!
+//! - Strings like module paths, identifiers, filenames, and thread names are written once in a string record,
+//!   and referenced by their index afterwards.
+//! - Log-ids are written once in an id record, and referenced by their index afterwards.
+//!   Explanations and help URLs are not written, because they are looked up in the registry by the log-id.
+//! - Event records contain the referenced log-id, the entry ID, the timestamp as varint nanoseconds relative to the previous event,
+//!   the message, the origin, and the addons of the event. Each addon is prefixed with its kind and length.
+//!
+//! Records and addons of unknown kinds are skipped by the reader, and addons that are not supported with the enabled features are dropped.
+//! Formatted addons are written like their non-formatted counterpart, because only their rendered form is available.
+//!
+//! A record that is cut off, e.g. because the process crashed while the record was written, ends the log.
+//! All complete records before it are read, and [`BinaryReader::is_truncated()`] is set.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid::event_handler::{binary::BinaryWriter, builder::LogEventHandlerBuilder};
+//!
+//! let writer = BinaryWriter::new(Vec::new()).unwrap();
+//!
+//! let _handler = LogEventHandlerBuilder::new()
+//!     .add_handler(writer.handler())
+//!     .all_log_events()
+//!     .build();
+//! ~~~
+
+use std::{
+    collections::HashMap,
+    io::{BufWriter, Read, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use logid_core::{
+    evident::{
+        event::{finalized::FinalizedEvent, origin::Origin, Event},
+        uuid::Uuid,
+    },
+    log_id::{LogId, LogLevel},
+    logging::{
+        event_entry::{AddonKind, LogEventEntry},
+        field::{FieldValue, LogField},
+        intern::{intern, MAX_INTERNED_BYTES},
+        msg::LogMsg,
+    },
+    serde_json::Value,
+};
+
+use super::{json::event_to_json_with, render::RenderOptions, terminal::render_terminal};
+
+/// Header every binary log starts with, followed by the format version.
+pub const MAGIC: &[u8; 8] = b"LOGIDBIN";
+
+/// Version of the binary format that is written.
+///
+/// Logs with a higher version are rejected by the reader.
+pub const FORMAT_VERSION: u8 = 1;
+
+const STRING_RECORD: u8 = 1;
+const ID_RECORD: u8 = 2;
+const EVENT_RECORD: u8 = 3;
+
+const INFO_ADDON: u8 = 1;
+const DEBUG_ADDON: u8 = 2;
+const TRACE_ADDON: u8 = 3;
+const FIELD_ADDON: u8 = 4;
+const RELATED_ADDON: u8 = 5;
+const PARENT_ADDON: u8 = 6;
+#[cfg(feature = "hint_note")]
+const HINT_ADDON: u8 = 7;
+#[cfg(feature = "hint_note")]
+const NOTE_ADDON: u8 = 8;
+#[cfg(feature = "diagnostics")]
+const DIAGNOSTIC_ADDON: u8 = 9;
+#[cfg(feature = "payloads")]
+const PAYLOAD_ADDON: u8 = 10;
+
+const HAS_TIMESTAMP: u8 = 1;
+const HAS_MSG: u8 = 1 << 1;
+const HAS_THREAD_NAME: u8 = 1 << 2;
+
+const INT_VALUE: u8 = 0;
+const UINT_VALUE: u8 = 1;
+const FLOAT_VALUE: u8 = 2;
+const BOOL_VALUE: u8 = 3;
+const STR_VALUE: u8 = 4;
+const DURATION_VALUE: u8 = 5;
+const TIMESTAMP_VALUE: u8 = 6;
+const BYTES_VALUE: u8 = 7;
+
+/// Writer encoding events in the binary log format.
+///
+/// Clones share the same underlying writer.
+/// Events are written in the order they are received, and the underlying writer is flushed when the last clone is dropped.
+#[derive(Clone)]
+pub struct BinaryWriter {
+    state: Arc<Mutex<WriterState>>,
+}
+
+struct WriterState {
+    writer: Box<dyn Write + std::marker::Send + 'static>,
+    strings: HashMap<String, u64>,
+    ids: HashMap<LogId, u64>,
+    /// Timestamp of the previous event in nanoseconds since the Unix epoch.
+    last_timestamp: i64,
+    /// Buffer for the content of the current record.
+    record: Vec<u8>,
+}
+
+impl BinaryWriter {
+    /// Creates a binary writer, and writes the header to the given writer.
+    ///
+    /// **Note:** Every event is written separately, so a buffered writer should be used to write to files or sockets.
+    pub fn new(writer: impl Write + std::marker::Send + 'static) -> Result<Self, std::io::Error> {
+        let mut writer: Box<dyn Write + std::marker::Send + 'static> = Box::new(writer);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+
+        Ok(BinaryWriter {
+            state: Arc::new(Mutex::new(WriterState {
+                writer,
+                strings: HashMap::new(),
+                ids: HashMap::new(),
+                last_timestamp: 0,
+                record: Vec::new(),
+            })),
+        })
+    }
+
+    /// Creates the file at the given path, and returns a buffered binary writer for it.
+    ///
+    /// An existing file is truncated.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        BinaryWriter::new(BufWriter::new(std::fs::File::create(path)?))
+    }
+
+    /// Returns a handler function for [`LogEventHandlerBuilder::add_handler`](super::builder::LogEventHandlerBuilder::add_handler)
+    /// that writes all received events.
+    ///
+    /// Write errors are ignored like for the terminal handlers.
+    pub fn handler(
+        &self,
+    ) -> impl FnMut(Arc<Event<LogId, LogMsg, LogEventEntry>>) + std::marker::Send + 'static {
+        let writer = self.clone();
+        move |event| {
+            let _ = writer.write_event(&event);
+        }
+    }
+
+    /// Writes the given event, and the strings and log-ids it references that were not written before.
+    pub fn write_event(
+        &self,
+        event: &Event<LogId, LogMsg, LogEventEntry>,
+    ) -> Result<(), std::io::Error> {
+        match self.state.lock() {
+            Ok(mut state) => state.write_event(event),
+            Err(_) => Err(std::io::Error::other("Binary writer is poisoned.")),
+        }
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&self) -> Result<(), std::io::Error> {
+        match self.state.lock() {
+            Ok(mut state) => state.writer.flush(),
+            Err(_) => Err(std::io::Error::other("Binary writer is poisoned.")),
+        }
+    }
+}
+
+impl std::fmt::Debug for BinaryWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinaryWriter").finish_non_exhaustive()
+    }
+}
+
+impl Drop for WriterState {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+impl WriterState {
+    fn write_event(
+        &mut self,
+        event: &Event<LogId, LogMsg, LogEventEntry>,
+    ) -> Result<(), std::io::Error> {
+        let entry = event.get_entry();
+        let origin = event.get_origin();
+
+        // Note: Referenced strings and ids must be written before the event record that references them
+        let id_index = self.id_index(*event.get_event_id())?;
+        let module_index = self.string_index(origin.module_path)?;
+        let file_index = self.string_index(origin.filename)?;
+        let thread_index = match event.get_thread_name() {
+            Some(thread_name) => Some(self.string_index(thread_name)?),
+            None => None,
+        };
+        let linked = entry
+            .get_parent()
+            .into_iter()
+            .chain(entry.get_related())
+            .map(|finalized| *finalized.get_event_id());
+        let mut linked_indices = HashMap::new();
+        for linked_id in linked {
+            let index = self.id_index(linked_id)?;
+            linked_indices.insert(linked_id, index);
+        }
+
+        let timestamp = event.get_timestamp().as_ref().and_then(unix_nanos);
+        let msg = event.get_msg().map(|msg| msg.to_string());
+
+        let mut flags = 0;
+        if timestamp.is_some() {
+            flags |= HAS_TIMESTAMP;
+        }
+        if msg.is_some() {
+            flags |= HAS_MSG;
+        }
+        if thread_index.is_some() {
+            flags |= HAS_THREAD_NAME;
+        }
+
+        let mut record = std::mem::take(&mut self.record);
+        record.clear();
+        record.push(flags);
+        write_varint(&mut record, id_index);
+        record.extend_from_slice(event.get_entry_id().as_bytes());
+        if let Some(timestamp) = timestamp {
+            write_varint(
+                &mut record,
+                zigzag(timestamp.wrapping_sub(self.last_timestamp)),
+            );
+            self.last_timestamp = timestamp;
+        }
+        if let Some(msg) = msg {
+            write_bytes(&mut record, msg.as_bytes());
+        }
+        write_varint(&mut record, module_index);
+        write_varint(&mut record, file_index);
+        write_varint(&mut record, u64::from(origin.line_nr));
+        if let Some(thread_index) = thread_index {
+            write_varint(&mut record, thread_index);
+        }
+
+        encode_addons(&mut record, entry, &linked_indices);
+
+        let res = self.write_record(EVENT_RECORD, &record);
+        self.record = record;
+        res
+    }
+
+    fn string_index(&mut self, s: &str) -> Result<u64, std::io::Error> {
+        if let Some(index) = self.strings.get(s) {
+            return Ok(*index);
+        }
+
+        self.write_record(STRING_RECORD, s.as_bytes())?;
+        let index = self.strings.len() as u64;
+        self.strings.insert(s.to_string(), index);
+        Ok(index)
+    }
+
+    fn id_index(&mut self, id: LogId) -> Result<u64, std::io::Error> {
+        if let Some(index) = self.ids.get(&id) {
+            return Ok(*index);
+        }
+
+        let module_index = self.string_index(id.get_module_path())?;
+        let identifier_index = self.string_index(id.get_identifier())?;
+        let code_index = match id.get_code() {
+            Some(code) => self.string_index(code)? + 1,
+            None => 0,
+        };
+
+        let mut record = Vec::new();
+        write_varint(&mut record, module_index);
+        write_varint(&mut record, identifier_index);
+        record.push(id.get_log_level() as u8);
+        write_varint(&mut record, code_index);
+        self.write_record(ID_RECORD, &record)?;
+
+        let index = self.ids.len() as u64;
+        self.ids.insert(id, index);
+        Ok(index)
+    }
+
+    fn write_record(&mut self, tag: u8, content: &[u8]) -> Result<(), std::io::Error> {
+        let mut header = Vec::with_capacity(11);
+        header.push(tag);
+        write_varint(&mut header, content.len() as u64);
+        self.writer.write_all(&header)?;
+        self.writer.write_all(content)
+    }
+}
+
+/// Appends all addons of the given entry to the record.
+fn encode_addons(
+    record: &mut Vec<u8>,
+    entry: &LogEventEntry,
+    linked_indices: &HashMap<LogId, u64>,
+) {
+    let mut content = Vec::new();
+
+    for info in entry.get_infos() {
+        write_addon(record, INFO_ADDON, info.as_bytes());
+    }
+    #[cfg(feature = "fmt")]
+    for info in entry.get_fmt_infos() {
+        write_addon(record, INFO_ADDON, info.to_string().as_bytes());
+    }
+    for debug in entry.get_debugs() {
+        write_addon(record, DEBUG_ADDON, debug.as_bytes());
+    }
+    #[cfg(feature = "fmt")]
+    for debug in entry.get_fmt_debugs() {
+        write_addon(record, DEBUG_ADDON, debug.to_string().as_bytes());
+    }
+    for trace in entry.get_traces() {
+        write_addon(record, TRACE_ADDON, trace.as_bytes());
+    }
+    #[cfg(feature = "fmt")]
+    for trace in entry.get_fmt_traces() {
+        write_addon(record, TRACE_ADDON, trace.to_string().as_bytes());
+    }
+
+    for field in entry.get_fields() {
+        content.clear();
+        encode_field(&mut content, field);
+        write_addon(record, FIELD_ADDON, &content);
+    }
+
+    if let Some(parent) = entry.get_parent() {
+        content.clear();
+        encode_finalized(&mut content, parent, linked_indices);
+        write_addon(record, PARENT_ADDON, &content);
+    }
+    for related in entry.get_related() {
+        content.clear();
+        encode_finalized(&mut content, related, linked_indices);
+        write_addon(record, RELATED_ADDON, &content);
+    }
+
+    #[cfg(feature = "hint_note")]
+    {
+        for hint in entry.get_hints() {
+            write_addon(record, HINT_ADDON, hint.as_bytes());
+        }
+        #[cfg(feature = "fmt")]
+        for hint in entry.get_fmt_hints() {
+            write_addon(record, HINT_ADDON, hint.to_string().as_bytes());
+        }
+        for note in entry.get_notes() {
+            write_addon(record, NOTE_ADDON, note.as_bytes());
+        }
+        #[cfg(feature = "fmt")]
+        for note in entry.get_fmt_notes() {
+            write_addon(record, NOTE_ADDON, note.to_string().as_bytes());
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    {
+        #[allow(unused_mut)]
+        let mut diagnostics: Vec<&logid_core::lsp_types::Diagnostic> =
+            entry.get_diagnostics().iter().collect();
+        #[cfg(feature = "fmt")]
+        diagnostics.extend(
+            entry
+                .get_fmt_diagnostics()
+                .iter()
+                .map(|diag| diag.get_data()),
+        );
+        for diagnostic in diagnostics {
+            if let Ok(json) = logid_core::serde_json::to_string(diagnostic) {
+                write_addon(record, DIAGNOSTIC_ADDON, json.as_bytes());
+            }
+        }
+    }
+
+    #[cfg(feature = "payloads")]
+    {
+        for payload in entry.get_payloads() {
+            write_addon(record, PAYLOAD_ADDON, payload.to_string().as_bytes());
+        }
+        #[cfg(feature = "fmt")]
+        for payload in entry.get_fmt_payloads() {
+            let payload = Value::String(payload.to_string());
+            write_addon(record, PAYLOAD_ADDON, payload.to_string().as_bytes());
+        }
+    }
+}
+
+fn write_addon(record: &mut Vec<u8>, kind: u8, content: &[u8]) {
+    record.push(kind);
+    write_bytes(record, content);
+}
+
+fn encode_field(buf: &mut Vec<u8>, field: &LogField) {
+    write_bytes(buf, field.get_key().as_bytes());
+    match field.get_value() {
+        FieldValue::Int(v) => {
+            buf.push(INT_VALUE);
+            write_varint(buf, zigzag(*v));
+        }
+        FieldValue::UInt(v) => {
+            buf.push(UINT_VALUE);
+            write_varint(buf, *v);
+        }
+        FieldValue::Float(v) => {
+            buf.push(FLOAT_VALUE);
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        FieldValue::Bool(v) => {
+            buf.push(BOOL_VALUE);
+            buf.push(u8::from(*v));
+        }
+        FieldValue::Str(v) => {
+            buf.push(STR_VALUE);
+            buf.extend_from_slice(v.as_bytes());
+        }
+        FieldValue::Duration(v) => {
+            buf.push(DURATION_VALUE);
+            write_varint(buf, v.as_secs());
+            write_varint(buf, u64::from(v.subsec_nanos()));
+        }
+        FieldValue::Timestamp(v) => {
+            buf.push(TIMESTAMP_VALUE);
+            let (secs, nanos) = match v.duration_since(UNIX_EPOCH) {
+                Ok(since_epoch) => (since_epoch.as_secs() as i64, since_epoch.subsec_nanos()),
+                Err(err) => {
+                    // Note: Timestamps before the epoch are stored as negative seconds plus positive nanoseconds
+                    let before_epoch = err.duration();
+                    let mut secs = -(before_epoch.as_secs() as i64);
+                    let mut nanos = before_epoch.subsec_nanos();
+                    if nanos > 0 {
+                        secs -= 1;
+                        nanos = 1_000_000_000 - nanos;
+                    }
+                    (secs, nanos)
+                }
+            };
+            write_varint(buf, zigzag(secs));
+            write_varint(buf, u64::from(nanos));
+        }
+        FieldValue::Bytes(v) => {
+            buf.push(BYTES_VALUE);
+            buf.extend_from_slice(v);
+        }
+    }
+}
+
+fn encode_finalized(
+    buf: &mut Vec<u8>,
+    finalized: &FinalizedEvent<LogId>,
+    linked_indices: &HashMap<LogId, u64>,
+) {
+    // Note: All linked ids are written before encoding addons, so the index always exists
+    let index = linked_indices
+        .get(finalized.get_event_id())
+        .copied()
+        .unwrap_or_default();
+    write_varint(buf, index);
+    buf.extend_from_slice(finalized.get_entry_id().as_bytes());
+}
+
+/// Event read from a binary log.
+#[derive(Debug)]
+pub struct BinaryEvent {
+    pub event: Event<LogId, LogMsg, LogEventEntry>,
+    /// Time the event was captured at, if the log contains it.
+    pub timestamp: Option<SystemTime>,
+    /// Name of the thread the event was set in, if the thread had a name.
+    pub thread_name: Option<String>,
+}
+
+impl BinaryEvent {
+    /// Converts the event into a JSON object like [`event_to_json()`](super::json::event_to_json),
+    /// with the timestamp and thread name of the log.
+    pub fn to_json(&self) -> Value {
+        self.to_json_with(&RenderOptions::default())
+    }
+
+    /// Converts the event into a JSON object like [`event_to_json_with()`], with the timestamp and thread name of the log.
+    pub fn to_json_with(&self, options: &RenderOptions) -> Value {
+        let mut value = event_to_json_with(&self.event, options);
+
+        if let Value::Object(obj) = &mut value {
+            // Note: The event was created by the reader, so its thread name is the one of the reading thread
+            obj.remove("thread");
+            if let Some(thread_name) = &self.thread_name {
+                obj.insert("thread".to_string(), Value::String(thread_name.clone()));
+            }
+
+            if let Some(timestamp) = self.timestamp {
+                let timestamp = if options.placeholder_timestamps {
+                    Value::String(super::render::TIMESTAMP_PLACEHOLDER.to_string())
+                } else {
+                    timestamp
+                        .duration_since(UNIX_EPOCH)
+                        .ok()
+                        .and_then(|since_epoch| {
+                            logid_core::serde_json::Number::from_f64(since_epoch.as_secs_f64())
+                        })
+                        .map(Value::Number)
+                        .unwrap_or(Value::Null)
+                };
+                obj.insert("timestamp".to_string(), timestamp);
+            }
+        }
+        value
+    }
+
+    /// Renders the event like it is printed by the terminal handlers.
+    ///
+    /// See [`render_terminal()`] for more information.
+    pub fn render_terminal(&self, options: &RenderOptions) -> String {
+        render_terminal(&self.event, options)
+    }
+}
+
+/// Reader decoding events of a binary log written by a [`BinaryWriter`].
+///
+/// The reader may also be used as iterator over the read events.
+///
+/// Log-ids and origins require static strings, so read strings are [interned](logid_core::logging::intern) once per process.
+pub struct BinaryReader<R: Read> {
+    reader: R,
+    version: u8,
+    strings: Vec<&'static str>,
+    ids: Vec<LogId>,
+    /// Timestamp of the previous event in nanoseconds since the Unix epoch.
+    last_timestamp: i64,
+    truncated: bool,
+    /// Buffer for the content of the current record.
+    record: Vec<u8>,
+}
+
+impl<R: Read> BinaryReader<R> {
+    /// Creates a reader, and reads the header from the given reader.
+    pub fn new(mut reader: R) -> Result<Self, BinaryError> {
+        let mut header = [0; 9];
+        reader.read_exact(&mut header).map_err(|err| {
+            if err.kind() == std::io::ErrorKind::UnexpectedEof {
+                BinaryError::InvalidHeader
+            } else {
+                BinaryError::Io(err)
+            }
+        })?;
+
+        if &header[..8] != MAGIC {
+            return Err(BinaryError::InvalidHeader);
+        }
+        let version = header[8];
+        if version == 0 || version > FORMAT_VERSION {
+            return Err(BinaryError::UnsupportedVersion(version));
+        }
+
+        Ok(BinaryReader {
+            reader,
+            version,
+            strings: Vec::new(),
+            ids: Vec::new(),
+            last_timestamp: 0,
+            truncated: false,
+            record: Vec::new(),
+        })
+    }
+
+    /// Returns the format version of the log.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns `true` if the log ended with an incomplete record.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Reads the next event, or returns `None` at the end of the log.
+    pub fn read_event(&mut self) -> Result<Option<BinaryEvent>, BinaryError> {
+        while let Some(tag) = self.read_record()? {
+            let mut record = std::mem::take(&mut self.record);
+            let res = self.decode_record(tag, &mut Decoder::new(&record));
+            record.clear();
+            self.record = record;
+
+            if let Some(event) = res? {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Writes all remaining events as JSON lines to the given writer, and returns the number of written events.
+    pub fn write_json_lines<W: Write>(&mut self, mut writer: W) -> Result<usize, BinaryError> {
+        let mut written = 0;
+        while let Some(event) = self.read_event()? {
+            writeln!(writer, "{}", event.to_json()).map_err(BinaryError::Io)?;
+            written += 1;
+        }
+        writer.flush().map_err(BinaryError::Io)?;
+        Ok(written)
+    }
+
+    /// Writes all remaining events like the terminal handlers to the given writer, and returns the number of written events.
+    pub fn write_terminal<W: Write>(
+        &mut self,
+        mut writer: W,
+        options: &RenderOptions,
+    ) -> Result<usize, BinaryError> {
+        let mut written = 0;
+        while let Some(event) = self.read_event()? {
+            writer
+                .write_all(event.render_terminal(options).as_bytes())
+                .map_err(BinaryError::Io)?;
+            written += 1;
+        }
+        writer.flush().map_err(BinaryError::Io)?;
+        Ok(written)
+    }
+
+    /// Reads the next record into the record buffer, and returns its tag.
+    ///
+    /// Returns `None` at the end of the log, or if the record is incomplete.
+    fn read_record(&mut self) -> Result<Option<u8>, BinaryError> {
+        if self.truncated {
+            return Ok(None);
+        }
+
+        let mut tag = [0];
+        loop {
+            match self.reader.read(&mut tag) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(BinaryError::Io(err)),
+            }
+        }
+
+        let mut len = 0;
+        let mut shift = 0;
+        loop {
+            let mut byte = [0];
+            match self.reader.read_exact(&mut byte) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    self.truncated = true;
+                    return Ok(None);
+                }
+                Err(err) => return Err(BinaryError::Io(err)),
+            }
+            if shift >= 64 {
+                return Err(BinaryError::InvalidRecord(
+                    "record length is too large".to_string(),
+                ));
+            }
+            len |= u64::from(byte[0] & 0x7f) << shift;
+            if byte[0] & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+
+        // Note: Reading via `take()` only allocates as much as is available, even if the length is corrupted
+        self.record.clear();
+        let read = (&mut self.reader)
+            .take(len)
+            .read_to_end(&mut self.record)
+            .map_err(BinaryError::Io)?;
+        if (read as u64) < len {
+            self.truncated = true;
+            return Ok(None);
+        }
+
+        Ok(Some(tag[0]))
+    }
+
+    fn decode_record(
+        &mut self,
+        tag: u8,
+        decoder: &mut Decoder,
+    ) -> Result<Option<BinaryEvent>, BinaryError> {
+        match tag {
+            STRING_RECORD => {
+                let s = decoder.str(decoder.remaining())?;
+                let interned = intern(s).ok_or_else(|| {
+                    BinaryError::InvalidRecord(format!(
+                        "strings exceed the limit of {MAX_INTERNED_BYTES} bytes"
+                    ))
+                })?;
+                self.strings.push(interned);
+                Ok(None)
+            }
+            ID_RECORD => {
+                let module_path = self.string(decoder.varint()?)?;
+                let identifier = self.string(decoder.varint()?)?;
+                let level = decode_level(decoder.byte()?)?;
+                let mut id = LogId::new(module_path, identifier, level);
+                let code_index = decoder.varint()?;
+                if code_index > 0 {
+                    id = id.with_code(self.string(code_index - 1)?);
+                }
+                self.ids.push(id);
+                Ok(None)
+            }
+            EVENT_RECORD => self.decode_event(decoder).map(Some),
+            // Note: Records added in later versions are skipped to keep older readers working
+            _ => Ok(None),
+        }
+    }
+
+    fn decode_event(&mut self, decoder: &mut Decoder) -> Result<BinaryEvent, BinaryError> {
+        let flags = decoder.byte()?;
+        let id = self.id(decoder.varint()?)?;
+        let entry_id = decoder.uuid()?;
+
+        let timestamp = if flags & HAS_TIMESTAMP != 0 {
+            let nanos = self
+                .last_timestamp
+                .wrapping_add(unzigzag(decoder.varint()?));
+            self.last_timestamp = nanos;
+            from_unix_nanos(nanos)
+        } else {
+            None
+        };
+        let msg = if flags & HAS_MSG != 0 {
+            let len = decoder.len()?;
+            Some(LogMsg::from(decoder.str(len)?))
+        } else {
+            None
+        };
+
+        let module_path = self.string(decoder.varint()?)?;
+        let filename = self.string(decoder.varint()?)?;
+        let line_nr = u32::try_from(decoder.varint()?)
+            .map_err(|_| BinaryError::InvalidRecord("line number is too large".to_string()))?;
+        let origin = Origin::new(module_path, filename, line_nr);
+
+        let thread_name = if flags & HAS_THREAD_NAME != 0 {
+            Some(self.string(decoder.varint()?)?.to_string())
+        } else {
+            None
+        };
+
+        // Note: Addons fill the rest of the record
+        let mut addons = Vec::new();
+        while decoder.remaining() > 0 {
+            let kind = decoder.byte()?;
+            let len = decoder.len()?;
+            let mut addon_decoder = Decoder::new(decoder.bytes(len)?);
+            if let Some(addon) = self.decode_addon(kind, &mut addon_decoder)? {
+                addons.push(addon);
+            }
+        }
+        let entry = LogEventEntry::restore(id, entry_id, msg, origin, addons);
+
+        Ok(BinaryEvent {
+            event: Event::new(entry),
+            timestamp,
+            thread_name,
+        })
+    }
+
+    /// Decodes an addon, or returns `None` if the addon kind is unknown or not supported with the enabled features.
+    fn decode_addon(
+        &self,
+        kind: u8,
+        decoder: &mut Decoder,
+    ) -> Result<Option<AddonKind>, BinaryError> {
+        let addon = match kind {
+            INFO_ADDON => AddonKind::Info(decoder.rest_str()?),
+            DEBUG_ADDON => AddonKind::Debug(decoder.rest_str()?),
+            TRACE_ADDON => AddonKind::Trace(decoder.rest_str()?),
+            FIELD_ADDON => AddonKind::Field(decode_field(decoder)?),
+            PARENT_ADDON => AddonKind::Parent(self.decode_finalized(decoder)?),
+            RELATED_ADDON => AddonKind::Related(self.decode_finalized(decoder)?),
+
+            #[cfg(feature = "hint_note")]
+            HINT_ADDON => AddonKind::Hint(decoder.rest_str()?),
+            #[cfg(feature = "hint_note")]
+            NOTE_ADDON => AddonKind::Note(decoder.rest_str()?),
+
+            #[cfg(feature = "diagnostics")]
+            DIAGNOSTIC_ADDON => AddonKind::Diagnostic(
+                logid_core::serde_json::from_str(&decoder.rest_str()?)
+                    .map_err(|err| BinaryError::InvalidRecord(err.to_string()))?,
+            ),
+
+            #[cfg(feature = "payloads")]
+            PAYLOAD_ADDON => AddonKind::Payload(
+                logid_core::serde_json::from_str(&decoder.rest_str()?)
+                    .map_err(|err| BinaryError::InvalidRecord(err.to_string()))?,
+            ),
+
+            _ => return Ok(None),
+        };
+        Ok(Some(addon))
+    }
+
+    fn decode_finalized(
+        &self,
+        decoder: &mut Decoder,
+    ) -> Result<FinalizedEvent<LogId>, BinaryError> {
+        let id = self.id(decoder.varint()?)?;
+        Ok(FinalizedEvent::new(id, decoder.uuid()?))
+    }
+
+    fn string(&self, index: u64) -> Result<&'static str, BinaryError> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.strings.get(index))
+            .copied()
+            .ok_or_else(|| BinaryError::InvalidRecord(format!("unknown string index {index}")))
+    }
+
+    fn id(&self, index: u64) -> Result<LogId, BinaryError> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.ids.get(index))
+            .copied()
+            .ok_or_else(|| BinaryError::InvalidRecord(format!("unknown log-id index {index}")))
+    }
+}
+
+impl BinaryReader<std::io::BufReader<std::fs::File>> {
+    /// Opens the binary log at the given path, and reads its header.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BinaryError> {
+        let file = std::fs::File::open(path).map_err(BinaryError::Io)?;
+        BinaryReader::new(std::io::BufReader::new(file))
+    }
+}
+
+impl<R: Read> Iterator for BinaryReader<R> {
+    type Item = Result<BinaryEvent, BinaryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_event().transpose()
+    }
+}
+
+impl<R: Read> std::fmt::Debug for BinaryReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinaryReader")
+            .field("version", &self.version)
+            .field("truncated", &self.truncated)
+            .finish_non_exhaustive()
+    }
+}
+
+fn decode_field(decoder: &mut Decoder) -> Result<LogField, BinaryError> {
+    let key_len = decoder.len()?;
+    let key = decoder.str(key_len)?.to_string();
+
+    let value = match decoder.byte()? {
+        INT_VALUE => FieldValue::Int(unzigzag(decoder.varint()?)),
+        UINT_VALUE => FieldValue::UInt(decoder.varint()?),
+        FLOAT_VALUE => {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(decoder.bytes(8)?);
+            FieldValue::Float(f64::from_le_bytes(bytes))
+        }
+        BOOL_VALUE => FieldValue::Bool(decoder.byte()? != 0),
+        STR_VALUE => FieldValue::Str(decoder.rest_str()?),
+        DURATION_VALUE => {
+            let secs = decoder.varint()?;
+            let nanos = decoder.nanos()?;
+            FieldValue::Duration(Duration::new(secs, nanos))
+        }
+        TIMESTAMP_VALUE => {
+            let secs = unzigzag(decoder.varint()?);
+            let nanos = decoder.nanos()?;
+            let timestamp = if secs >= 0 {
+                UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+            } else {
+                UNIX_EPOCH
+                    .checked_sub(Duration::from_secs(secs.unsigned_abs()))
+                    .and_then(|time| time.checked_add(Duration::from_nanos(u64::from(nanos))))
+            };
+            FieldValue::Timestamp(timestamp.ok_or_else(|| {
+                BinaryError::InvalidRecord("field timestamp is out of range".to_string())
+            })?)
+        }
+        BYTES_VALUE => FieldValue::Bytes(decoder.bytes(decoder.remaining())?.to_vec()),
+        value_kind => {
+            return Err(BinaryError::InvalidRecord(format!(
+                "unknown field value kind {value_kind}"
+            )))
+        }
+    };
+    Ok(LogField::new(key, value))
+}
+
+fn decode_level(level: u8) -> Result<LogLevel, BinaryError> {
+    match level {
+        0 => Ok(LogLevel::Trace),
+        1 => Ok(LogLevel::Debug),
+        2 => Ok(LogLevel::Info),
+        3 => Ok(LogLevel::Warn),
+        4 => Ok(LogLevel::Error),
+        level => Err(BinaryError::InvalidRecord(format!(
+            "unknown log level {level}"
+        ))),
+    }
+}
+
+/// Decoder over the content of one record.
+struct Decoder<'a> {
+    content: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(content: &'a [u8]) -> Self {
+        Decoder { content, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.content.len() - self.pos
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BinaryError> {
+        if len > self.remaining() {
+            return Err(BinaryError::InvalidRecord(
+                "content is shorter than its length".to_string(),
+            ));
+        }
+        let bytes = &self.content[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, BinaryError> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            if shift >= 64 {
+                return Err(BinaryError::InvalidRecord(
+                    "varint is too large".to_string(),
+                ));
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn len(&mut self) -> Result<usize, BinaryError> {
+        usize::try_from(self.varint()?)
+            .map_err(|_| BinaryError::InvalidRecord("length is too large".to_string()))
+    }
+
+    fn nanos(&mut self) -> Result<u32, BinaryError> {
+        u32::try_from(self.varint()?)
+            .ok()
+            .filter(|nanos| *nanos < 1_000_000_000)
+            .ok_or_else(|| BinaryError::InvalidRecord("invalid nanoseconds".to_string()))
+    }
+
+    fn str(&mut self, len: usize) -> Result<&'a str, BinaryError> {
+        std::str::from_utf8(self.bytes(len)?)
+            .map_err(|err| BinaryError::InvalidRecord(err.to_string()))
+    }
+
+    fn rest_str(&mut self) -> Result<String, BinaryError> {
+        self.str(self.remaining()).map(str::to_string)
+    }
+
+    fn uuid(&mut self) -> Result<Uuid, BinaryError> {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(self.bytes(16)?);
+        Ok(Uuid::from_bytes(bytes))
+    }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Returns nanoseconds since the Unix epoch, or `None` if the timestamp is outside the range of `i64` nanoseconds (years 1677 to 2262).
+fn unix_nanos(timestamp: &SystemTime) -> Option<i64> {
+    match timestamp.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => i64::try_from(since_epoch.as_nanos()).ok(),
+        Err(err) => i64::try_from(err.duration().as_nanos())
+            .ok()
+            .map(|nanos| -nanos),
+    }
+}
+
+fn from_unix_nanos(nanos: i64) -> Option<SystemTime> {
+    if nanos >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_nanos(nanos as u64))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_nanos(nanos.unsigned_abs()))
+    }
+}
+
+#[derive(Debug)]
+pub enum BinaryError {
+    Io(std::io::Error),
+    InvalidHeader,
+    UnsupportedVersion(u8),
+    InvalidRecord(String),
+}
+
+impl std::error::Error for BinaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinaryError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryError::Io(err) => write!(f, "Failed to read binary log: {}", err),
+            BinaryError::InvalidHeader => write!(f, "Not a binary log of logid."),
+            BinaryError::UnsupportedVersion(version) => write!(
+                f,
+                "Binary log has version {}, but only versions up to {} are supported.",
+                version, FORMAT_VERSION
+            ),
+            BinaryError::InvalidRecord(err) => write!(f, "Invalid record in binary log: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unzigzag, write_varint, zigzag, Decoder};
+
+    #[test]
+    fn varint_roundtrip() {
+        for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, value);
+            assert_eq!(Decoder::new(&buf).varint().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn zigzag_roundtrip() {
+        for value in [0, 1, -1, 63, -64, i64::MAX, i64::MIN] {
+            assert_eq!(unzigzag(zigzag(value)), value);
+        }
+        assert_eq!(zigzag(-1), 1, "Small negative values must stay small.");
+    }
+}
@@ -10,10 +10,11 @@ use std::{
 use logid_core::{
     evident::event::Event,
     log_id::{LogId, START_LOGGING, STOP_LOGGING},
-    logging::{event_entry::LogEventEntry, msg::LogMsg, LOGGER},
+    logging::{event_entry::LogEventEntry, logger::Logger, msg::LogMsg, LOGGER},
 };
 
 use super::{
+    render::RenderOptions,
     terminal::{stderr_writer, stdout_writer},
     LogEventHandler, HANDLER_START_LOGGING, HANDLER_STOP_LOGGING, SHUTDOWN_HANDLER,
 };
@@ -34,6 +35,8 @@ type Handler =
 pub struct LogEventHandlerBuilder<K> {
     log_ids: Vec<LogId>,
     handler: Vec<Handler>,
+    /// Logger the handler subscribes to, or `None` to subscribe to the [`LOGGER`].
+    logger: Option<Logger>,
     sub_kind: PhantomData<K>,
 }
 
@@ -49,10 +52,21 @@ impl LogEventHandlerBuilder<NoKind> {
                 SHUTDOWN_HANDLER,
             ],
             handler: Vec::new(),
+            logger: None,
             sub_kind: PhantomData,
         }
     }
 
+    /// Creates a builder for a handler receiving events of the given logger instead of the [`LOGGER`].
+    ///
+    /// Terminal handlers use the filter of the given logger to decide which event information is shown.
+    pub fn with_logger(logger: &Logger) -> Self {
+        LogEventHandlerBuilder {
+            logger: Some(logger.clone()),
+            ..LogEventHandlerBuilder::new()
+        }
+    }
+
     pub fn add_handler(
         mut self,
         handler: impl FnMut(Arc<Event<LogId, LogMsg, LogEventEntry>>) + std::marker::Send + 'static,
@@ -61,13 +75,57 @@ impl LogEventHandlerBuilder<NoKind> {
         self
     }
 
-    pub fn to_stderr(mut self) -> Self {
-        self.handler.push(Box::new(stderr_writer));
+    pub fn to_stderr(self) -> Self {
+        self.to_stderr_with(RenderOptions::default())
+    }
+
+    /// Prints events to stderr like [`to_stderr()`](Self::to_stderr), but rendered with the given options.
+    pub fn to_stderr_with(mut self, options: RenderOptions) -> Self {
+        let logger = self.logger.clone();
+        self.handler.push(Box::new(move |event| {
+            stderr_writer(event, logger.as_ref(), &options)
+        }));
         self
     }
 
-    pub fn to_stdout(mut self) -> Self {
-        self.handler.push(Box::new(stdout_writer));
+    pub fn to_stdout(self) -> Self {
+        self.to_stdout_with(RenderOptions::default())
+    }
+
+    /// Prints events to stdout like [`to_stdout()`](Self::to_stdout), but rendered with the given options.
+    pub fn to_stdout_with(mut self, options: RenderOptions) -> Self {
+        let logger = self.logger.clone();
+        self.handler.push(Box::new(move |event| {
+            stdout_writer(event, logger.as_ref(), &options)
+        }));
+        self
+    }
+
+    #[cfg(feature = "json")]
+    pub fn to_stderr_json(self) -> Self {
+        self.to_stderr_json_with(RenderOptions::default())
+    }
+
+    /// Prints events to stderr like [`to_stderr_json()`](Self::to_stderr_json), but rendered with the given options.
+    #[cfg(feature = "json")]
+    pub fn to_stderr_json_with(mut self, options: RenderOptions) -> Self {
+        self.handler.push(Box::new(move |event| {
+            super::json::stderr_json_writer(event, &options)
+        }));
+        self
+    }
+
+    #[cfg(feature = "json")]
+    pub fn to_stdout_json(self) -> Self {
+        self.to_stdout_json_with(RenderOptions::default())
+    }
+
+    /// Prints events to stdout like [`to_stdout_json()`](Self::to_stdout_json), but rendered with the given options.
+    #[cfg(feature = "json")]
+    pub fn to_stdout_json_with(mut self, options: RenderOptions) -> Self {
+        self.handler.push(Box::new(move |event| {
+            super::json::stdout_json_writer(event, &options)
+        }));
         self
     }
 
@@ -75,6 +133,7 @@ impl LogEventHandlerBuilder<NoKind> {
         LogEventHandlerBuilder {
             log_ids: self.log_ids,
             handler: self.handler,
+            logger: self.logger,
             sub_kind: PhantomData,
         }
     }
@@ -88,6 +147,7 @@ impl LogEventHandlerBuilder<NoKind> {
         LogEventHandlerBuilder {
             log_ids: self.log_ids,
             handler: self.handler,
+            logger: self.logger,
             sub_kind: PhantomData,
         }
     }
@@ -107,15 +167,24 @@ impl<K> LogEventHandlerBuilder<K> {
         let capturing = Arc::new(AtomicBool::new(true));
         let moved_capturing = capturing.clone();
 
-        let sub_res = if subscribe_specific {
-            LOGGER.subscribe_to_many(self.log_ids)
-        } else {
-            LOGGER.subscribe_to_all_events()
-        };
+        // Note: Subscriptions borrow their publisher, so the subscription is created on the handler thread that owns a logger clone.
+        let moved_logger = self.logger.clone();
+        let (sub_sender, sub_result) = std::sync::mpsc::channel();
+
+        let log_thread = std::thread::spawn(move || {
+            let publisher = match &moved_logger {
+                Some(logger) => &**logger,
+                None => &*LOGGER,
+            };
+            let sub_res = if subscribe_specific {
+                publisher.subscribe_to_many(self.log_ids)
+            } else {
+                publisher.subscribe_to_all_events()
+            };
 
-        match sub_res {
-            Ok(recv) => {
-                let log_thread = std::thread::spawn(move || {
+            match sub_res {
+                Ok(recv) => {
+                    let _ = sub_sender.send(true);
                     event_listener(
                         self.handler,
                         recv.get_receiver(),
@@ -124,17 +193,23 @@ impl<K> LogEventHandlerBuilder<K> {
                         moved_shutdown,
                         moved_capturing,
                     );
-                });
-
-                Ok(LogEventHandler {
-                    log_thread,
-                    start,
-                    stop,
-                    shutdown,
-                    capturing,
-                })
+                }
+                Err(_) => {
+                    let _ = sub_sender.send(false);
+                }
             }
-            Err(_) => Err(LogEventHandlerError::CreatingSubscription),
+        });
+
+        match sub_result.recv() {
+            Ok(true) => Ok(LogEventHandler {
+                log_thread,
+                logger: self.logger,
+                start,
+                stop,
+                shutdown,
+                capturing,
+            }),
+            _ => Err(LogEventHandlerError::CreatingSubscription),
         }
     }
 }
@@ -0,0 +1,328 @@
+//! Contains the [`FlightRecorder`] that keeps the latest events in memory, and dumps them on demand.
+//!
+//! The recorder only writes events once an event with the dump level or higher is received, or [`FlightRecorder::dump()`] is called.
+//! This gives detailed context around failures, without writing verbose output in steady state.
+//!
+//! The recorder is added as recorder of its target logger, so it records all events that pass its record filter,
+//! regardless of the filter of the target logger.
+//! To record debug and trace events, features `log_debugs` or `log_traces` must be enabled.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid::{
+//!     event_handler::flight_recorder::{FlightRecorder, RecorderCapacity},
+//!     log_id::LogLevel,
+//!     logging::filter::FilterConfig,
+//! };
+//!
+//! let recorder = FlightRecorder::builder(RecorderCapacity::Events(500))
+//!     .record_filter(FilterConfig::new("debug"))
+//!     .dump_level(LogLevel::Error)
+//!     .to_stderr()
+//!     .build()
+//!     .unwrap();
+//! ~~~
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use logid_core::{
+    evident::event::{entry::EventEntry, Event},
+    log_id::{LogId, LogLevel},
+    logging::{
+        event_entry::LogEventEntry,
+        filter::FilterConfig,
+        logger::{add_recorder, remove_recorder, Logger},
+        msg::LogMsg,
+    },
+};
+
+use super::{
+    builder::{LogEventHandlerBuilder, LogEventHandlerError},
+    render::RenderOptions,
+    terminal::{stderr_writer, stdout_writer},
+    LogEventHandler,
+};
+
+type RecordedEvent = Arc<Event<LogId, LogMsg, LogEventEntry>>;
+type Sink = Box<dyn FnMut(RecordedEvent) + std::marker::Send + 'static>;
+
+/// Defines how many events are kept by a [`FlightRecorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecorderCapacity {
+    /// Keeps at most the given number of events.
+    Events(usize),
+    /// Keeps events until their estimated size exceeds the given number of bytes.
+    ///
+    /// The latest event is always kept, even if it is larger than the given size.
+    Bytes(usize),
+}
+
+/// Recorder keeping the latest events in a ring buffer, and writing them to its sink on dump.
+///
+/// Clones share the same buffer and sink. Recording stops once the last clone is dropped.
+#[derive(Clone)]
+pub struct FlightRecorder {
+    state: Arc<Mutex<RecorderState>>,
+    sink: Arc<Mutex<Sink>>,
+    dump_level: LogLevel,
+    _capture: Arc<RecorderCapture>,
+}
+
+struct RecorderState {
+    capacity: RecorderCapacity,
+    events: VecDeque<(RecordedEvent, usize)>,
+    bytes: usize,
+}
+
+/// Dedicated logger of a recorder, and the handler receiving its events.
+struct RecorderCapture {
+    /// Logger the recorder is added to, or `None` for the [`LOGGER`](logid_core::logging::LOGGER).
+    target: Option<Logger>,
+    logger: Logger,
+    _handler: LogEventHandler,
+}
+
+impl Drop for RecorderCapture {
+    fn drop(&mut self) {
+        remove_recorder(self.target.as_ref(), &self.logger);
+    }
+}
+
+impl FlightRecorder {
+    pub fn builder(capacity: RecorderCapacity) -> FlightRecorderBuilder {
+        FlightRecorderBuilder::new(capacity)
+    }
+
+    /// Writes all recorded events to the sink in the order they were received, and clears the recorder.
+    pub fn dump(&self) {
+        dump(&self.state, &self.sink);
+    }
+
+    /// Returns all recorded events in the order they were received.
+    pub fn events(&self) -> Vec<Arc<Event<LogId, LogMsg, LogEventEntry>>> {
+        match self.state.lock() {
+            Ok(state) => state
+                .events
+                .iter()
+                .map(|(event, _)| event.clone())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the estimated size of all recorded events in bytes.
+    pub fn recorded_bytes(&self) -> usize {
+        match self.state.lock() {
+            Ok(state) => state.bytes,
+            Err(_) => 0,
+        }
+    }
+
+    /// Removes all recorded events without writing them.
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.events.clear();
+            state.bytes = 0;
+        }
+    }
+}
+
+impl std::fmt::Debug for FlightRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlightRecorder")
+            .field("dump_level", &self.dump_level)
+            .field("recorded_bytes", &self.recorded_bytes())
+            .finish()
+    }
+}
+
+/// Records the given event, and dumps all recorded events if the event has the dump level or higher.
+fn record(
+    state: &Mutex<RecorderState>,
+    sink: &Mutex<Sink>,
+    dump_level: LogLevel,
+    event: RecordedEvent,
+) {
+    let is_dump = event.get_event_id().get_log_level() >= dump_level;
+
+    if let Ok(mut state) = state.lock() {
+        state.push(event);
+    }
+
+    if is_dump {
+        dump(state, sink);
+    }
+}
+
+/// Takes all recorded events, and writes them to the sink after the recorder is unlocked.
+///
+/// **Note:** The sink may set events itself, so it must not be called while the recorder is locked.
+fn dump(state: &Mutex<RecorderState>, sink: &Mutex<Sink>) {
+    let events = match state.lock() {
+        Ok(mut state) => state.take(),
+        Err(_) => return,
+    };
+
+    if let Ok(mut sink) = sink.lock() {
+        for (event, _) in events {
+            (sink)(event);
+        }
+    }
+}
+
+impl RecorderState {
+    fn push(&mut self, event: RecordedEvent) {
+        let size = estimated_size(event.get_entry());
+        self.bytes += size;
+        self.events.push_back((event, size));
+
+        // Note: The latest event is never removed, so a dump always contains the event that triggered it.
+        while self.events.len() > 1 && self.exceeds_capacity() {
+            if let Some((_, size)) = self.events.pop_front() {
+                self.bytes -= size;
+            }
+        }
+    }
+
+    fn exceeds_capacity(&self) -> bool {
+        match self.capacity {
+            RecorderCapacity::Events(max) => self.events.len() > max,
+            RecorderCapacity::Bytes(max) => self.bytes > max,
+        }
+    }
+
+    fn take(&mut self) -> VecDeque<(RecordedEvent, usize)> {
+        self.bytes = 0;
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// Returns the estimated memory size of the given entry in bytes.
+fn estimated_size(entry: &LogEventEntry) -> usize {
+    let msg_len = entry
+        .get_msg()
+        .map(|msg| msg.to_string().len())
+        .unwrap_or_default();
+    let addon_len: usize = entry
+        .get_infos()
+        .iter()
+        .chain(entry.get_debugs())
+        .chain(entry.get_traces())
+        .map(String::len)
+        .sum();
+    let field_len: usize = entry
+        .get_fields()
+        .iter()
+        .map(|field| field.get_key().len() + field.get_value().to_string().len())
+        .sum();
+
+    std::mem::size_of::<Event<LogId, LogMsg, LogEventEntry>>() + msg_len + addon_len + field_len
+}
+
+/// Builder for a [`FlightRecorder`].
+pub struct FlightRecorderBuilder {
+    capacity: RecorderCapacity,
+    dump_level: LogLevel,
+    record_filter: FilterConfig,
+    target: Option<Logger>,
+    sink: Option<Sink>,
+}
+
+impl FlightRecorderBuilder {
+    /// Creates a builder for a recorder with the given capacity.
+    ///
+    /// By default, all events set for the [`LOGGER`](logid_core::logging::LOGGER) are recorded,
+    /// error events trigger a dump, and events are dumped to `stderr`.
+    pub fn new(capacity: RecorderCapacity) -> Self {
+        FlightRecorderBuilder {
+            capacity,
+            dump_level: LogLevel::Error,
+            record_filter: FilterConfig::new("trace"),
+            target: None,
+            sink: None,
+        }
+    }
+
+    /// Sets the lowest level of events that trigger a dump.
+    pub fn dump_level(mut self, level: LogLevel) -> Self {
+        self.dump_level = level;
+        self
+    }
+
+    /// Sets the filter deciding which events are recorded, regardless of the filter of the target logger.
+    pub fn record_filter<T: Into<FilterConfig>>(mut self, into_filter: T) -> Self {
+        self.record_filter = into_filter.into();
+        self
+    }
+
+    /// Records events set for the given logger instead of the [`LOGGER`](logid_core::logging::LOGGER).
+    pub fn target(mut self, logger: &Logger) -> Self {
+        self.target = Some(logger.clone());
+        self
+    }
+
+    /// Sets the function that receives dumped events.
+    pub fn sink(
+        mut self,
+        sink: impl FnMut(Arc<Event<LogId, LogMsg, LogEventEntry>>) + std::marker::Send + 'static,
+    ) -> Self {
+        self.sink = Some(Box::new(sink));
+        self
+    }
+
+    pub fn to_stderr(self) -> Self {
+        self.sink(|event| stderr_writer(event, None, &RenderOptions::default()))
+    }
+
+    pub fn to_stdout(self) -> Self {
+        self.sink(|event| stdout_writer(event, None, &RenderOptions::default()))
+    }
+
+    #[cfg(feature = "json")]
+    pub fn to_stderr_json(self) -> Self {
+        self.sink(|event| super::json::stderr_json_writer(event, &RenderOptions::default()))
+    }
+
+    #[cfg(feature = "json")]
+    pub fn to_stdout_json(self) -> Self {
+        self.sink(|event| super::json::stdout_json_writer(event, &RenderOptions::default()))
+    }
+
+    /// Creates the recorder, and starts recording events set for the target logger.
+    pub fn build(self) -> Result<FlightRecorder, LogEventHandlerError> {
+        let sink = self.sink.unwrap_or_else(|| {
+            Box::new(|event| stderr_writer(event, None, &RenderOptions::default()))
+        });
+        let state = Arc::new(Mutex::new(RecorderState {
+            capacity: self.capacity,
+            events: VecDeque::new(),
+            bytes: 0,
+        }));
+        let sink = Arc::new(Mutex::new(sink));
+
+        let logger = Logger::builder().filter(self.record_filter).build();
+        let handler_state = state.clone();
+        let handler_sink = sink.clone();
+        let dump_level = self.dump_level;
+        let handler = LogEventHandlerBuilder::with_logger(&logger)
+            .add_handler(move |event| record(&handler_state, &handler_sink, dump_level, event))
+            .all_log_events()
+            .build()?;
+        add_recorder(self.target.as_ref(), &logger);
+
+        Ok(FlightRecorder {
+            state,
+            sink,
+            dump_level,
+            _capture: Arc::new(RecorderCapture {
+                target: self.target,
+                logger,
+                _handler: handler,
+            }),
+        })
+    }
+}
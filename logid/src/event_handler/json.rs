@@ -0,0 +1,305 @@
+use std::{io::Write, sync::Arc};
+
+use logid_core::{
+    evident::event::{finalized::FinalizedEvent, Event},
+    log_id::{LogId, LogLevel},
+    logging::{
+        event_entry::LogEventEntry,
+        field::{FieldValue, LogField},
+        msg::LogMsg,
+    },
+    serde_json::{Map, Number, Value},
+};
+
+use super::render::{
+    EntryPlaceholders, RenderOptions, FILE_PLACEHOLDER, LINE_PLACEHOLDER, TIMESTAMP_PLACEHOLDER,
+};
+
+pub(super) fn stderr_json_writer(
+    log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>,
+    options: &RenderOptions,
+) {
+    json_writer(log_event, options, true);
+}
+
+pub(super) fn stdout_json_writer(
+    log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>,
+    options: &RenderOptions,
+) {
+    json_writer(log_event, options, false);
+}
+
+fn json_writer(
+    log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>,
+    options: &RenderOptions,
+    to_stderr: bool,
+) {
+    let line = event_to_json_with(&log_event, options).to_string();
+
+    if to_stderr {
+        let _ = writeln!(std::io::stderr().lock(), "{line}");
+    } else {
+        let _ = writeln!(std::io::stdout().lock(), "{line}");
+    }
+}
+
+/// Converts the given event into a JSON object.
+///
+/// Optional entries like addons are only added to the object if they are not empty.
+pub fn event_to_json(log_event: &Event<LogId, LogMsg, LogEventEntry>) -> Value {
+    event_to_json_with(log_event, &RenderOptions::default())
+}
+
+/// Converts the given event into a JSON object like [`event_to_json()`], but replaces values according to the given options.
+///
+/// Entry ID placeholders are numbered per event, starting with the entry ID of the given event.
+pub fn event_to_json_with(
+    log_event: &Event<LogId, LogMsg, LogEventEntry>,
+    options: &RenderOptions,
+) -> Value {
+    event_to_json_numbered(log_event, options, &EntryPlaceholders::default())
+}
+
+/// Converts the given event like [`event_to_json_with()`], but numbers entry ID placeholders with the given placeholders.
+pub(super) fn event_to_json_numbered(
+    log_event: &Event<LogId, LogMsg, LogEventEntry>,
+    options: &RenderOptions,
+    placeholders: &EntryPlaceholders,
+) -> Value {
+    let id = log_event.get_event_id();
+    let entry = log_event.get_entry();
+    let origin = log_event.get_origin();
+    let mut obj = Map::new();
+
+    if let Some(timestamp) = log_event.get_timestamp() {
+        if options.placeholder_timestamps {
+            obj.insert(
+                "timestamp".to_string(),
+                Value::String(TIMESTAMP_PLACEHOLDER.to_string()),
+            );
+        } else if let Ok(since_epoch) = timestamp.duration_since(std::time::UNIX_EPOCH) {
+            obj.insert(
+                "timestamp".to_string(),
+                f64_value(since_epoch.as_secs_f64()),
+            );
+        }
+    }
+    obj.insert(
+        "level".to_string(),
+        Value::String(level_str(id.get_log_level()).to_string()),
+    );
+    obj.insert(
+        "id".to_string(),
+        Value::String(format!("{}::{}", id.get_module_path(), id.get_identifier())),
+    );
+    if let Some(code) = id.get_code() {
+        obj.insert("code".to_string(), Value::String(code.to_string()));
+    }
+    if let Some(help) = id.help() {
+        obj.insert("help".to_string(), Value::String(help));
+    }
+    obj.insert(
+        "entry".to_string(),
+        Value::String(options.entry_id_string(&log_event.get_entry_id(), placeholders)),
+    );
+    if let Some(msg) = log_event.get_msg() {
+        obj.insert("msg".to_string(), Value::String(msg.to_string()));
+    }
+
+    let mut origin_obj = Map::new();
+    origin_obj.insert(
+        "module".to_string(),
+        Value::String(origin.module_path.to_string()),
+    );
+    if options.placeholder_origins {
+        origin_obj.insert(
+            "file".to_string(),
+            Value::String(FILE_PLACEHOLDER.to_string()),
+        );
+        origin_obj.insert(
+            "line".to_string(),
+            Value::String(LINE_PLACEHOLDER.to_string()),
+        );
+    } else {
+        origin_obj.insert(
+            "file".to_string(),
+            Value::String(origin.filename.to_string()),
+        );
+        origin_obj.insert("line".to_string(), Value::from(origin.line_nr));
+    }
+    obj.insert("origin".to_string(), Value::Object(origin_obj));
+
+    if let Some(thread_name) = log_event.get_thread_name() {
+        obj.insert("thread".to_string(), Value::String(thread_name.to_string()));
+    }
+
+    if !entry.get_fields().is_empty() {
+        obj.insert(
+            "fields".to_string(),
+            fields_to_json_with(entry.get_fields(), options),
+        );
+    }
+
+    #[allow(unused_mut)]
+    let mut infos = to_string_values(entry.get_infos());
+    #[cfg(feature = "fmt")]
+    infos.extend(to_string_values(entry.get_fmt_infos()));
+    insert_non_empty(&mut obj, "infos", infos);
+
+    #[allow(unused_mut)]
+    let mut debugs = to_string_values(entry.get_debugs());
+    #[cfg(feature = "fmt")]
+    debugs.extend(to_string_values(entry.get_fmt_debugs()));
+    insert_non_empty(&mut obj, "debugs", debugs);
+
+    #[allow(unused_mut)]
+    let mut traces = to_string_values(entry.get_traces());
+    #[cfg(feature = "fmt")]
+    traces.extend(to_string_values(entry.get_fmt_traces()));
+    insert_non_empty(&mut obj, "traces", traces);
+
+    if let Some(parent) = entry.get_parent() {
+        obj.insert(
+            "parent".to_string(),
+            finalized_to_json(parent, options, placeholders),
+        );
+    }
+
+    let related = entry
+        .get_related()
+        .iter()
+        .map(|related| finalized_to_json(related, options, placeholders))
+        .collect();
+    insert_non_empty(&mut obj, "related", related);
+
+    #[cfg(feature = "hint_note")]
+    {
+        #[allow(unused_mut)]
+        let mut hints = to_string_values(entry.get_hints());
+        #[cfg(feature = "fmt")]
+        hints.extend(to_string_values(entry.get_fmt_hints()));
+        insert_non_empty(&mut obj, "hints", hints);
+
+        #[allow(unused_mut)]
+        let mut notes = to_string_values(entry.get_notes());
+        #[cfg(feature = "fmt")]
+        notes.extend(to_string_values(entry.get_fmt_notes()));
+        insert_non_empty(&mut obj, "notes", notes);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    {
+        let diagnostics = entry
+            .get_diagnostics()
+            .iter()
+            .filter_map(|diag| logid_core::serde_json::to_value(diag).ok())
+            .collect();
+        insert_non_empty(&mut obj, "diagnostics", diagnostics);
+    }
+
+    #[cfg(feature = "payloads")]
+    {
+        #[allow(unused_mut)]
+        let mut payloads: Vec<Value> = entry.get_payloads().clone();
+        #[cfg(feature = "fmt")]
+        payloads.extend(to_string_values(entry.get_fmt_payloads()));
+        insert_non_empty(&mut obj, "payloads", payloads);
+    }
+
+    Value::Object(obj)
+}
+
+/// Converts fields into a JSON object with one member per field.
+///
+/// **Note:** If multiple fields have the same key, the last one is used.
+pub fn fields_to_json(fields: &[LogField]) -> Value {
+    fields_to_json_with(fields, &RenderOptions::default())
+}
+
+fn fields_to_json_with(fields: &[LogField], options: &RenderOptions) -> Value {
+    let mut obj = Map::new();
+
+    for field in fields {
+        let value = match field.get_value() {
+            FieldValue::Timestamp(_) if options.placeholder_timestamps => {
+                Value::String(TIMESTAMP_PLACEHOLDER.to_string())
+            }
+            value => field_value_to_json(value),
+        };
+        obj.insert(field.get_key().to_string(), value);
+    }
+
+    Value::Object(obj)
+}
+
+/// Converts the value of a field into its JSON representation.
+///
+/// Durations and timestamps are converted to seconds, and bytes to a hex-encoded string.
+/// Timestamps are seconds since the Unix epoch.
+pub fn field_value_to_json(value: &FieldValue) -> Value {
+    match value {
+        FieldValue::Int(v) => Value::from(*v),
+        FieldValue::UInt(v) => Value::from(*v),
+        FieldValue::Float(v) => f64_value(*v),
+        FieldValue::Bool(v) => Value::Bool(*v),
+        FieldValue::Str(v) => Value::String(v.clone()),
+        FieldValue::Duration(v) => f64_value(v.as_secs_f64()),
+        FieldValue::Timestamp(_) => value.as_f64().map(f64_value).unwrap_or(Value::Null),
+        FieldValue::Bytes(_) => Value::String(value.to_string()),
+    }
+}
+
+/// Converts a finalized event into a JSON object with its ID, level, and entry ID.
+fn finalized_to_json(
+    finalized: &FinalizedEvent<LogId>,
+    options: &RenderOptions,
+    placeholders: &EntryPlaceholders,
+) -> Value {
+    let id = finalized.get_event_id();
+    let mut obj = Map::new();
+    obj.insert(
+        "id".to_string(),
+        Value::String(format!("{}::{}", id.get_module_path(), id.get_identifier())),
+    );
+    if let Some(code) = id.get_code() {
+        obj.insert("code".to_string(), Value::String(code.to_string()));
+    }
+    obj.insert(
+        "level".to_string(),
+        Value::String(level_str(id.get_log_level()).to_string()),
+    );
+    obj.insert(
+        "entry".to_string(),
+        Value::String(options.entry_id_string(finalized.get_entry_id(), placeholders)),
+    );
+    Value::Object(obj)
+}
+
+fn f64_value(v: f64) -> Value {
+    Number::from_f64(v)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+fn level_str(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Error => "error",
+        LogLevel::Warn => "warn",
+        LogLevel::Info => "info",
+        LogLevel::Debug => "debug",
+        LogLevel::Trace => "trace",
+    }
+}
+
+fn to_string_values<T: ToString>(values: &[T]) -> Vec<Value> {
+    values
+        .iter()
+        .map(|v| Value::String(v.to_string()))
+        .collect()
+}
+
+fn insert_non_empty(obj: &mut Map<String, Value>, key: &str, values: Vec<Value>) {
+    if !values.is_empty() {
+        obj.insert(key.to_string(), Value::Array(values));
+    }
+}
@@ -8,19 +8,28 @@ use std::{
 
 use logid_core::{
     log_id::{LogId, LogLevel},
-    logging::{event_entry::LogEventEntry, intermediary_event::IntermediaryLogEvent},
+    logging::{intermediary_event::IntermediaryLogEvent, logger::Logger, msg::NO_MSG},
     new_log_id,
 };
 
+#[cfg(feature = "binary")]
+pub mod binary;
 pub mod builder;
+pub mod flight_recorder;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod render;
+pub mod store;
 pub mod terminal;
 
-pub(self) const HANDLER_START_LOGGING: LogId = new_log_id!("HANDLER_START_LOGGING", LogLevel::Info);
-pub(self) const HANDLER_STOP_LOGGING: LogId = new_log_id!("HANDLER_STOP_LOGGING", LogLevel::Info);
-pub(self) const SHUTDOWN_HANDLER: LogId = new_log_id!("SHUTDOWN_HANDLER", LogLevel::Info);
+const HANDLER_START_LOGGING: LogId = new_log_id!("HANDLER_START_LOGGING", LogLevel::Info);
+const HANDLER_STOP_LOGGING: LogId = new_log_id!("HANDLER_STOP_LOGGING", LogLevel::Info);
+const SHUTDOWN_HANDLER: LogId = new_log_id!("SHUTDOWN_HANDLER", LogLevel::Info);
 
 pub struct LogEventHandler {
     log_thread: JoinHandle<()>,
+    /// Logger the handler is subscribed to, or `None` for the [`LOGGER`](crate::logging::LOGGER).
+    logger: Option<Logger>,
     /// Start flag needed to have independent handler.
     start: Arc<AtomicBool>,
     /// Stop flag needed to have independent handler.
@@ -35,21 +44,13 @@ impl LogEventHandler {
     pub fn start(&self) {
         self.start.store(true, Ordering::Release);
 
-        crate::evident::event::set_event::<_, _, LogEventEntry, IntermediaryLogEvent>(
-            HANDLER_START_LOGGING,
-            crate::evident::this_origin!(),
-        )
-        .finalize();
+        self.set_control_event(HANDLER_START_LOGGING);
     }
 
     pub fn stop(&self) {
         self.stop.store(true, Ordering::Release);
 
-        crate::evident::event::set_event::<_, _, LogEventEntry, IntermediaryLogEvent>(
-            HANDLER_STOP_LOGGING,
-            crate::evident::this_origin!(),
-        )
-        .finalize();
+        self.set_control_event(HANDLER_STOP_LOGGING);
     }
 
     pub fn shutdown(self) {
@@ -59,17 +60,24 @@ impl LogEventHandler {
     pub fn is_capturing(&self) -> bool {
         self.capturing.load(Ordering::Acquire)
     }
+
+    /// Sets the given control event for the logger the handler is subscribed to.
+    fn set_control_event(&self, id: LogId) {
+        IntermediaryLogEvent::with_target(
+            self.logger.as_ref(),
+            id,
+            NO_MSG,
+            crate::evident::this_origin!(),
+        )
+        .finalize();
+    }
 }
 
 impl Drop for LogEventHandler {
     fn drop(&mut self) {
         self.shutdown.store(true, Ordering::Release);
 
-        crate::evident::event::set_event::<_, _, LogEventEntry, IntermediaryLogEvent>(
-            SHUTDOWN_HANDLER,
-            crate::evident::this_origin!(),
-        )
-        .finalize();
+        self.set_control_event(SHUTDOWN_HANDLER);
 
         // Note: 'join()' needs 'self', but drop() only provides `&mut self`
         // Also in some rare timing issues the shutdown event is not received in the handler thread, if the main thread finished before sending the event to all listeners.
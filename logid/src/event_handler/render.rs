@@ -0,0 +1,270 @@
+//! Contains options and helpers to render log-id events to strings.
+//!
+//! Rendered events contain random entry IDs, timestamps, and code positions.
+//! [`RenderOptions::deterministic()`] replaces them with stable placeholders,
+//! so rendered output may be compared against snapshots in tests.
+//!
+//! **Usage:** (with feature `testing`)
+//!
+//! ~~~
+//! # #[cfg(feature = "testing")]
+//! # {
+//! use logid::{
+//!     event_handler::render::{render_captured, RenderFormat, RenderOptions},
+//!     log,
+//!     log_id::{LogId, LogLevel},
+//!     new_log_id,
+//!     testing::capture_events,
+//! };
+//!
+//! const NOT_FOUND: LogId = new_log_id!("NotFound", LogLevel::Error);
+//!
+//! let cap = capture_events();
+//! log!(NOT_FOUND, "File not found.");
+//!
+//! let rendered = render_captured(&cap, RenderFormat::Terminal, &RenderOptions::deterministic());
+//! assert!(rendered.starts_with("ERR   File not found.\n"));
+//! # }
+//! ~~~
+
+use std::{cell::RefCell, collections::HashMap};
+
+use logid_core::{
+    evident::{
+        event::{origin::Origin, Event},
+        uuid::Uuid,
+    },
+    log_id::LogId,
+    logging::{
+        event_entry::LogEventEntry,
+        field::{FieldValue, LogField},
+        logger::target_filter,
+        msg::LogMsg,
+    },
+};
+
+/// Placeholder for timestamps of events and timestamp fields.
+pub const TIMESTAMP_PLACEHOLDER: &str = "<timestamp>";
+/// Placeholder for the file of event origins.
+pub const FILE_PLACEHOLDER: &str = "<file>";
+/// Placeholder for the line of event origins.
+pub const LINE_PLACEHOLDER: &str = "<line>";
+
+/// Options to render log-id events.
+///
+/// The default options render events like the event handlers print them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Replaces entry IDs with `<entry-N>`, numbered in order of their first appearance.
+    pub placeholder_entry_ids: bool,
+    /// Replaces timestamps with [`TIMESTAMP_PLACEHOLDER`].
+    pub placeholder_timestamps: bool,
+    /// Replaces file and line of origins with [`FILE_PLACEHOLDER`] and [`LINE_PLACEHOLDER`].
+    pub placeholder_origins: bool,
+    /// Keeps terminal colors in the rendered output.
+    pub colored: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            placeholder_entry_ids: false,
+            placeholder_timestamps: false,
+            placeholder_origins: false,
+            colored: true,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Options replacing all values that change between runs, and removing terminal colors.
+    pub fn deterministic() -> Self {
+        RenderOptions {
+            placeholder_entry_ids: true,
+            placeholder_timestamps: true,
+            placeholder_origins: true,
+            colored: false,
+        }
+    }
+
+    /// Applies the color option to the given rendered output.
+    pub fn apply(&self, rendered: &str) -> String {
+        if self.colored {
+            rendered.to_string()
+        } else {
+            strip_ansi(rendered)
+        }
+    }
+
+    pub(super) fn entry_id_string(
+        &self,
+        entry_id: &Uuid,
+        placeholders: &EntryPlaceholders,
+    ) -> String {
+        if self.placeholder_entry_ids {
+            placeholders.get(entry_id)
+        } else {
+            entry_id.to_string()
+        }
+    }
+
+    pub(super) fn origin_string(&self, origin: &Origin) -> String {
+        if self.placeholder_origins {
+            format!(
+                "module=\"{}\", file=\"{}\", line={}",
+                origin.module_path, FILE_PLACEHOLDER, LINE_PLACEHOLDER
+            )
+        } else {
+            origin.to_string()
+        }
+    }
+
+    pub(super) fn field_string(&self, field: &LogField) -> String {
+        match field.get_value() {
+            FieldValue::Timestamp(_) if self.placeholder_timestamps => {
+                format!("{}={}", field.get_key(), TIMESTAMP_PLACEHOLDER)
+            }
+            _ => field.to_string(),
+        }
+    }
+}
+
+/// Placeholders of entry IDs rendered together, numbered in order of their first appearance.
+#[derive(Debug, Default)]
+pub(super) struct EntryPlaceholders(RefCell<HashMap<Uuid, usize>>);
+
+impl EntryPlaceholders {
+    fn get(&self, entry_id: &Uuid) -> String {
+        let mut numbers = self.0.borrow_mut();
+        let next_nr = numbers.len() + 1;
+        format!("<entry-{}>", numbers.entry(*entry_id).or_insert(next_nr))
+    }
+}
+
+/// Output format of rendered events.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// Format of the terminal handlers.
+    #[default]
+    Terminal,
+    /// JSON Lines format of the JSON handlers.
+    #[cfg(feature = "json")]
+    Json,
+}
+
+/// Renders the given events in order, with one event after the other.
+///
+/// Events caused by a previously rendered event are indented in terminal format.
+/// Entry ID placeholders are numbered across all events, so relations between events stay visible.
+pub fn render_events<'a>(
+    events: impl IntoIterator<Item = &'a Event<LogId, LogMsg, LogEventEntry>>,
+    format: RenderFormat,
+    options: &RenderOptions,
+) -> String {
+    let mut depths: HashMap<Uuid, usize> = HashMap::new();
+    let placeholders = EntryPlaceholders::default();
+    let mut rendered = String::new();
+
+    for event in events {
+        match format {
+            RenderFormat::Terminal => {
+                let depth = event
+                    .get_entry()
+                    .get_parent()
+                    .and_then(|parent| depths.get(parent.get_entry_id()))
+                    .map(|parent_depth| parent_depth + 1)
+                    .unwrap_or(0);
+                depths.insert(event.get_entry_id(), depth);
+
+                rendered.push_str(&super::terminal::render_terminal_at_depth(
+                    event,
+                    options,
+                    &placeholders,
+                    target_filter(None),
+                    depth,
+                ));
+            }
+            #[cfg(feature = "json")]
+            RenderFormat::Json => {
+                rendered.push_str(
+                    &super::json::event_to_json_numbered(event, options, &placeholders).to_string(),
+                );
+                rendered.push('\n');
+            }
+        }
+    }
+
+    options.apply(&rendered)
+}
+
+/// Renders all events collected by the given capture guard, e.g. to compare them against a snapshot.
+///
+/// **Note:** Captured events have no timestamp, because timestamps are set once events are received by subscribers.
+/// Only available with feature `testing`.
+#[cfg(feature = "testing")]
+pub fn render_captured(
+    captured: &logid_core::testing::CapturedEvents,
+    format: RenderFormat,
+    options: &RenderOptions,
+) -> String {
+    let events: Vec<_> = captured.entries().into_iter().map(Event::new).collect();
+    render_events(&events, format, options)
+}
+
+/// Removes ANSI escape sequences used for terminal colors.
+pub(super) fn strip_ansi(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Note: Sequences end with a char in range '@'..='~' after '['
+            if chars.next() == Some('[') {
+                for seq_char in chars.by_ref() {
+                    if ('@'..='~').contains(&seq_char) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_ids_numbered_by_first_appearance() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let placeholders = EntryPlaceholders::default();
+        let options = RenderOptions::deterministic();
+
+        assert_eq!(options.entry_id_string(&first, &placeholders), "<entry-1>");
+        assert_eq!(options.entry_id_string(&second, &placeholders), "<entry-2>");
+        assert_eq!(options.entry_id_string(&first, &placeholders), "<entry-1>");
+    }
+
+    #[test]
+    fn entry_ids_kept_without_placeholders() {
+        let entry_id = Uuid::new_v4();
+
+        assert_eq!(
+            RenderOptions::default().entry_id_string(&entry_id, &EntryPlaceholders::default()),
+            entry_id.to_string()
+        );
+    }
+
+    #[test]
+    fn ansi_colors_stripped() {
+        assert_eq!(
+            strip_ansi("\u{1b}[1;31mERR\u{1b}[0m   Failed."),
+            "ERR   Failed."
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Contains the [`EventStore`] that retains captured events in memory, and allows to query them.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid::{
+//!     event_handler::{builder::LogEventHandlerBuilder, store::EventStore},
+//!     log_id::LogLevel,
+//! };
+//!
+//! let store = EventStore::with_capacity(10_000);
+//!
+//! let _handler = LogEventHandlerBuilder::new()
+//!     .add_handler(store.handler())
+//!     .all_log_events()
+//!     .build();
+//!
+//! let recent_db_errors = store
+//!     .query()
+//!     .min_level(LogLevel::Error)
+//!     .module_prefix("my_crate::db")
+//!     .within(std::time::Duration::from_secs(5 * 60))
+//!     .events();
+//! ~~~
+
+use std::{
+    collections::VecDeque,
+    ops::{Bound, RangeBounds},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use logid_core::{
+    evident::{event::Event, uuid::Uuid},
+    log_id::{LogId, LogLevel},
+    logging::{event_entry::LogEventEntry, msg::LogMsg},
+};
+
+type StoredEvent = Arc<Event<LogId, LogMsg, LogEventEntry>>;
+
+/// Store retaining captured events in the order they were received.
+///
+/// Clones share the same events.
+#[derive(Debug, Clone, Default)]
+pub struct EventStore {
+    events: Arc<RwLock<VecDeque<StoredEvent>>>,
+    /// Maximum number of retained events, or `None` to retain all events.
+    capacity: Option<usize>,
+}
+
+impl EventStore {
+    /// Creates a store retaining all events.
+    pub fn new() -> Self {
+        EventStore::default()
+    }
+
+    /// Creates a store retaining at most the given number of events.
+    ///
+    /// The oldest events are removed once the capacity is reached.
+    pub fn with_capacity(capacity: usize) -> Self {
+        EventStore {
+            events: Arc::new(RwLock::new(VecDeque::with_capacity(capacity))),
+            capacity: Some(capacity),
+        }
+    }
+
+    /// Returns a handler function for [`LogEventHandlerBuilder::add_handler`](super::builder::LogEventHandlerBuilder::add_handler)
+    /// that inserts all received events into this store.
+    pub fn handler(
+        &self,
+    ) -> impl FnMut(Arc<Event<LogId, LogMsg, LogEventEntry>>) + std::marker::Send + 'static {
+        let store = self.clone();
+        move |event| store.insert(event)
+    }
+
+    pub fn insert(&self, event: Arc<Event<LogId, LogMsg, LogEventEntry>>) {
+        if let Ok(mut events) = self.events.write() {
+            if let Some(capacity) = self.capacity {
+                if capacity == 0 {
+                    return;
+                }
+                while events.len() >= capacity {
+                    events.pop_front();
+                }
+            }
+            events.push_back(event);
+        }
+    }
+
+    /// Returns a query over all retained events.
+    pub fn query(&self) -> EventQuery<'_> {
+        EventQuery::new(self)
+    }
+
+    /// Returns the event with the given entry ID, if it is retained.
+    pub fn get(&self, entry_id: Uuid) -> Option<Arc<Event<LogId, LogMsg, LogEventEntry>>> {
+        self.query().entry(entry_id).events().into_iter().next()
+    }
+
+    /// Returns the retained parent and related events of the event with the given entry ID.
+    pub fn linked_from(&self, entry_id: Uuid) -> Vec<Arc<Event<LogId, LogMsg, LogEventEntry>>> {
+        let Some(event) = self.get(entry_id) else {
+            return Vec::new();
+        };
+        let entry = event.get_entry();
+        let linked: Vec<Uuid> = entry
+            .get_parent()
+            .into_iter()
+            .chain(entry.get_related())
+            .map(|finalized| *finalized.get_entry_id())
+            .collect();
+
+        match self.events.read() {
+            Ok(events) => events
+                .iter()
+                .filter(|event| linked.contains(&event.get_entry_id()))
+                .cloned()
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self.events.read() {
+            Ok(events) => events.len(),
+            Err(_) => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all retained events.
+    pub fn clear(&self) {
+        if let Ok(mut events) = self.events.write() {
+            events.clear();
+        }
+    }
+}
+
+/// Query over the events of an [`EventStore`].
+///
+/// All set conditions must match for an event to be returned.
+/// Events without timestamp never match a time window.
+#[derive(Debug)]
+pub struct EventQuery<'a> {
+    store: &'a EventStore,
+    ids: Vec<LogId>,
+    min_level: Bound<LogLevel>,
+    max_level: Bound<LogLevel>,
+    module_prefix: Option<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    entry_id: Option<Uuid>,
+    linked_to: Option<Uuid>,
+    limit: Option<usize>,
+}
+
+impl<'a> EventQuery<'a> {
+    fn new(store: &'a EventStore) -> Self {
+        EventQuery {
+            store,
+            ids: Vec::new(),
+            min_level: Bound::Unbounded,
+            max_level: Bound::Unbounded,
+            module_prefix: None,
+            since: None,
+            until: None,
+            entry_id: None,
+            linked_to: None,
+            limit: None,
+        }
+    }
+
+    /// Only matches events set with the given log-id.
+    ///
+    /// Setting more than one log-id matches events of any of them.
+    pub fn id(mut self, id: impl Into<LogId>) -> Self {
+        self.ids.push(id.into());
+        self
+    }
+
+    /// Only matches events with a level in the given range, e.g. `LogLevel::Warn..=LogLevel::Error`.
+    pub fn levels(mut self, levels: impl RangeBounds<LogLevel>) -> Self {
+        self.min_level = levels.start_bound().cloned();
+        self.max_level = levels.end_bound().cloned();
+        self
+    }
+
+    /// Only matches events with the given level or higher.
+    pub fn min_level(self, level: LogLevel) -> Self {
+        self.levels(level..)
+    }
+
+    /// Only matches events set in the given module or one of its submodules.
+    ///
+    /// **Note:** The module path of the event origin is used, which is the module the log-id was created in for events set by the extension traits.
+    pub fn module_prefix(mut self, module_path: impl Into<String>) -> Self {
+        self.module_prefix = Some(module_path.into());
+        self
+    }
+
+    /// Only matches events captured at or after the given time.
+    pub fn since(mut self, time: SystemTime) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Only matches events captured at or before the given time.
+    pub fn until(mut self, time: SystemTime) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Only matches events captured within the given duration before now.
+    pub fn within(self, duration: Duration) -> Self {
+        match SystemTime::now().checked_sub(duration) {
+            Some(since) => self.since(since),
+            None => self,
+        }
+    }
+
+    /// Only matches the event with the given entry ID.
+    pub fn entry(mut self, entry_id: Uuid) -> Self {
+        self.entry_id = Some(entry_id);
+        self
+    }
+
+    /// Only matches events that have the event with the given entry ID as parent or related event.
+    pub fn linked_to(mut self, entry_id: Uuid) -> Self {
+        self.linked_to = Some(entry_id);
+        self
+    }
+
+    /// Returns at most the given number of the latest matching events.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns all matching events in the order they were received.
+    pub fn events(&self) -> Vec<Arc<Event<LogId, LogMsg, LogEventEntry>>> {
+        let Ok(events) = self.store.events.read() else {
+            return Vec::new();
+        };
+        let mut matching: Vec<StoredEvent> = events
+            .iter()
+            .filter(|event| self.matches(event))
+            .cloned()
+            .collect();
+
+        if let Some(limit) = self.limit {
+            let skip = matching.len().saturating_sub(limit);
+            matching.drain(..skip);
+        }
+        matching
+    }
+
+    /// Returns the number of matching events.
+    pub fn count(&self) -> usize {
+        self.events().len()
+    }
+
+    /// Returns `true` if the given event matches all conditions of this query.
+    pub fn matches(&self, event: &Event<LogId, LogMsg, LogEventEntry>) -> bool {
+        let id = event.get_event_id();
+
+        if !self.ids.is_empty() && !self.ids.contains(id) {
+            return false;
+        }
+
+        if !(self.min_level, self.max_level).contains(&id.get_log_level()) {
+            return false;
+        }
+
+        if let Some(prefix) = &self.module_prefix {
+            if !is_module_or_submodule(event.get_origin().module_path, prefix) {
+                return false;
+            }
+        }
+
+        if self.since.is_some() || self.until.is_some() {
+            let Some(timestamp) = event.get_timestamp() else {
+                return false;
+            };
+            if self.since.is_some_and(|since| *timestamp < since)
+                || self.until.is_some_and(|until| *timestamp > until)
+            {
+                return false;
+            }
+        }
+
+        if let Some(entry_id) = self.entry_id {
+            if event.get_entry_id() != entry_id {
+                return false;
+            }
+        }
+
+        if let Some(linked) = self.linked_to {
+            let entry = event.get_entry();
+            let is_linked = entry
+                .get_parent()
+                .into_iter()
+                .chain(entry.get_related())
+                .any(|finalized| *finalized.get_entry_id() == linked);
+            if !is_linked {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn is_module_or_submodule(module_path: &str, prefix: &str) -> bool {
+    match module_path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_module_or_submodule;
+
+    #[test]
+    fn module_prefix_matches_path_segments() {
+        assert!(is_module_or_submodule("my_crate::db", "my_crate::db"));
+        assert!(is_module_or_submodule("my_crate::db::pool", "my_crate::db"));
+        assert!(!is_module_or_submodule("my_crate::dbx", "my_crate::db"));
+        assert!(!is_module_or_submodule("other::db", "my_crate::db"));
+    }
+}
@@ -1,4 +1,6 @@
 use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
     io::{BufWriter, Write},
     str::Lines,
     sync::Arc,
@@ -6,20 +8,131 @@ use std::{
 
 use colored::*;
 use logid_core::{
-    evident::event::Event,
+    evident::{event::Event, uuid::Uuid},
     log_id::{LogId, LogLevel},
-    logging::{event_entry::LogEventEntry, msg::LogMsg, LOGGER},
+    logging::{
+        event_entry::LogEventEntry,
+        filter::LogFilter,
+        logger::{target_filter, Logger},
+        msg::LogMsg,
+    },
 };
 
-pub(super) fn stderr_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>) {
-    terminal_writer(log_event, true);
+use super::render::{strip_ansi, EntryPlaceholders, RenderOptions};
+
+pub(super) fn stderr_writer(
+    log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>,
+    target: Option<&Logger>,
+    options: &RenderOptions,
+) {
+    terminal_writer(log_event, target, options, true);
+}
+
+pub(super) fn stdout_writer(
+    log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>,
+    target: Option<&Logger>,
+    options: &RenderOptions,
+) {
+    terminal_writer(log_event, target, options, false);
+}
+
+/// Writes the given event, showing the event id and origin if allowed by the filter of the target logger.
+fn terminal_writer(
+    log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>,
+    target: Option<&Logger>,
+    options: &RenderOptions,
+    to_stderr: bool,
+) {
+    let mut content_builder = build_content(
+        &log_event,
+        options,
+        &EntryPlaceholders::default(),
+        target_filter(target),
+    );
+    content_builder.indent(chain_depth(log_event.get_entry_id(), log_event.get_entry()));
+    if !options.colored {
+        content_builder.strip_colors();
+    }
+
+    let content_len = content_builder.content_len + content_builder.lines.len(); // + line-len for newline char
+    if to_stderr {
+        content_builder.write(BufWriter::with_capacity(
+            content_len,
+            std::io::stderr().lock(),
+        ));
+    } else {
+        content_builder.write(BufWriter::with_capacity(
+            content_len,
+            std::io::stdout().lock(),
+        ));
+    };
+}
+
+/// Renders the given event like it is printed by the terminal handlers.
+///
+/// Use [`RenderOptions::deterministic()`] to get the same output on every run, e.g. for snapshot tests.
+///
+/// Entry ID placeholders are numbered per event, starting with the entry ID of the given event.
+/// Events are not indented by their chain depth, because the depth depends on previously printed events.
+/// Use [`render_events()`](super::render::render_events) to render event chains.
+pub fn render_terminal(
+    log_event: &Event<LogId, LogMsg, LogEventEntry>,
+    options: &RenderOptions,
+) -> String {
+    options.apply(&render_terminal_at_depth(
+        log_event,
+        options,
+        &EntryPlaceholders::default(),
+        target_filter(None),
+        0,
+    ))
 }
 
-pub(super) fn stdout_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>) {
-    terminal_writer(log_event, false);
+/// Renders the given event like [`render_terminal()`], but indented by the given chain depth.
+///
+/// The given filter decides if the event id and origin are shown, instead of the filter of the [`LOGGER`](logid_core::logging::LOGGER).
+/// This allows to render events that were not captured in this process, e.g. events read from written logs.
+pub fn render_terminal_with_filter(
+    log_event: &Event<LogId, LogMsg, LogEventEntry>,
+    options: &RenderOptions,
+    filter: &LogFilter,
+    depth: usize,
+) -> String {
+    options.apply(&render_terminal_at_depth(
+        log_event,
+        options,
+        &EntryPlaceholders::default(),
+        Some(filter),
+        depth,
+    ))
 }
 
-fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stderr: bool) {
+/// Renders the given event indented by the given chain depth, without applying the color option.
+pub(super) fn render_terminal_at_depth(
+    log_event: &Event<LogId, LogMsg, LogEventEntry>,
+    options: &RenderOptions,
+    placeholders: &EntryPlaceholders,
+    filter: Option<&LogFilter>,
+    depth: usize,
+) -> String {
+    let mut content_builder = build_content(log_event, options, placeholders, filter);
+    content_builder.indent(depth);
+
+    let mut rendered =
+        String::with_capacity(content_builder.content_len + content_builder.lines.len());
+    for line in content_builder.lines {
+        rendered.push_str(&line);
+        rendered.push('\n');
+    }
+    rendered
+}
+
+fn build_content(
+    log_event: &Event<LogId, LogMsg, LogEventEntry>,
+    options: &RenderOptions,
+    placeholders: &EntryPlaceholders,
+    filter: Option<&LogFilter>,
+) -> ContentBuilder {
     let id = log_event.get_event_id();
     let level = id.get_log_level();
     let entry = log_event.get_entry();
@@ -35,12 +148,15 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
         Some(msg) => content_builder.add_header(level, &msg.to_string(), &colored_vbar),
         None => content_builder.add_header(
             level,
-            &get_event_string(id, &log_event.get_entry_id().to_string()),
+            &get_event_string(
+                id,
+                &options.entry_id_string(&log_event.get_entry_id(), placeholders),
+            ),
             &colored_vbar,
         ),
     };
 
-    if let Some(filter) = LOGGER.get_filter() {
+    if let Some(filter) = filter {
         let origin = log_event.get_origin();
 
         if filter.show_id(*id, origin) {
@@ -49,7 +165,10 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
                 colored_lcross,
                 colored_arrow,
                 "Event".bold(),
-                get_event_string(id, &log_event.get_entry_id().to_string())
+                get_event_string(
+                    id,
+                    &options.entry_id_string(&log_event.get_entry_id(), placeholders)
+                )
             );
             content_builder.add_line(event_line);
         }
@@ -60,7 +179,7 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
                 colored_lcross,
                 colored_arrow,
                 "Origin".bold(),
-                origin
+                options.origin_string(origin)
             );
             content_builder.add_line(origin_line);
         }
@@ -68,6 +187,22 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
 
     // Note: Addon filter is already applied on capture side, so printing what is captured is fine here
 
+    if let Some(parent) = entry.get_parent() {
+        let parent_id = parent.get_event_id();
+        let parent_line = format!(
+            "{}{} {}: lvl='{}', {}",
+            colored_lcross,
+            colored_arrow,
+            "Caused by".bold(),
+            get_colored_level(parent_id.get_log_level()),
+            get_event_string(
+                parent_id,
+                &options.entry_id_string(parent.get_entry_id(), placeholders)
+            ),
+        );
+        content_builder.add_line(parent_line);
+    }
+
     for related in entry.get_related() {
         let related_id = related.get_event_id();
         let related_line = format!(
@@ -76,11 +211,31 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
             colored_arrow,
             "Related".bold(),
             get_colored_level(related_id.get_log_level()),
-            get_event_string(related_id, &related.get_entry_id().to_string()),
+            get_event_string(
+                related_id,
+                &options.entry_id_string(related.get_entry_id(), placeholders)
+            ),
         );
         content_builder.add_line(related_line);
     }
 
+    if !entry.get_fields().is_empty() {
+        let fields = entry
+            .get_fields()
+            .iter()
+            .map(|field| options.field_string(field))
+            .collect::<Vec<_>>()
+            .join(" ");
+        content_builder.add_multiline_addon(
+            "Fields",
+            fields.lines(),
+            None,
+            &colored_lcross,
+            &colored_arrow,
+            &colored_vbar,
+        );
+    }
+
     for info in entry.get_infos() {
         content_builder.add_multiline_addon(
             "Info",
@@ -198,6 +353,17 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
         );
     }
 
+    if let Some(help) = id.help() {
+        content_builder.add_multiline_addon(
+            "Help",
+            help.lines(),
+            Some(Color::Cyan),
+            &colored_lcross,
+            &colored_arrow,
+            &colored_vbar,
+        );
+    }
+
     // Note: Only formatted diag output, because non-formatted is too much clutter
     #[cfg(all(feature = "diagnostics", feature = "fmt"))]
     for diag in entry.get_fmt_diagnostics() {
@@ -249,22 +415,65 @@ fn terminal_writer(log_event: Arc<Event<LogId, LogMsg, LogEventEntry>>, to_stder
         }
     }
 
-    let content_len = content_builder.content_len + content_builder.lines.len(); // + line-len for newline char
-    if to_stderr {
-        content_builder.write(BufWriter::with_capacity(
-            content_len,
-            std::io::stderr().lock(),
-        ));
-    } else {
-        content_builder.write(BufWriter::with_capacity(
-            content_len,
-            std::io::stdout().lock(),
-        ));
-    };
+    content_builder
 }
 
 const HEADER_PREFIX_LEN: usize = 6;
 
+/// Number of printed events remembered to indent event chains.
+const CHAIN_HISTORY_LEN: usize = 256;
+
+const CHAIN_INDENT: &str = "    ";
+
+thread_local! {
+    /// Chain depth of recently printed events.
+    static CHAIN_DEPTHS: RefCell<ChainDepths> = RefCell::new(ChainDepths::new());
+}
+
+/// Returns the depth of the event in the chain of printed events.
+fn chain_depth(entry_id: Uuid, entry: &LogEventEntry) -> usize {
+    CHAIN_DEPTHS
+        .try_with(|depths| depths.borrow_mut().depth_of(entry_id, entry))
+        .unwrap_or(0)
+}
+
+/// Chain depths of recently rendered events, to indent events below the event that caused them.
+///
+/// Only the depths of the latest 256 events are remembered, so memory does not grow with the number of rendered events.
+#[derive(Debug, Default)]
+pub struct ChainDepths {
+    depths: HashMap<Uuid, usize>,
+    /// Entry IDs in the order their depths were remembered.
+    order: VecDeque<Uuid>,
+}
+
+impl ChainDepths {
+    pub fn new() -> Self {
+        ChainDepths::default()
+    }
+
+    /// Returns the depth of the event in its event chain, and remembers it for events caused by this event.
+    ///
+    /// **Note:** Events whose parent was not rendered recently start a new chain.
+    pub fn depth_of(&mut self, entry_id: Uuid, entry: &LogEventEntry) -> usize {
+        let depth = entry
+            .get_parent()
+            .and_then(|parent| self.depths.get(parent.get_entry_id()))
+            .map(|parent_depth| parent_depth + 1)
+            .unwrap_or(0);
+
+        if self.order.len() >= CHAIN_HISTORY_LEN {
+            if let Some(oldest) = self.order.pop_front() {
+                self.depths.remove(&oldest);
+            }
+        }
+        self.order.push_back(entry_id);
+        self.depths.insert(entry_id, depth);
+
+        depth
+    }
+}
+
 /// Returns number of spaces to align printed levels.
 const fn get_level_space_alignment(level: LogLevel) -> usize {
     match level {
@@ -322,7 +531,10 @@ fn get_colored_lbot(level: LogLevel) -> String {
 fn get_event_string(id: &LogId, entry_id: &str) -> String {
     let module = id.get_module_path();
     let identifier = id.get_identifier();
-    format!("id='{module}::{identifier}', entry='{entry_id}'")
+    match id.get_code() {
+        Some(code) => format!("id='{module}::{identifier}', code='{code}', entry='{entry_id}'"),
+        None => format!("id='{module}::{identifier}', entry='{entry_id}'"),
+    }
 }
 
 struct ContentBuilder {
@@ -345,6 +557,14 @@ impl ContentBuilder {
         let _ = writer.flush();
     }
 
+    /// Removes terminal colors, and updates the content length accordingly.
+    fn strip_colors(&mut self) {
+        for line in &mut self.lines {
+            *line = strip_ansi(line);
+        }
+        self.content_len = self.lines.iter().map(|line| line.len()).sum();
+    }
+
     fn add_header(&mut self, level: LogLevel, msg: &str, colored_bar: &str) {
         let colored_level = get_colored_level(level);
         let space_offset = " ".repeat(get_level_space_alignment(level));
@@ -372,6 +592,19 @@ impl ContentBuilder {
         self.add_lines(prefix, get_addon_prefix_len(kind), content, colored_vbar);
     }
 
+    /// Indents all lines by the given chain depth.
+    fn indent(&mut self, depth: usize) {
+        if depth == 0 {
+            return;
+        }
+
+        let indent = CHAIN_INDENT.repeat(depth);
+        for line in self.lines.iter_mut() {
+            line.insert_str(0, &indent);
+            self.content_len += indent.len();
+        }
+    }
+
     fn add_line(&mut self, line: String) {
         self.content_len += line.len();
         self.lines.push(line);
@@ -7,6 +7,9 @@
 //! This allows to add subscribers to one or more [`log_id::LogId`]s to receive events set with those [`log_id::LogId`]s.
 //! For more information about subscription variants, checkout [`evident`].
 //!
+//! Independent [`Logger`](crate::logging::logger::Logger)s with their own filter and subscribers may be created,
+//! and set as target of the logging macros, e.g. `log!(target: logger, ...)`.
+//!
 //! **Usage:**
 //!
 //! ~~~
@@ -34,5 +37,13 @@
 pub use logid_core::*;
 pub use logid_derive::*;
 
+pub use logid_core::logging::{
+    ext::{LogOptionExt, LogResultExt},
+    scope::scope,
+};
+
+#[cfg(feature = "registry")]
+pub use logid_core::registry::explain;
+
 pub mod event_handler;
 pub mod macros;
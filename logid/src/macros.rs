@@ -1,118 +1,342 @@
-crate::evident::create_set_event_macro!(
-    id_type = logid::log_id::LogId,
-    msg_type = logid::logging::msg::LogMsg,
-    entry_type = logid::logging::event_entry::LogEventEntry,
-    interm_event_type = logid::logging::intermediary_event::IntermediaryLogEvent
-);
-
+/// Sets an event for the given log-id, and returns the intermediary event.
+///
+/// **Variants:**
+///
+/// - `set_event!(id)` ... Set an event for the given log-id without a message
+/// - `set_event!(id, msg)` ... Set an event for the given log-id with the given message
+/// - `set_event!(target: logger, id)` and `set_event!(target: logger, id, msg)` ... Set an event that is captured by the given [`Logger`](crate::logging::logger::Logger)
+///
+/// The event is captured once it is finalized or dropped.
 #[macro_export]
-macro_rules! log {
-    ($any:expr) => {
-        {
-            let s = $any.to_string();
-            $crate::set_event!(($any).into(), s).finalize()
-        }
+macro_rules! set_event {
+    (target: $logger:expr, $id:expr) => {
+        $crate::logging::intermediary_event::IntermediaryLogEvent::with_target(
+            ::std::option::Option::Some(
+                ::std::convert::AsRef::<$crate::logging::logger::Logger>::as_ref(&$logger),
+            ),
+            $id,
+            $crate::logging::msg::NO_MSG,
+            $crate::evident::this_origin!(),
+        )
     };
-    ($any:expr, $(add:$addon:expr),*) => {
-        {
-            let s = $any.to_string();
-            $crate::set_event!(($any).into(), s)$(.add_addon($addon))*.finalize()
-        }
+    (target: $logger:expr, $id:expr, $msg:expr) => {
+        $crate::logging::intermediary_event::IntermediaryLogEvent::with_target(
+            ::std::option::Option::Some(
+                ::std::convert::AsRef::<$crate::logging::logger::Logger>::as_ref(&$logger),
+            ),
+            $id,
+            ::std::option::Option::Some($msg),
+            $crate::evident::this_origin!(),
+        )
     };
-    ($any:expr, $msg:expr) => {
-        $crate::set_event!(($any).into(), $msg).finalize()
+    ($id:expr) => {
+        $crate::logging::intermediary_event::IntermediaryLogEvent::with_target(
+            ::std::option::Option::None,
+            $id,
+            $crate::logging::msg::NO_MSG,
+            $crate::evident::this_origin!(),
+        )
     };
-    ($any:expr, $msg:expr, $(add:$addon:expr),*) => {
-        $crate::set_event!(($any).into(), $msg)$(.add_addon($addon))*.finalize()
+    ($id:expr, $msg:expr) => {
+        $crate::logging::intermediary_event::IntermediaryLogEvent::with_target(
+            ::std::option::Option::None,
+            $id,
+            ::std::option::Option::Some($msg),
+            $crate::evident::this_origin!(),
+        )
     };
+}
 
-    // Note: It is not possible to check for "fmt" feature flag here
-    ($any:expr, $fmt_fn:expr, $fmt_data:expr) => {
-        $crate::set_event!(($any).into(), $crate::logging::msg::FmtMsg::new($fmt_fn, $fmt_data)).finalize()
+/// Logs the given log-id, and returns the finalized event.
+///
+/// The given value is evaluated once, and logged by reference, so it is not consumed.
+/// It must either convert into a [`LogId`](crate::log_id::LogId) by reference (e.g. if the conversion is derived),
+/// or implement `Clone` and convert into a [`LogId`](crate::log_id::LogId) by value.
+///
+/// A string literal as message is used as format string with optional arguments, like for [`format!`].
+///
+/// **Breaking change:** String literals are used as format strings even without arguments.
+/// Previously, a single string literal was used as message as is.
+/// Braces in literal messages must now be escaped as `{{` and `}}`, because names in braces refer to variables in scope.
+/// A message that is no literal (e.g. a `&str` constant) is still used as is.
+///
+/// The message and all addons are only created if the filter may allow the event for the log-id and the call site.
+///
+/// If the given value implements [`std::error::Error`], and an error of its source chain was logged before on the same thread,
+/// the event of the nearest logged source is set as parent of the new event.
+/// If the given value implements [`CaptureAddons`](crate::logging::capture::CaptureAddons), the captured addons are added to the event.
+///
+/// The event is captured by the [`LOGGER`](crate::logging::LOGGER), unless a [`Logger`](crate::logging::logger::Logger) is given with `target:` as first argument.
+/// The filter of the target decides if the event and its addons are created.
+///
+/// **Usage:**
+///
+/// ~~~
+/// use logid::{lazy_info, log, log_id::{LogId, LogLevel}, new_log_id};
+///
+/// const RETRY_FAILED: LogId = new_log_id!("RetryFailed", LogLevel::Warn);
+///
+/// let retries = 3;
+/// let host = "localhost";
+/// log!(RETRY_FAILED, "Failed after {} retries: {host}", retries, add: lazy_info!("Timeout was {}ms.", 500));
+///
+/// let logger = logid::logging::logger::Logger::new();
+/// log!(target: logger, RETRY_FAILED, "Failed to reach {host}.");
+///
+/// // Braces must be escaped, because the literal is a format string
+/// log!(RETRY_FAILED, "Retry policy {{ max: 3 }} exhausted.");
+/// ~~~
+#[macro_export]
+macro_rules! log {
+    (target: $logger:expr, $($arg:tt)+) => {
+        $crate::__log!(
+            ::std::option::Option::Some(::std::convert::AsRef::<$crate::logging::logger::Logger>::as_ref(&$logger));
+            $($arg)+
+        )
     };
-    ($any:expr, $fmt_fn:expr, $fmt_data:expr, $(add:$addon:expr),*) => {
-        $crate::set_event!(($any).into(), $crate::logging::msg::FmtMsg::new($fmt_fn, $fmt_data))$(.add_addon($addon))*.finalize()
+    ($($arg:tt)+) => {
+        $crate::__log!(::std::option::Option::None; $($arg)+)
     };
 }
 
+/// Logs like [`log!`] with the given target, which is an `Option<&Logger>`.
+#[doc(hidden)]
 #[macro_export]
-macro_rules! err {
-    ($error:expr) => {
+macro_rules! __log {
+    ($target:expr; $any:expr) => {
         {
-            $crate::log!($error);
-            Err($error)
+            let any = &$any;
+            $crate::__log_finalize!($target, any, any.to_string())
         }
     };
-    ($error:expr, $(add:$addon:expr),*) => {
+    ($target:expr; $any:expr, $(add:$addon:expr),*) => {
         {
-            $crate::log!($error, $(add:$addon),*);
-            Err($error)
+            let any = &$any;
+            $crate::__log_finalize!($target, any, any.to_string()$(, $addon)*)
         }
     };
-    ($error:expr, $msg:expr) => {
+
+    // Note: Must be matched before messages, because a literal is also an expression
+    ($target:expr; $any:expr, $fmt:literal $($rest:tt)*) => {
         {
-            $crate::log!($error, $msg);
-            Err($error)
+            let any = &$any;
+            $crate::__log_fmt!($target, any [$fmt] $($rest)*)
         }
     };
-    ($error:expr, $msg:expr, $(add:$addon:expr),*) => {
+
+    ($target:expr; $any:expr, $msg:expr) => {
         {
-            $crate::log!($error, $msg, $(add:$addon),*);
-            Err($error)
+            let any = &$any;
+            $crate::__log_finalize!($target, any, $msg)
+        }
+    };
+    ($target:expr; $any:expr, $msg:expr, $(add:$addon:expr),*) => {
+        {
+            let any = &$any;
+            $crate::__log_finalize!($target, any, $msg$(, $addon)*)
         }
     };
 
-    ($error:expr, $fmt_fn:expr, $fmt_data:expr) => {
+    // Note: Format strings are matched above, so the remaining two expressions must be a format function and its data
+    ($target:expr; $any:expr, $fmt_fn:expr, $fmt_data:expr) => {
         {
-            $crate::log!($error, $fmt_fn, $fmt_data);
-            Err($error)
+            let any = &$any;
+            $crate::__log_finalize!($target, any, $crate::logging::msg::FmtMsg::new($fmt_fn, $fmt_data))
         }
     };
-    ($error:expr, $fmt_fn:expr, $fmt_data:expr, $(add:$addon:expr),*) => {
+    ($target:expr; $any:expr, $fmt_fn:expr, $fmt_data:expr, $(add:$addon:expr),*) => {
         {
-            $crate::log!($error, $fmt_fn, $fmt_data, $(add:$addon),*);
-            Err($error)
+            let any = &$any;
+            $crate::__log_finalize!($target, any, $crate::logging::msg::FmtMsg::new($fmt_fn, $fmt_data)$(, $addon)*)
         }
     };
 }
 
+/// Collects format arguments until the first addon, and logs the event with the formatted message.
+#[doc(hidden)]
 #[macro_export]
-macro_rules! pipe {
-    ($any:expr) => {
-        {
-            $crate::log!($any);
-            $any
-        }
+macro_rules! __log_fmt {
+    (@finish $target:expr, $any:ident [$($fmt:tt)*] $(add:$addon:expr),+) => {
+        $crate::__log_finalize!($target, $any, ::std::format!($($fmt)*)$(, $addon)+)
+    };
+    ($target:expr, $any:ident [$($fmt:tt)*] , add: $($addons:tt)+) => {
+        $crate::__log_fmt!(@finish $target, $any [$($fmt)*] add: $($addons)+)
+    };
+    ($target:expr, $any:ident [$($fmt:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__log_fmt!($target, $any [$($fmt)* $next] $($rest)*)
+    };
+    ($target:expr, $any:ident [$($fmt:tt)*]) => {
+        $crate::__log_finalize!($target, $any, ::std::format!($($fmt)*))
     };
-    ($any:expr, $(add:$addon:expr),*) => {
+}
+
+/// Creates the event for the referenced value for the given target logger, links it to the event of its source error, adds captured and given addons, and finalizes the event.
+///
+/// The message, chain link, captured and given addons are only evaluated if the filter may allow the event.
+///
+/// **Note:** `$any` must be a reference without side effects, because it is used more than once.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_finalize {
+    ($target:expr, $any:expr, $msg:expr $(, $addon:expr)*) => {
         {
-            $crate::log!($any, $(add:$addon),*);
-            $any
+            #[allow(unused_imports)]
+            use $crate::{
+                evident::event::intermediary::IntermediaryEvent as _,
+                log_id::{__ViaClone as _, __ViaRef as _},
+                logging::{
+                    capture::{__ViaCaptureAddons as _, __ViaNoCapture as _},
+                    chain::{__ViaAny as _, __ViaError as _},
+                },
+            };
+
+            let log_id = (&$crate::log_id::__ToLogId($any)).__log_id();
+            let origin = $crate::evident::this_origin!();
+            let target: ::std::option::Option<&$crate::logging::logger::Logger> = $target;
+            let allowed = $crate::logging::filter::allow_event_for(target, log_id, &origin);
+
+            let (msg, link): (Option<$crate::logging::msg::LogMsg>, _) = if allowed {
+                (Some(($msg).into()), (&$crate::logging::chain::__ErrorChain($any)).__chain_link())
+            } else {
+                (None, $crate::logging::chain::__ChainLink::default())
+            };
+            let mut event = link.link($crate::logging::intermediary_event::IntermediaryLogEvent::with_target(target, log_id, msg, origin));
+
+            if allowed {
+                for addon in (&$crate::logging::capture::__Capture($any)).__capture_addons() {
+                    event = event.add_addon(addon);
+                }
+                $(event = $crate::logging::intermediary_event::EventAddon::add_to($addon, event);)*
+            }
+            event.finalize()
         }
     };
-    ($any:expr, $msg:expr) => {
+}
+
+/// Creates a lazy info addon with a message formatted like [`format!`].
+///
+/// The message is only formatted if the filter allows info addons for the event the addon is added to.
+#[macro_export]
+macro_rules! lazy_info {
+    ($($arg:tt)+) => {
+        $crate::logging::intermediary_event::LazyAddon::new(
+            $crate::logging::filter::AddonFilter::Infos,
+            || $crate::logging::event_entry::AddonKind::Info(::std::format!($($arg)+)),
+        )
+    };
+}
+
+/// Creates a lazy debug addon with a message formatted like [`format!`].
+///
+/// The message is only formatted if the filter allows debug addons for the event the addon is added to.
+#[macro_export]
+macro_rules! lazy_debug {
+    ($($arg:tt)+) => {
+        $crate::logging::intermediary_event::LazyAddon::new(
+            $crate::logging::filter::AddonFilter::Debugs,
+            || $crate::logging::event_entry::AddonKind::Debug(::std::format!($($arg)+)),
+        )
+    };
+}
+
+/// Creates a lazy trace addon with a message formatted like [`format!`].
+///
+/// The message is only formatted if the filter allows trace addons for the event the addon is added to.
+#[macro_export]
+macro_rules! lazy_trace {
+    ($($arg:tt)+) => {
+        $crate::logging::intermediary_event::LazyAddon::new(
+            $crate::logging::filter::AddonFilter::Traces,
+            || $crate::logging::event_entry::AddonKind::Trace(::std::format!($($arg)+)),
+        )
+    };
+}
+
+/// Logs the given error like [`log!`], and returns it wrapped in `Err`.
+///
+/// The error is evaluated once, and moved into `Err` after it is logged.
+#[macro_export]
+macro_rules! err {
+    (target: $logger:expr, $error:expr $(, $($arg:tt)+)?) => {
         {
-            $crate::log!($any, $msg);
-            $any
+            let error = $error;
+            $crate::log!(target: $logger, error $(, $($arg)+)?);
+            Err(error)
         }
     };
-    ($any:expr, $msg:expr, $(add:$addon:expr),*) => {
+    ($error:expr $(, $($arg:tt)+)?) => {
         {
-            $crate::log!($any, $msg, $(add:$addon),*);
-            $any
+            let error = $error;
+            $crate::log!(error $(, $($arg)+)?);
+            Err(error)
         }
     };
+}
 
-    ($any:expr, $fmt_fn:expr, $fmt_data:expr) => {
+/// Logs the given value like [`log!`], and returns it.
+///
+/// The value is evaluated once, and returned after it is logged.
+#[macro_export]
+macro_rules! pipe {
+    (target: $logger:expr, $any:expr $(, $($arg:tt)+)?) => {
         {
-            $crate::log!($any, $fmt_fn, $fmt_data);
-            $any
+            let any = $any;
+            $crate::log!(target: $logger, any $(, $($arg)+)?);
+            any
         }
     };
-    ($any:expr, $fmt_fn:expr, $fmt_data:expr, $(add:$addon:expr),*) => {
+    ($any:expr $(, $($arg:tt)+)?) => {
         {
-            $crate::log!($any, $fmt_fn, $fmt_data, $(add:$addon),*);
-            $any
+            let any = $any;
+            $crate::log!(any $(, $($arg)+)?);
+            any
         }
     };
 }
+
+/// Creates a lazy payload addon from the given [`serde_json::Value`](crate::serde_json::Value) expression.
+///
+/// The expression is only evaluated if the filter allows payload addons for the event the addon is added to.
+#[cfg(feature = "payloads")]
+#[macro_export]
+macro_rules! payload {
+    ($value:expr) => {
+        $crate::logging::intermediary_event::LazyAddon::new(
+            $crate::logging::filter::AddonFilter::Payloads,
+            || $crate::logging::event_entry::AddonKind::Payload($value),
+        )
+    };
+}
+
+/// Starts a [`Span`](crate::logging::span::Span) for the given log-id.
+///
+/// The span event is finalized once the returned guard is dropped, or `finish()` is called on it.
+/// It contains the elapsed duration, start and end timestamps, and all events created on the same thread while the span was active.
+///
+/// The span event is captured by the [`LOGGER`](crate::logging::LOGGER), unless a [`Logger`](crate::logging::logger::Logger) is given with `target:` as first argument.
+#[macro_export]
+macro_rules! span {
+    (target: $logger:expr, $any:expr) => {{
+        let any = &$any;
+        $crate::span!(@start ::std::option::Option::Some(::std::convert::AsRef::<$crate::logging::logger::Logger>::as_ref(&$logger)), any, any.to_string())
+    }};
+    (target: $logger:expr, $any:expr, $msg:expr) => {{
+        let any = &$any;
+        $crate::span!(@start ::std::option::Option::Some(::std::convert::AsRef::<$crate::logging::logger::Logger>::as_ref(&$logger)), any, $msg)
+    }};
+    ($any:expr) => {{
+        let any = &$any;
+        $crate::span!(@start ::std::option::Option::None, any, any.to_string())
+    }};
+    ($any:expr, $msg:expr) => {{
+        let any = &$any;
+        $crate::span!(@start ::std::option::Option::None, any, $msg)
+    }};
+    (@start $target:expr, $any:ident, $msg:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::log_id::{__ViaClone as _, __ViaRef as _};
+
+        let log_id = (&$crate::log_id::__ToLogId($any)).__log_id();
+        $crate::logging::span::Span::with_target($target, log_id, Some($msg), $crate::evident::this_origin!())
+    }};
+}
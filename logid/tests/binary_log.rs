@@ -0,0 +1,217 @@
+#![cfg(feature = "binary")]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use logid::{
+    event_handler::{
+        binary::{BinaryError, BinaryReader, FORMAT_VERSION, MAGIC},
+        render::RenderOptions,
+    },
+    field, log,
+    log_id::{LogId, LogLevel},
+    logging::{event_entry::AddonKind, field::FieldValue, logger::Logger},
+    new_log_id,
+};
+
+mod common;
+
+use common::{binary::binary_setup, wait_for};
+
+const REQUEST_FAILED: LogId = new_log_id!("RequestFailed", LogLevel::Error);
+const RETRYING: LogId = new_log_id!("Retrying", LogLevel::Warn).with_code("W0007");
+
+fn log_request_failure(logger: &Logger) {
+    let cause = log!(
+        target: logger,
+        REQUEST_FAILED,
+        "Request to '{}' failed.",
+        "/users",
+        add: AddonKind::Info("Status: 503".to_string()),
+        add: field!("attempt", 1),
+        add: field!("offset", -12),
+        add: field!("ratio", 0.5),
+        add: field!("cached", false),
+        add: field!("path", "/users"),
+        add: field!("elapsed", Duration::from_millis(1500)),
+        add: field!("deadline", UNIX_EPOCH + Duration::new(1_700_000_000, 42)),
+        add: field!("checksum", vec![0xde_u8, 0xad])
+    );
+    log!(
+        target: logger,
+        RETRYING,
+        "Retrying request.",
+        add: AddonKind::Parent(cause)
+    );
+}
+
+#[test]
+fn events_roundtrip_through_binary_log() {
+    let (logger, buf, store, _handler) = binary_setup();
+
+    log_request_failure(&logger);
+    wait_for(&store, 2);
+
+    let written = store.query().events();
+    let bytes = buf.bytes();
+    let mut reader = BinaryReader::new(bytes.as_slice()).unwrap();
+    assert_eq!(reader.version(), FORMAT_VERSION);
+    let read: Vec<_> = reader.by_ref().map(Result::unwrap).collect();
+    assert!(!reader.is_truncated());
+    assert_eq!(read.len(), 2);
+
+    for (written, read) in written.iter().zip(&read) {
+        assert_eq!(read.event.get_event_id(), written.get_event_id());
+        assert_eq!(read.event.get_entry_id(), written.get_entry_id());
+        assert_eq!(read.event.get_origin(), written.get_origin());
+        assert_eq!(
+            read.event.get_msg().map(|msg| msg.to_string()),
+            written.get_msg().map(|msg| msg.to_string())
+        );
+        assert_eq!(&read.timestamp, written.get_timestamp());
+        assert_eq!(read.thread_name.as_deref(), written.get_thread_name());
+        assert_eq!(
+            read.event.get_entry().get_infos(),
+            written.get_entry().get_infos()
+        );
+        assert_eq!(
+            read.event.get_entry().get_fields(),
+            written.get_entry().get_fields(),
+            "Field values changed in binary log."
+        );
+    }
+
+    assert_eq!(read[1].event.get_event_id().get_code(), Some("W0007"));
+    let parent = read[1].event.get_entry().get_parent().unwrap();
+    assert_eq!(parent.get_event_id(), &REQUEST_FAILED);
+    assert_eq!(parent.get_entry_id(), &read[0].event.get_entry_id());
+    assert_eq!(
+        read[0]
+            .event
+            .get_entry()
+            .get_field("deadline")
+            .unwrap()
+            .get_value(),
+        &FieldValue::Timestamp(UNIX_EPOCH + Duration::new(1_700_000_000, 42))
+    );
+}
+
+#[test]
+fn log_ids_and_strings_are_written_once() {
+    let (logger, buf, store, _handler) = binary_setup();
+
+    // Note: Messages are set, because the default message contains the identifier
+    log!(target: logger, RETRYING, "Request retried.");
+    wait_for(&store, 1);
+    let first_len = buf.bytes().len();
+    log!(target: logger, RETRYING, "Request retried.");
+    wait_for(&store, 2);
+    let bytes = buf.bytes();
+
+    assert!(
+        bytes.len() - first_len < first_len - MAGIC.len(),
+        "Second event of the same log-id is not smaller than the first."
+    );
+    let identifier_cnt = bytes
+        .windows("Retrying".len())
+        .filter(|window| *window == b"Retrying")
+        .count();
+    assert_eq!(identifier_cnt, 1, "Identifier was written more than once.");
+
+    let reader = BinaryReader::new(bytes.as_slice()).unwrap();
+    let ids: Vec<LogId> = reader
+        .map(|read| *read.unwrap().event.get_event_id())
+        .collect();
+    assert_eq!(ids, vec![RETRYING, RETRYING]);
+}
+
+#[test]
+fn truncated_log_keeps_complete_events() {
+    let (logger, buf, store, _handler) = binary_setup();
+
+    log_request_failure(&logger);
+    wait_for(&store, 2);
+    let bytes = buf.bytes();
+
+    // Note: Every possible cut off must keep all complete events before it
+    let mut prev_cnt = 0;
+    for len in MAGIC.len() + 1..bytes.len() {
+        let cnt = BinaryReader::new(&bytes[..len])
+            .unwrap()
+            .map(Result::unwrap)
+            .count();
+
+        assert!(cnt < 2, "Cut off event was read.");
+        assert!(cnt >= prev_cnt, "Complete event was lost.");
+        prev_cnt = cnt;
+    }
+    assert_eq!(
+        prev_cnt, 1,
+        "First event not read before the cut off event."
+    );
+
+    let mut reader = BinaryReader::new(&bytes[..bytes.len() - 1]).unwrap();
+    assert_eq!(reader.by_ref().count(), 1);
+    assert!(reader.is_truncated());
+}
+
+#[test]
+fn invalid_header_is_rejected() {
+    assert!(matches!(
+        BinaryReader::new(&b"{\"level\":\"warn\"}"[..]),
+        Err(BinaryError::InvalidHeader)
+    ));
+    assert!(matches!(
+        BinaryReader::new(&MAGIC[..4]),
+        Err(BinaryError::InvalidHeader)
+    ));
+
+    let mut newer = MAGIC.to_vec();
+    newer.push(FORMAT_VERSION + 1);
+    assert!(matches!(
+        BinaryReader::new(newer.as_slice()),
+        Err(BinaryError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1
+    ));
+}
+
+#[test]
+fn convert_binary_log_to_json_and_terminal() {
+    let (logger, buf, store, _handler) = binary_setup();
+
+    let before = SystemTime::now();
+    log_request_failure(&logger);
+    wait_for(&store, 2);
+    let bytes = buf.bytes();
+
+    let mut json = Vec::new();
+    let written = BinaryReader::new(bytes.as_slice())
+        .unwrap()
+        .write_json_lines(&mut json)
+        .unwrap();
+    assert_eq!(written, 2);
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(json)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines[0]["id"], format!("{}::RequestFailed", module_path!()));
+    assert_eq!(lines[0]["msg"], "Request to '/users' failed.");
+    assert_eq!(lines[0]["fields"]["attempt"], 1);
+    assert_eq!(lines[1]["code"], "W0007");
+    assert_eq!(lines[1]["parent"]["entry"], lines[0]["entry"]);
+    let timestamp = lines[0]["timestamp"].as_f64().unwrap();
+    assert!(
+        timestamp >= before.duration_since(UNIX_EPOCH).unwrap().as_secs_f64() - 1.0,
+        "Timestamp of the log was not restored."
+    );
+
+    let mut terminal = Vec::new();
+    BinaryReader::new(bytes.as_slice())
+        .unwrap()
+        .write_terminal(&mut terminal, &RenderOptions::deterministic())
+        .unwrap();
+    let terminal = String::from_utf8(terminal).unwrap();
+    assert!(terminal.contains("Request to '/users' failed."));
+    assert!(terminal.contains("Status: 503"));
+    assert!(terminal.contains("Retrying request."));
+}
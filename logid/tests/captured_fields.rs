@@ -0,0 +1,141 @@
+use logid::{log, ErrLogId};
+use logid_core::logging::LOGGER;
+use thiserror::Error;
+
+#[derive(Debug, Clone, ErrLogId, Error)]
+#[log_id(capture = "infos")]
+enum FileError {
+    #[error("Could not read file.")]
+    Read {
+        path: String,
+        #[log_id(skip)]
+        token: String,
+    },
+    #[error("Invalid line.")]
+    InvalidLine(usize, #[log_id(skip)] String),
+    #[log_id(capture = "off")]
+    #[error("Not captured.")]
+    NotCaptured { path: String },
+}
+
+#[test]
+fn named_fields_captured_as_infos() {
+    let recv = LOGGER
+        .subscribe(
+            FileError::Read {
+                path: String::new(),
+                token: String::new(),
+            }
+            .into(),
+        )
+        .unwrap();
+
+    log!(FileError::Read {
+        path: "/etc/app.toml".to_string(),
+        token: "secret".to_string(),
+    });
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        event.get_entry().get_infos(),
+        &vec!["path: \"/etc/app.toml\"".to_string()],
+        "Named field not captured, or skipped field captured"
+    );
+}
+
+#[test]
+fn unnamed_fields_captured_as_infos() {
+    let recv = LOGGER
+        .subscribe(FileError::InvalidLine(0, String::new()).into())
+        .unwrap();
+
+    log!(FileError::InvalidLine(42, "secret".to_string()));
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        event.get_entry().get_infos(),
+        &vec!["0: 42".to_string()],
+        "Unnamed field not captured, or skipped field captured"
+    );
+}
+
+#[test]
+fn variant_capture_turned_off() {
+    let recv = LOGGER
+        .subscribe(
+            FileError::NotCaptured {
+                path: String::new(),
+            }
+            .into(),
+        )
+        .unwrap();
+
+    log!(FileError::NotCaptured {
+        path: "/etc/app.toml".to_string(),
+    });
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert!(
+        event.get_entry().get_infos().is_empty(),
+        "Fields captured for variant with capture turned off"
+    );
+}
+
+#[cfg(feature = "payloads")]
+mod payload_tests {
+    use logid::{log, LogId};
+    use logid_core::logging::LOGGER;
+
+    #[derive(Debug, Clone, LogId)]
+    #[log_id(level = "warn", capture = "payload")]
+    enum RequestWarning {
+        Slow { route: String, millis: u64 },
+    }
+
+    impl std::fmt::Display for RequestWarning {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Slow request.")
+        }
+    }
+
+    #[test]
+    fn fields_captured_as_payload() {
+        let recv = LOGGER
+            .subscribe(
+                RequestWarning::Slow {
+                    route: String::new(),
+                    millis: 0,
+                }
+                .into(),
+            )
+            .unwrap();
+
+        log!(RequestWarning::Slow {
+            route: "/users".to_string(),
+            millis: 1200,
+        });
+
+        let event = recv
+            .get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+
+        assert_eq!(
+            event.get_entry().get_payloads(),
+            &vec![serde_json::json!({ "route": "/users", "millis": 1200 })],
+            "Fields not captured as payload object"
+        );
+    }
+}
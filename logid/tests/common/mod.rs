@@ -0,0 +1,140 @@
+//! Fixtures shared by the integration tests of handlers.
+//!
+//! **Note:** Every test binary only uses some of the fixtures.
+#![allow(dead_code)]
+
+use std::{
+    sync::mpsc::{channel, Receiver},
+    time::{Duration, Instant},
+};
+
+use logid::{
+    event_handler::{
+        builder::LogEventHandlerBuilder,
+        flight_recorder::{FlightRecorder, RecorderCapacity},
+        store::EventStore,
+        LogEventHandler,
+    },
+    log_id::LogId,
+    logging::{filter::FilterConfig, logger::Logger},
+};
+
+/// Time events are waited for before a test fails.
+pub const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Waits until the given condition holds, and panics if it does not hold within [`TIMEOUT`].
+pub fn wait_until(what: &str, condition: impl Fn() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        if start.elapsed() > TIMEOUT {
+            panic!("Timed out waiting for {what}.");
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Waits until the given store contains at least the given number of events.
+pub fn wait_for(store: &EventStore, len: usize) {
+    wait_until(&format!("{len} stored events"), || store.len() >= len);
+}
+
+/// Creates a logger allowing all events, and a store receiving its events.
+pub fn store_setup() -> (Logger, EventStore, LogEventHandler) {
+    let logger = Logger::builder()
+        .filter(FilterConfig::new("trace(all)"))
+        .build();
+    let store = EventStore::new();
+    let handler = LogEventHandlerBuilder::with_logger(&logger)
+        .add_handler(store.handler())
+        .all_log_events()
+        .build()
+        .unwrap();
+
+    (logger, store, handler)
+}
+
+/// Creates a logger only emitting errors, and a recorder recording all its events.
+///
+/// The returned receiver gets the IDs of dumped events.
+pub fn recorder_setup(capacity: RecorderCapacity) -> (Logger, FlightRecorder, Receiver<LogId>) {
+    let logger = Logger::builder().filter(FilterConfig::new("error")).build();
+    let (sender, receiver) = channel();
+    let recorder = FlightRecorder::builder(capacity)
+        .target(&logger)
+        .record_filter(FilterConfig::new("trace"))
+        .sink(move |event| {
+            let _ = sender.send(*event.get_event_id());
+        })
+        .build()
+        .unwrap();
+
+    (logger, recorder, receiver)
+}
+
+/// Receives the given number of IDs, and panics if they are not received within [`TIMEOUT`].
+pub fn receive(receiver: &Receiver<LogId>, len: usize) -> Vec<LogId> {
+    (0..len)
+        .map(|nr| {
+            receiver
+                .recv_timeout(TIMEOUT)
+                .unwrap_or_else(|_| panic!("Timed out waiting for ID {} of {len}.", nr + 1))
+        })
+        .collect()
+}
+
+#[cfg(feature = "binary")]
+pub mod binary {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use logid::{
+        event_handler::{
+            binary::BinaryWriter, builder::LogEventHandlerBuilder, store::EventStore,
+            LogEventHandler,
+        },
+        logging::{filter::FilterConfig, logger::Logger},
+    };
+
+    /// Writer that keeps written bytes accessible after it was moved into a [`BinaryWriter`].
+    #[derive(Clone, Default)]
+    pub struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuf {
+        pub fn bytes(&self) -> Vec<u8> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Creates a logger allowing all events, a binary writer writing its events to the returned buffer,
+    /// and a store receiving its events after the writer.
+    pub fn binary_setup() -> (Logger, SharedBuf, EventStore, LogEventHandler) {
+        let logger = Logger::builder()
+            .filter(FilterConfig::new("trace(all)"))
+            .build();
+        let buf = SharedBuf::default();
+        let writer = BinaryWriter::new(buf.clone()).unwrap();
+        let store = EventStore::new();
+        // Note: The store receives events after the writer, so stored events are already written
+        let handler = LogEventHandlerBuilder::with_logger(&logger)
+            .add_handler(writer.handler())
+            .add_handler(store.handler())
+            .all_log_events()
+            .build()
+            .unwrap();
+
+        (logger, buf, store, handler)
+    }
+}
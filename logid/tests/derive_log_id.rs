@@ -1,5 +1,5 @@
-use logid::log_id::{LogId, LogLevel};
-use logid::{DbgLogId, ErrLogId, FromLogId, InfoLogId, TraceLogId, WarnLogId};
+use logid::log_id::{FromLogIdError, LogId, LogLevel};
+use logid::{DbgLogId, ErrLogId, FromLogId, InfoLogId, LogId, TraceLogId, WarnLogId};
 
 #[derive(PartialEq, Eq, Debug, Default, ErrLogId, FromLogId)]
 enum LogIdEnum {
@@ -53,17 +53,17 @@ fn enum_into_log_id() {
 #[test]
 fn enum_as_err_log_id() {
     let first_id: LogId = LogIdEnum::First.into();
-    let first_enum: LogIdEnum = first_id.into();
+    let first_enum: LogIdEnum = first_id.try_into().unwrap();
 
     assert_eq!(
-        LogIdEnum::from(first_id),
-        LogIdEnum::First,
-        "Conversion back to enum using `from()` failed.",
+        LogIdEnum::try_from(first_id),
+        Ok(LogIdEnum::First),
+        "Conversion back to enum using `try_from()` failed.",
     );
     assert_eq!(
         first_enum,
         LogIdEnum::First,
-        "Conversion back to enum using `into()` failed.",
+        "Conversion back to enum using `try_into()` failed.",
     );
     assert_eq!(
         first_id.get_log_level(),
@@ -250,3 +250,227 @@ fn complex_enum_log_id_struct_variant_mult_fields() {
         "LogLevel::Error was not set using ErrLogId derive macro.",
     );
 }
+
+#[derive(PartialEq, Eq, Debug, Default, LogId, FromLogId)]
+#[log_id(level = "warn")]
+enum MixedLogId {
+    #[default]
+    Recoverable,
+    #[log_id(level = "error")]
+    Fatal,
+    #[log_id(name = "E1042")]
+    Renamed,
+    #[log_id(level = "info", name = "I0001")]
+    RenamedInfo,
+}
+
+#[test]
+fn enum_with_variant_levels() {
+    let recoverable_id: LogId = MixedLogId::Recoverable.into();
+    let fatal_id: LogId = MixedLogId::Fatal.into();
+
+    assert_eq!(
+        recoverable_id.get_log_level(),
+        LogLevel::Warn,
+        "Enum level not set for variant without level attribute.",
+    );
+    assert_eq!(
+        fatal_id.get_log_level(),
+        LogLevel::Error,
+        "Variant level did not overwrite enum level.",
+    );
+    assert_eq!(
+        fatal_id.get_identifier(),
+        "MixedLogId::Fatal",
+        "Derive set wrong identifier name for variant with level attribute.",
+    );
+}
+
+#[test]
+fn enum_with_custom_variant_names() {
+    let renamed_id: LogId = MixedLogId::Renamed.into();
+    let renamed_info_id: LogId = MixedLogId::RenamedInfo.into();
+
+    assert_eq!(
+        renamed_id.get_identifier(),
+        "E1042",
+        "Custom identifier name not set.",
+    );
+    assert_eq!(
+        renamed_id.get_log_level(),
+        LogLevel::Warn,
+        "Enum level not set for renamed variant.",
+    );
+    assert_eq!(
+        renamed_info_id.get_identifier(),
+        "I0001",
+        "Custom identifier name not set for variant with level attribute.",
+    );
+    assert_eq!(
+        renamed_info_id.get_log_level(),
+        LogLevel::Info,
+        "Variant level not set for renamed variant.",
+    );
+    assert_eq!(
+        MixedLogId::try_from(renamed_id),
+        Ok(MixedLogId::Renamed),
+        "Conversion back to enum failed for custom identifier name.",
+    );
+}
+
+#[derive(LogId)]
+#[log_id(level = "debug", name = "custom_struct_id")]
+struct CustomStructId;
+
+#[test]
+fn struct_with_level_and_name() {
+    let struct_id: LogId = CustomStructId.into();
+
+    assert_eq!(
+        struct_id.get_identifier(),
+        "custom_struct_id",
+        "Custom identifier name not set for struct.",
+    );
+    assert_eq!(
+        struct_id.get_log_level(),
+        LogLevel::Debug,
+        "Level attribute not set for struct.",
+    );
+}
+
+#[derive(LogId)]
+#[log_id(level = "error")]
+enum CodedLogId {
+    #[log_id(code = "E0042")]
+    Coded,
+    Uncoded,
+}
+
+#[derive(LogId)]
+#[log_id(level = "warn", code = "W0001")]
+struct CodedStructId;
+
+#[test]
+fn codes_set_via_derive() {
+    let coded_id: LogId = CodedLogId::Coded.into();
+    let uncoded_id: LogId = CodedLogId::Uncoded.into();
+    let struct_id: LogId = CodedStructId.into();
+
+    assert_eq!(coded_id.get_code(), Some("E0042"), "Variant code not set.");
+    assert_eq!(
+        coded_id.get_identifier(),
+        "CodedLogId::Coded",
+        "Code changed the identifier.",
+    );
+    assert_eq!(uncoded_id.get_code(), None, "Code set for uncoded variant.");
+    assert_eq!(struct_id.get_code(), Some("W0001"), "Struct code not set.");
+}
+
+#[derive(PartialEq, Eq, Debug, ErrLogId, FromLogId)]
+enum DataLogId {
+    Unit,
+    Tuple(String, u16),
+    Named {
+        path: String,
+        #[log_id(default = "42")]
+        retries: u8,
+    },
+}
+
+#[test]
+fn enum_with_data_variants_from_log_id() {
+    let tuple_id: LogId = DataLogId::Tuple("data".to_string(), 3).into();
+    let named_id: LogId = DataLogId::Named {
+        path: "/tmp".to_string(),
+        retries: 1,
+    }
+    .into();
+
+    assert_eq!(
+        DataLogId::try_from(tuple_id),
+        Ok(DataLogId::Tuple(String::new(), 0)),
+        "Tuple variant not created with default values.",
+    );
+    assert_eq!(
+        DataLogId::try_from(named_id),
+        Ok(DataLogId::Named {
+            path: String::new(),
+            retries: 42
+        }),
+        "Named variant not created with default and custom default values.",
+    );
+}
+
+#[test]
+fn unknown_log_id_not_converted() {
+    let foreign_id = logid::log_id::STOP_LOGGING;
+    let unknown_id = logid::new_log_id!("unknown", LogLevel::Error);
+
+    assert_eq!(
+        DataLogId::try_from(foreign_id),
+        Err(FromLogIdError::ForeignModule(foreign_id)),
+        "Log-id of other module converted.",
+    );
+    assert_eq!(
+        DataLogId::try_from(unknown_id),
+        Err(FromLogIdError::UnknownIdentifier(unknown_id)),
+        "Log-id with unknown identifier converted.",
+    );
+}
+
+#[derive(Debug, LogId)]
+#[log_id(id = "backend_error", level = "error", capture = "infos")]
+struct BackendError<B: std::fmt::Debug> {
+    backend: B,
+}
+
+#[derive(Debug, PartialEq, Eq, ErrLogId, FromLogId)]
+enum GenericLogId<T, E>
+where
+    E: std::fmt::Debug,
+{
+    Value(T),
+    #[log_id(id = "generic_failure")]
+    Failure {
+        error: E,
+    },
+}
+
+#[test]
+fn generic_struct_with_custom_id() {
+    let struct_id: LogId = BackendError {
+        backend: "postgres",
+    }
+    .into();
+
+    assert_eq!(
+        struct_id.get_identifier(),
+        "backend_error",
+        "Custom id not set for generic struct.",
+    );
+    assert_eq!(
+        struct_id.get_log_level(),
+        LogLevel::Error,
+        "Level not set for generic struct.",
+    );
+}
+
+#[test]
+fn generic_enum_to_and_from_log_id() {
+    let value_id: LogId = GenericLogId::<u8, String>::Value(1).into();
+    let failure_id: LogId = GenericLogId::<u8, String>::Failure {
+        error: "timeout".to_string(),
+    }
+    .into();
+
+    assert_eq!(
+        failure_id.get_identifier(),
+        "generic_failure",
+        "Custom id not set for variant of generic enum.",
+    );
+    assert_eq!(
+        GenericLogId::<u8, String>::try_from(value_id),
+        Ok(GenericLogId::Value(0)),
+        "Conversion back to generic enum failed.",
+    );
+}
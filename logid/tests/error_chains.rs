@@ -0,0 +1,143 @@
+use logid::{log, ErrLogId};
+use logid_core::{
+    log_id::{LogId, LogLevel},
+    logging::{event_entry::AddonKind, filter::FilterConfig, logger::Logger, LOGGER},
+    new_log_id,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, ErrLogId, Error)]
+enum StorageError {
+    #[error("Disk is full.")]
+    DiskFull,
+}
+
+#[derive(Debug, Clone, ErrLogId, Error)]
+enum ServiceError {
+    #[error("Could not save user.")]
+    SaveFailed(#[source] StorageError),
+}
+
+#[test]
+fn logged_source_error_is_parent() {
+    let recv = LOGGER
+        .subscribe(ServiceError::SaveFailed(StorageError::DiskFull).into())
+        .unwrap();
+
+    let storage_event = log!(StorageError::DiskFull);
+    let service_event = log!(ServiceError::SaveFailed(StorageError::DiskFull));
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        event.get_entry_id(),
+        service_event.entry_id,
+        "Received wrong service event"
+    );
+    assert_eq!(
+        event.get_entry().get_parent(),
+        Some(&storage_event),
+        "Logged source error not set as parent"
+    );
+}
+
+#[test]
+fn filtered_source_error_is_no_parent() {
+    let logger = Logger::builder().filter(FilterConfig::new("off")).build();
+    let recv = logger
+        .subscribe(ServiceError::SaveFailed(StorageError::DiskFull).into())
+        .unwrap();
+
+    log!(target: logger, StorageError::DiskFull);
+    logger.set_filter(FilterConfig::new("error")).unwrap();
+    log!(target: logger, ServiceError::SaveFailed(StorageError::DiskFull));
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        event.get_entry().get_parent(),
+        None,
+        "Filtered out source error set as parent"
+    );
+}
+
+static FORMATTED: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Debug, Clone)]
+struct CountingDisplay;
+
+impl std::fmt::Display for CountingDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        FORMATTED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        write!(f, "Counted.")
+    }
+}
+
+#[derive(Debug, Clone, ErrLogId, Error)]
+enum CountingError {
+    #[error("{0}")]
+    Counted(CountingDisplay),
+}
+
+#[test]
+fn filtered_error_is_not_formatted() {
+    let logger = Logger::builder().filter(FilterConfig::new("off")).build();
+
+    log!(target: logger, CountingError::Counted(CountingDisplay));
+
+    assert_eq!(
+        FORMATTED.load(std::sync::atomic::Ordering::Relaxed),
+        0,
+        "Filtered out error was formatted"
+    );
+}
+
+#[test]
+fn explicit_parent_addon() {
+    let parent_id: LogId = new_log_id!("parent_event", LogLevel::Warn);
+    let child_id: LogId = new_log_id!("child_event", LogLevel::Warn);
+    let recv = LOGGER.subscribe(child_id).unwrap();
+
+    let parent = log!(parent_id, "Parent.");
+    log!(child_id, "Child.", add: AddonKind::Parent(parent.clone()));
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        event.get_entry().get_parent(),
+        Some(&parent),
+        "Parent addon not set as parent"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn parent_as_json_member() {
+    let parent_id: LogId = new_log_id!("json_parent_event", LogLevel::Warn);
+    let child_id: LogId = new_log_id!("json_child_event", LogLevel::Warn);
+    let recv = LOGGER.subscribe(child_id).unwrap();
+
+    let parent = log!(parent_id, "Parent.");
+    log!(child_id, "Child.", add: AddonKind::Parent(parent.clone()));
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    let json = logid::event_handler::json::event_to_json(&event);
+    assert_eq!(
+        json["parent"]["entry"],
+        serde_json::Value::String(parent.entry_id.to_string()),
+        "Parent entry ID not set in JSON"
+    );
+}
@@ -0,0 +1,132 @@
+use std::time::{Duration, SystemTime};
+
+use logid::{
+    event_handler::{builder::LogEventHandlerBuilder, store::EventStore},
+    log,
+    log_id::{LogId, LogLevel},
+    logging::{event_entry::AddonKind, filter::FilterConfig, logger::Logger},
+    new_log_id,
+};
+
+mod common;
+
+use common::{store_setup, wait_for, wait_until};
+
+const USER_CREATED: LogId = new_log_id!("UserCreated", LogLevel::Info);
+const SLOW_QUERY: LogId = new_log_id!("SlowQuery", LogLevel::Warn);
+const QUERY_FAILED: LogId = new_log_id!("QueryFailed", LogLevel::Error);
+
+mod db {
+    use super::*;
+
+    pub fn fail(logger: &Logger) {
+        log!(target: logger, SLOW_QUERY);
+        log!(target: logger, QUERY_FAILED);
+    }
+}
+
+#[test]
+fn query_by_id_level_and_module() {
+    let (logger, store, _handler) = store_setup();
+
+    log!(target: logger, USER_CREATED);
+    db::fail(&logger);
+    wait_for(&store, 3);
+
+    assert_eq!(store.query().id(USER_CREATED).count(), 1);
+    assert_eq!(
+        store
+            .query()
+            .levels(LogLevel::Warn..=LogLevel::Error)
+            .count(),
+        2
+    );
+
+    let db_errors = store
+        .query()
+        .min_level(LogLevel::Error)
+        .module_prefix(format!("{}::db", module_path!()))
+        .events();
+    assert_eq!(db_errors.len(), 1);
+    assert_eq!(db_errors[0].get_event_id(), &QUERY_FAILED);
+
+    assert_eq!(
+        store
+            .query()
+            .module_prefix(format!("{}::d", module_path!()))
+            .count(),
+        0,
+        "Module prefix matched a partial module name."
+    );
+}
+
+#[test]
+fn query_by_time_window_and_limit() {
+    let (logger, store, _handler) = store_setup();
+
+    log!(target: logger, USER_CREATED);
+    wait_for(&store, 1);
+    let between = SystemTime::now();
+    std::thread::sleep(Duration::from_millis(5));
+    log!(target: logger, SLOW_QUERY);
+    log!(target: logger, SLOW_QUERY);
+    wait_for(&store, 3);
+
+    assert_eq!(store.query().since(between).count(), 2);
+    assert_eq!(store.query().until(between).count(), 1);
+    assert_eq!(store.query().within(Duration::from_secs(60)).count(), 3);
+
+    let latest = store.query().limit(1).events();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0].get_event_id(), &SLOW_QUERY);
+}
+
+#[test]
+fn query_by_entry_and_links() {
+    let (logger, store, _handler) = store_setup();
+
+    let cause = log!(target: logger, QUERY_FAILED);
+    let effect = log!(
+        target: logger,
+        SLOW_QUERY,
+        add: AddonKind::Related(cause.clone())
+    );
+    wait_for(&store, 2);
+
+    let stored_cause = store.get(*cause.get_entry_id()).unwrap();
+    assert_eq!(stored_cause.get_event_id(), &QUERY_FAILED);
+
+    let linked = store.query().linked_to(*cause.get_entry_id()).events();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].get_entry_id(), *effect.get_entry_id());
+
+    let linked_from = store.linked_from(*effect.get_entry_id());
+    assert_eq!(linked_from.len(), 1);
+    assert_eq!(linked_from[0].get_entry_id(), *cause.get_entry_id());
+}
+
+#[test]
+fn capacity_drops_oldest_events() {
+    let store = EventStore::with_capacity(2);
+    let logger = Logger::builder().filter(FilterConfig::new("trace")).build();
+    let _handler = LogEventHandlerBuilder::with_logger(&logger)
+        .add_handler(store.handler())
+        .all_log_events()
+        .build()
+        .unwrap();
+
+    log!(target: logger, USER_CREATED);
+    log!(target: logger, SLOW_QUERY);
+    log!(target: logger, QUERY_FAILED);
+    wait_until("the latest event", || {
+        store.query().id(QUERY_FAILED).count() == 1
+    });
+
+    let ids: Vec<LogId> = store
+        .query()
+        .events()
+        .iter()
+        .map(|event| *event.get_event_id())
+        .collect();
+    assert_eq!(ids, vec![SLOW_QUERY, QUERY_FAILED]);
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "registry")]
+
+use logid::{
+    log_id::LogId,
+    registry::{self, RegisteredLogId},
+    ErrLogId,
+};
+
+#[derive(Debug, ErrLogId)]
+enum StorageError {
+    #[log_id(
+        code = "E0041",
+        explain = "The storage backend could not be reached.",
+        help_url = "https://example.com/errors/E0041"
+    )]
+    Unreachable,
+    #[log_id(code = "E0042", explain_file = "tests/explanations/E0042.md")]
+    Full,
+    Unexplained,
+}
+
+#[test]
+fn explanation_set_via_derive() {
+    let unreachable_id: LogId = StorageError::Unreachable.into();
+    let unexplained_id: LogId = StorageError::Unexplained.into();
+    let unreachable = registry::find(&unreachable_id).unwrap();
+
+    assert_eq!(
+        unreachable.get_explanation(),
+        Some("The storage backend could not be reached."),
+        "Explanation not set via derive."
+    );
+    assert_eq!(
+        unreachable.get_help_url(),
+        Some("https://example.com/errors/E0041"),
+        "Help URL not set via derive."
+    );
+    assert_eq!(
+        registry::find(&unexplained_id).and_then(RegisteredLogId::get_explanation),
+        None,
+        "Explanation set for variant without explanation."
+    );
+    assert_eq!(
+        unreachable_id.explanation(),
+        unreachable.get_explanation(),
+        "Explanation of log-id differs from registered explanation."
+    );
+}
+
+#[test]
+fn explanation_set_via_file() {
+    let full_id: LogId = StorageError::Full.into();
+
+    assert_eq!(
+        registry::find(&full_id).and_then(RegisteredLogId::get_explanation),
+        Some(include_str!("explanations/E0042.md")),
+        "Explanation not read from file."
+    );
+}
+
+#[test]
+fn help_refers_to_url() {
+    let unreachable_id: LogId = StorageError::Unreachable.into();
+
+    assert!(
+        unreachable_id
+            .help()
+            .unwrap()
+            .ends_with("see https://example.com/errors/E0041"),
+        "Help does not refer to help URL."
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn help_as_json_member() {
+    use logid::{log, logging::LOGGER};
+
+    logid::log_id::set_explain_command("myapp --explain");
+    let recv = LOGGER.subscribe(StorageError::Full.into()).unwrap();
+
+    log!(StorageError::Full, "Storage full.");
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    let json = logid::event_handler::json::event_to_json(&event);
+
+    assert_eq!(
+        json["help"], "run `myapp --explain E0042`",
+        "Help not added as JSON member."
+    );
+}
+
+#[test]
+fn explain_registered_code() {
+    assert_eq!(
+        logid::explain("E0041"),
+        Some("The storage backend could not be reached."),
+        "Explanation not found by code."
+    );
+    assert_eq!(
+        logid::explain("StorageError::Full"),
+        Some(include_str!("explanations/E0042.md")),
+        "Explanation not found by identifier."
+    );
+    assert_eq!(
+        logid::explain("E9999"),
+        None,
+        "Explanation found for unknown code."
+    );
+}
@@ -0,0 +1,130 @@
+use logid::{
+    log_id::{LogId, LogLevel},
+    logging::{event_entry::AddonKind, LOGGER},
+    new_log_id, ErrLogId, LogOptionExt, LogResultExt,
+};
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, ErrLogId, Error)]
+enum ParseError {
+    #[error("Value is empty.")]
+    Empty,
+}
+
+#[derive(Debug, PartialEq, ErrLogId, Error)]
+enum ConfigError {
+    #[error("Invalid configuration.")]
+    Invalid(#[source] ParseError),
+}
+
+fn parse(value: &str) -> Result<String, ParseError> {
+    if value.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(value.to_string())
+}
+
+fn load(value: &str) -> Result<String, ConfigError> {
+    let parsed = parse(value).log_err().map_err(ConfigError::Invalid)?;
+    Ok(parsed)
+}
+
+#[test]
+fn log_err_logs_only_errors() {
+    let recv = LOGGER.subscribe(ParseError::Empty.into()).unwrap();
+
+    assert_eq!(parse("value").log_err(), Ok("value".to_string()));
+    assert!(
+        recv.get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .is_err(),
+        "Ok value was logged"
+    );
+
+    let line = line!() + 1;
+    assert_eq!(parse("").log_err(), Err(ParseError::Empty));
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    assert_eq!(event.get_msg().unwrap(), "Value is empty.");
+    assert_eq!(
+        event.get_origin().line_nr,
+        line,
+        "Event not set at caller location"
+    );
+    assert_eq!(event.get_origin().filename, file!());
+    assert_eq!(
+        event.get_origin().module_path,
+        module_path!(),
+        "Module path of the log-id not set as origin"
+    );
+}
+
+#[test]
+fn log_err_links_logged_source() {
+    let recv = LOGGER
+        .subscribe(ConfigError::Invalid(ParseError::Empty).into())
+        .unwrap();
+
+    let result = load("").log_err();
+
+    assert!(result.is_err(), "Error not returned");
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    let parent = event.get_entry().get_parent().expect("Parent not set");
+    assert_eq!(parent.get_event_id(), &LogId::from(ParseError::Empty));
+}
+
+#[test]
+fn log_err_with_adds_addons() {
+    let recv = LOGGER.subscribe(ParseError::Empty.into()).unwrap();
+
+    let _ = parse("").log_err_with(|err| [AddonKind::Info(format!("Parsing failed: {err}"))]);
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    assert_eq!(
+        event.get_entry().get_infos(),
+        &vec!["Parsing failed: Value is empty.".to_string()],
+        "Addon not added"
+    );
+}
+
+#[test]
+fn log_none_and_inspect_log() {
+    let missing: LogId = new_log_id!("missing_value", LogLevel::Warn);
+    let loaded: LogId = new_log_id!("loaded_value", LogLevel::Info);
+    let recv = LOGGER.subscribe_to_many(vec![missing, loaded]).unwrap();
+
+    assert_eq!(Some(1).log_none(missing), Some(1));
+    assert_eq!(None::<u8>.log_none(missing), None);
+    assert_eq!(None::<u8>.inspect_log(|_| loaded), None);
+    assert_eq!(
+        parse("value").inspect_log(|_| loaded),
+        Ok("value".to_string())
+    );
+
+    let missing_event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    let loaded_event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(missing_event.get_event_id(), &missing);
+    assert_eq!(loaded_event.get_event_id(), &loaded);
+    assert!(
+        recv.get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .is_err(),
+        "Logged on wrong path"
+    );
+}
@@ -0,0 +1,77 @@
+use logid::{field, log};
+use logid_core::logging::{field::FieldValue, LOGGER};
+use logid_derive::InfoLogId;
+
+#[derive(Debug, Default, InfoLogId, Clone)]
+enum TestInfoId {
+    One,
+    #[default]
+    Two,
+}
+
+impl std::fmt::Display for TestInfoId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[test]
+fn capture_single_logid_with_fields() {
+    let recv = LOGGER.subscribe(TestInfoId::One.into()).unwrap();
+
+    log!(
+        TestInfoId::One,
+        "Request handled.",
+        add: field!("user_id", 42),
+        add: field!("cached", true),
+        add: field!("elapsed", std::time::Duration::from_millis(15))
+    );
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    let entry = event.get_entry();
+    assert_eq!(entry.get_fields().len(), 3, "Not all fields were set");
+    assert_eq!(
+        entry.get_field("user_id").unwrap().get_value(),
+        &FieldValue::Int(42),
+        "Set and stored int field are not equal"
+    );
+    assert_eq!(
+        entry.get_field("cached").unwrap().get_value(),
+        &FieldValue::Bool(true),
+        "Set and stored bool field are not equal"
+    );
+    assert_eq!(
+        entry.get_field("elapsed").unwrap().to_string(),
+        "elapsed=15ms",
+        "Field not displayed as key-value pair"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn fields_as_json_object_members() {
+    let recv = LOGGER.subscribe(TestInfoId::Two.into()).unwrap();
+
+    log!(
+        TestInfoId::Two,
+        "Request handled.",
+        add: field!("user_id", 42),
+        add: field!("name", "bob")
+    );
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    let json = logid::event_handler::json::event_to_json(&event);
+    assert_eq!(
+        json["fields"],
+        serde_json::json!({ "user_id": 42, "name": "bob" }),
+        "Fields not converted to JSON object members"
+    );
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use logid::{
+    event_handler::flight_recorder::RecorderCapacity,
+    log,
+    log_id::{LogId, LogLevel},
+    new_log_id,
+};
+
+mod common;
+
+use common::{receive, recorder_setup, wait_until};
+
+const REQUEST_RECEIVED: LogId = new_log_id!("RequestReceived", LogLevel::Debug);
+const QUERY_STARTED: LogId = new_log_id!("QueryStarted", LogLevel::Trace);
+const QUERY_SLOW: LogId = new_log_id!("QuerySlow", LogLevel::Warn);
+const QUERY_FAILED: LogId = new_log_id!("QueryFailed", LogLevel::Error);
+
+#[test]
+fn error_dumps_recorded_events() {
+    let (logger, recorder, receiver) = recorder_setup(RecorderCapacity::Events(10));
+
+    log!(target: logger, REQUEST_RECEIVED);
+    log!(target: logger, QUERY_STARTED);
+    log!(target: logger, QUERY_SLOW);
+
+    wait_until("3 recorded events", || recorder.events().len() == 3);
+    assert!(
+        receiver.try_recv().is_err(),
+        "Events were written without dump."
+    );
+
+    log!(target: logger, QUERY_FAILED);
+
+    assert_eq!(
+        receive(&receiver, 4),
+        vec![REQUEST_RECEIVED, QUERY_STARTED, QUERY_SLOW, QUERY_FAILED]
+    );
+}
+
+#[test]
+fn only_latest_events_are_kept() {
+    let (logger, _recorder, receiver) = recorder_setup(RecorderCapacity::Events(2));
+
+    log!(target: logger, REQUEST_RECEIVED);
+    log!(target: logger, QUERY_STARTED);
+    log!(target: logger, QUERY_SLOW);
+    log!(target: logger, QUERY_FAILED);
+
+    assert_eq!(receive(&receiver, 2), vec![QUERY_SLOW, QUERY_FAILED]);
+    assert!(
+        receiver.recv_timeout(Duration::from_millis(50)).is_err(),
+        "Dropped events were written."
+    );
+}
+
+#[test]
+fn byte_capacity_keeps_triggering_event() {
+    let (logger, recorder, receiver) = recorder_setup(RecorderCapacity::Bytes(1));
+
+    log!(target: logger, REQUEST_RECEIVED);
+    log!(target: logger, QUERY_STARTED);
+
+    wait_until("the latest event", || {
+        recorder
+            .events()
+            .iter()
+            .any(|event| event.get_event_id() == &QUERY_STARTED)
+    });
+    assert_eq!(recorder.events().len(), 1, "Byte capacity was ignored.");
+    assert!(recorder.recorded_bytes() > 1);
+
+    log!(target: logger, QUERY_FAILED);
+
+    assert_eq!(receive(&receiver, 1), vec![QUERY_FAILED]);
+}
+
+#[test]
+fn dump_on_request() {
+    let (logger, recorder, receiver) = recorder_setup(RecorderCapacity::Events(10));
+
+    log!(target: logger, REQUEST_RECEIVED);
+    log!(target: logger, QUERY_SLOW);
+
+    wait_until("2 recorded events", || recorder.events().len() == 2);
+    recorder.dump();
+
+    assert_eq!(receive(&receiver, 2), vec![REQUEST_RECEIVED, QUERY_SLOW]);
+    assert!(
+        recorder.events().is_empty(),
+        "Recorder was not cleared after dump."
+    );
+}
+
+#[test]
+fn events_below_emitted_level_are_recorded() {
+    let (logger, _recorder, receiver) = recorder_setup(RecorderCapacity::Events(10));
+    let emitted = logger.subscribe_to_all_events().unwrap();
+
+    log!(target: logger, REQUEST_RECEIVED);
+    log!(target: logger, QUERY_FAILED);
+
+    assert_eq!(receive(&receiver, 2), vec![REQUEST_RECEIVED, QUERY_FAILED]);
+    // Note: Events are emitted in order, so the first emitted event shows if the debug event was filtered out
+    let first_emitted = emitted
+        .get_receiver()
+        .recv_timeout(common::TIMEOUT)
+        .unwrap();
+    assert_eq!(
+        first_emitted.get_event_id(),
+        &QUERY_FAILED,
+        "Filter of the logger was bypassed for emitted events."
+    );
+}
+
+#[test]
+fn recording_stops_on_drop() {
+    let (logger, recorder, receiver) = recorder_setup(RecorderCapacity::Events(10));
+    drop(recorder);
+
+    log!(target: logger, QUERY_FAILED);
+
+    assert!(
+        receiver.recv_timeout(Duration::from_millis(50)).is_err(),
+        "Dropped recorder still recorded events."
+    );
+}
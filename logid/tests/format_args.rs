@@ -0,0 +1,106 @@
+use logid::{
+    err, lazy_debug, lazy_info, log,
+    log_id::{LogId, LogLevel},
+    logging::{
+        filter::{set_filter, FilterConfig},
+        LOGGER,
+    },
+    new_log_id, pipe, ErrLogId,
+};
+use logid_core::evident::event::entry::EventEntry;
+use thiserror::Error;
+
+#[derive(Debug, ErrLogId, Error)]
+enum RequestError {
+    #[error("Request failed.")]
+    Failed,
+}
+
+#[test]
+fn log_with_format_args() {
+    let log_id: LogId = new_log_id!("format_args", LogLevel::Error);
+    let recv = LOGGER.subscribe(log_id).unwrap();
+
+    let retries = 3;
+    let host = "localhost";
+    log!(log_id, "Failed after {} retries: {host}", retries);
+
+    let entry = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap()
+        .get_entry()
+        .clone();
+    assert_eq!(
+        entry.get_msg().unwrap(),
+        "Failed after 3 retries: localhost",
+        "Message not formatted"
+    );
+}
+
+#[test]
+fn log_with_format_args_and_addons() {
+    let log_id: LogId = new_log_id!("format_args_with_addons", LogLevel::Error);
+    let recv = LOGGER.subscribe(log_id).unwrap();
+
+    let timeout = 500;
+    log!(log_id, "Timeout of {}ms reached.", timeout, add: lazy_info!("Host: {}", "localhost"), add: lazy_debug!("Timeout: {timeout}"));
+
+    let entry = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap()
+        .get_entry()
+        .clone();
+    assert_eq!(entry.get_msg().unwrap(), "Timeout of 500ms reached.");
+    assert_eq!(entry.get_infos(), &vec!["Host: localhost".to_string()]);
+    assert_eq!(entry.get_debugs(), &vec!["Timeout: 500".to_string()]);
+}
+
+#[test]
+fn err_and_pipe_with_format_args() {
+    let recv = LOGGER.subscribe(RequestError::Failed.into()).unwrap();
+
+    let status = 503;
+    let result: Result<(), _> = err!(RequestError::Failed, "Request failed with status {status}.");
+    assert!(result.is_err(), "Error not returned");
+    let entry = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap()
+        .get_entry()
+        .clone();
+    assert_eq!(entry.get_msg().unwrap(), "Request failed with status 503.");
+
+    let error =
+        pipe!(RequestError::Failed, "Piped {}.", "error", add: lazy_info!("Status {status}"));
+    assert!(matches!(error, RequestError::Failed), "Value not returned");
+    let entry = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap()
+        .get_entry()
+        .clone();
+    assert_eq!(entry.get_msg().unwrap(), "Piped error.");
+    assert_eq!(entry.get_infos(), &vec!["Status 503".to_string()]);
+}
+
+#[test]
+fn filtered_event_does_not_format() {
+    // Note: Only test of this file changing the filter, and all other tests use error level log-ids
+    set_filter(FilterConfig::new("error(all)")).unwrap();
+
+    let log_id: LogId = new_log_id!("filtered_format_args", LogLevel::Warn);
+    let mut formatted = 0;
+    let mut count = || {
+        formatted += 1;
+        formatted
+    };
+
+    log!(log_id, "Called {} times.", count(), add: lazy_info!("Called {} times.", count()));
+
+    assert_eq!(
+        formatted, 0,
+        "Message or addon of filtered event was formatted"
+    );
+}
@@ -0,0 +1,84 @@
+use logid::{
+    lazy_debug, lazy_info, log,
+    log_id::{LogId, LogLevel},
+    logging::{
+        event_entry::AddonKind,
+        filter::{set_filter, AddonFilter, FilterConfig},
+        LOGGER,
+    },
+    new_log_id, set_event,
+};
+
+/// Note: All tests of this file set the same filter, so they do not interfere.
+fn only_info_addons() {
+    set_filter(FilterConfig::new("error(infos)")).unwrap();
+}
+
+#[test]
+fn add_addon_with_skips_filtered_addons() {
+    only_info_addons();
+    let log_id: LogId = new_log_id!("add_addon_with", LogLevel::Error);
+    let recv = LOGGER.subscribe(log_id).unwrap();
+
+    let mut called = Vec::new();
+    set_event!(log_id, "Lazy addons.")
+        .add_addon_with(AddonFilter::Debugs, || {
+            called.push("debug");
+            AddonKind::Debug("Filtered debug.".to_string())
+        })
+        .add_addon_with(AddonFilter::Infos, || {
+            called.push("info");
+            AddonKind::Info("Allowed info.".to_string())
+        })
+        .finalize();
+
+    assert_eq!(called, vec!["info"], "Closure of filtered addon was called");
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    assert_eq!(
+        event.get_entry().get_infos(),
+        &vec!["Allowed info.".to_string()]
+    );
+    assert!(event.get_entry().get_debugs().is_empty());
+}
+
+#[test]
+fn addon_macros_are_lazy() {
+    only_info_addons();
+    let log_id: LogId = new_log_id!("lazy_addon_macros", LogLevel::Error);
+    let recv = LOGGER.subscribe(log_id).unwrap();
+
+    let mut formatted = 0;
+    let mut count = || {
+        formatted += 1;
+        formatted
+    };
+
+    log!(log_id, "Lazy addon macros.", add: lazy_debug!("Debug {}.", count()), add: lazy_info!("Info {}.", count()));
+
+    assert_eq!(formatted, 1, "Filtered addon was formatted");
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    assert_eq!(event.get_entry().get_infos(), &vec!["Info 1.".to_string()]);
+}
+
+#[cfg(feature = "payloads")]
+#[test]
+fn payload_macro_is_lazy() {
+    only_info_addons();
+    let log_id: LogId = new_log_id!("lazy_payload", LogLevel::Error);
+
+    let mut serialized = false;
+    log!(log_id, "Lazy payload.", add: logid::payload!({
+        serialized = true;
+        serde_json::json!({ "large": "payload" })
+    }));
+
+    assert!(!serialized, "Filtered payload was serialized");
+}
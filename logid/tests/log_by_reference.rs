@@ -0,0 +1,91 @@
+use logid::{err, log, pipe, ErrLogId, InfoLogId};
+use logid_core::{log_id::LogId, logging::LOGGER};
+use thiserror::Error;
+
+/// Intentionally not `Clone`, so it may only be logged by reference.
+#[derive(Debug, ErrLogId, Error)]
+enum ConnectionError {
+    #[error("Connection refused by '{0}'.")]
+    Refused(String),
+}
+
+#[derive(Debug, InfoLogId)]
+struct Connected;
+
+impl std::fmt::Display for Connected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Connected.")
+    }
+}
+
+fn connect(host: &str) -> Result<(), ConnectionError> {
+    err!(ConnectionError::Refused(host.to_string()))
+}
+
+#[test]
+fn derived_log_id_from_reference() {
+    let error = ConnectionError::Refused("localhost".to_string());
+    let by_ref: LogId = (&error).into();
+    let by_value: LogId = error.into();
+
+    assert_eq!(
+        by_ref, by_value,
+        "Conversions by reference and value differ"
+    );
+    assert_eq!(LogId::from(&Connected), LogId::from(Connected));
+}
+
+#[test]
+fn log_does_not_consume_value() {
+    let error = ConnectionError::Refused("example.com".to_string());
+    let recv = LOGGER.subscribe((&error).into()).unwrap();
+
+    log!(error);
+    log!(error, "Logged again.");
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    assert_eq!(
+        event.get_msg().unwrap(),
+        "Connection refused by 'example.com'.",
+        "Message not taken from borrowed value"
+    );
+    assert!(
+        matches!(error, ConnectionError::Refused(host) if host == "example.com"),
+        "Value changed by logging"
+    );
+}
+
+#[test]
+fn err_returns_logged_error() {
+    let recv = LOGGER
+        .subscribe(ConnectionError::Refused(String::new()).into())
+        .unwrap();
+
+    let result = connect("db");
+
+    assert!(
+        matches!(result, Err(ConnectionError::Refused(host)) if host == "db"),
+        "Logged error not returned"
+    );
+    assert!(
+        recv.get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .is_ok(),
+        "Error not logged"
+    );
+}
+
+#[test]
+fn pipe_evaluates_expression_once() {
+    let mut evaluated = 0;
+    let connected = pipe!({
+        evaluated += 1;
+        Connected
+    });
+
+    assert_eq!(evaluated, 1, "Expression evaluated more than once");
+    assert_eq!(LogId::from(&connected), LogId::from(Connected));
+}
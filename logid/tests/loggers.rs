@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use logid::{
+    err,
+    event_handler::builder::LogEventHandlerBuilder,
+    log,
+    log_id::{LogId, LogLevel},
+    logging::{
+        filter::FilterConfig,
+        logger::{add_recorder, remove_recorder, Logger},
+        LOGGER,
+    },
+    new_log_id, pipe, set_event,
+};
+
+const CACHE_MISS: LogId = new_log_id!("CacheMiss", LogLevel::Warn);
+const CACHE_CORRUPTED: LogId = new_log_id!("CacheCorrupted", LogLevel::Error);
+
+#[test]
+fn loggers_use_own_filters() {
+    let warn_logger = Logger::builder().filter(FilterConfig::new("warn")).build();
+    let error_logger = Logger::builder().filter(FilterConfig::new("error")).build();
+
+    let warn_recv = warn_logger.subscribe_to_all_events().unwrap();
+    let error_recv = error_logger.subscribe_to_all_events().unwrap();
+    let default_recv = LOGGER.subscribe(CACHE_MISS).unwrap();
+
+    log!(target: warn_logger, CACHE_MISS);
+    log!(target: error_logger, CACHE_MISS);
+    log!(target: error_logger, CACHE_CORRUPTED, "Checksum mismatch in {}.", "cache.db");
+
+    let event = warn_recv
+        .get_receiver()
+        .recv_timeout(Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(event.get_event_id(), &CACHE_MISS);
+
+    let event = error_recv
+        .get_receiver()
+        .recv_timeout(Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(
+        event.get_event_id(),
+        &CACHE_CORRUPTED,
+        "Warning was not filtered by the error logger."
+    );
+    assert_eq!(
+        event.get_msg().unwrap().to_string(),
+        "Checksum mismatch in cache.db."
+    );
+
+    assert!(
+        default_recv
+            .get_receiver()
+            .recv_timeout(Duration::from_millis(10))
+            .is_err(),
+        "Event set for a logger was captured by the default LOGGER."
+    );
+}
+
+#[test]
+fn set_filter_of_logger() {
+    let logger = Logger::builder().filter(FilterConfig::new("error")).build();
+    let recv = logger.subscribe(CACHE_MISS).unwrap();
+
+    set_event!(target: logger, CACHE_MISS).finalize();
+    logger.set_filter(FilterConfig::new("warn")).unwrap();
+    set_event!(target: logger, CACHE_MISS, "Cache miss after filter change.").finalize();
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(
+        event.get_msg().unwrap().to_string(),
+        "Cache miss after filter change."
+    );
+}
+
+#[test]
+fn invalid_filter_string_is_rejected() {
+    let logger = Logger::builder().filter(FilterConfig::new("warn")).build();
+    let recv = logger.subscribe(CACHE_MISS).unwrap();
+
+    assert!(
+        logger.set_filter("error{msg ~ }").is_err(),
+        "Invalid content filter was accepted."
+    );
+    set_event!(target: logger, CACHE_MISS, "Filter kept after invalid filter.").finalize();
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(
+        event.get_msg().unwrap().to_string(),
+        "Filter kept after invalid filter."
+    );
+}
+
+#[test]
+fn predicate_runs_once_per_event() {
+    let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let predicate_calls = calls.clone();
+    let logger = Logger::builder()
+        .filter(
+            FilterConfig::builder(LogLevel::Warn)
+                .content_predicate(move |_| {
+                    predicate_calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    true
+                })
+                .build(),
+        )
+        .build();
+    let recv = logger.subscribe(CACHE_MISS).unwrap();
+
+    log!(target: logger, CACHE_MISS, "Checked once.");
+
+    recv.get_receiver()
+        .recv_timeout(Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(
+        calls.load(std::sync::atomic::Ordering::Relaxed),
+        1,
+        "Predicate not evaluated exactly once."
+    );
+}
+
+#[test]
+fn handler_for_logger() {
+    let logger = Logger::builder().filter(FilterConfig::new("warn")).build();
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    let handler = LogEventHandlerBuilder::with_logger(&logger)
+        .add_handler(move |event| {
+            let _ = sender.send(*event.get_event_id());
+        })
+        .all_log_events()
+        .build()
+        .unwrap();
+
+    let by_ref = &logger;
+    let piped = pipe!(target: by_ref, CACHE_MISS);
+    let res: Result<(), LogId> = err!(target: logger, CACHE_CORRUPTED);
+
+    assert_eq!(piped, CACHE_MISS);
+    assert_eq!(res, Err(CACHE_CORRUPTED));
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_millis(100)).unwrap(),
+        CACHE_MISS
+    );
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_millis(100)).unwrap(),
+        CACHE_CORRUPTED
+    );
+
+    handler.shutdown();
+}
+
+#[test]
+fn recorder_ignores_filter_of_logger() {
+    let logger = Logger::builder().filter(FilterConfig::new("error")).build();
+    let recorder = Logger::builder().filter(FilterConfig::new("warn")).build();
+    let recv = recorder.subscribe(CACHE_MISS).unwrap();
+    add_recorder(Some(&logger), &recorder);
+
+    log!(target: logger, CACHE_MISS, "Recorded cache miss.");
+    remove_recorder(Some(&logger), &recorder);
+    log!(target: logger, CACHE_MISS, "Cache miss after removal.");
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(event.get_msg().unwrap().to_string(), "Recorded cache miss.");
+    assert!(
+        recv.get_receiver()
+            .recv_timeout(Duration::from_millis(100))
+            .is_err(),
+        "Removed recorder still received events."
+    );
+}
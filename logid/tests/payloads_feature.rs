@@ -44,8 +44,8 @@ mod payload_tests {
 
         let entry = event.get_entry();
         assert_eq!(
-            TestTraceId::from(*entry.get_event_id()),
-            TestTraceId::One,
+            TestTraceId::try_from(*entry.get_event_id()),
+            Ok(TestTraceId::One),
             "Set and stored log-ids are not equal"
         );
         assert_eq!(
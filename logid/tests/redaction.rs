@@ -0,0 +1,102 @@
+#[cfg(feature = "redaction")]
+mod redaction_tests {
+    use logid::{
+        field, log,
+        log_id::{LogId, LogLevel},
+        logging::{
+            event_entry::AddonKind,
+            filter::FilterConfig,
+            logger::Logger,
+            redaction::{set_redaction, Redacted, Redaction},
+            LOGGER,
+        },
+        new_log_id,
+    };
+    use logid_core::evident::event::entry::EventEntry;
+
+    /// Note: All tests of this file set the same rules, so they do not interfere.
+    fn set_rules() {
+        set_redaction(
+            Redaction::new()
+                .pointer("/user/email")
+                .pattern(r"[\w.]+@[\w.]+")
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn message_and_addons_are_redacted() {
+        set_rules();
+        let log_id: LogId = new_log_id!("redacted_event", LogLevel::Error);
+        let recv = LOGGER.subscribe(log_id).unwrap();
+
+        let email = "jane@example.com";
+        log!(
+            log_id,
+            "Sending mail to {email} failed.",
+            add: AddonKind::Info(format!("Recipient: {email}")),
+            add: field!("token", Redacted("abc123"))
+        );
+
+        let event = recv
+            .get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+        let entry = event.get_entry();
+
+        assert_eq!(entry.get_msg().unwrap(), "Sending mail to *** failed.");
+        assert_eq!(entry.get_infos(), &vec!["Recipient: ***".to_string()]);
+        assert_eq!(entry.get_fields()[0].to_string(), "token=\"***\"");
+    }
+
+    #[test]
+    fn content_filter_sees_raw_values() {
+        set_rules();
+        let log_id: LogId = new_log_id!("redacted_filtered_event", LogLevel::Error);
+        let logger = Logger::builder()
+            .filter(FilterConfig::new("error{msg ~ example.com}"))
+            .build();
+        let recv = logger.subscribe(log_id).unwrap();
+
+        log!(target: logger, log_id, "Mail to jane@example.com bounced.");
+        log!(target: logger, log_id, "Mail to jane@other.org bounced.");
+
+        let event = recv
+            .get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+        assert_eq!(event.get_entry().get_msg().unwrap(), "Mail to *** bounced.");
+        assert!(
+            recv.get_receiver()
+                .recv_timeout(std::time::Duration::from_millis(10))
+                .is_err(),
+            "Event without matching content must be filtered out."
+        );
+    }
+
+    #[cfg(feature = "payloads")]
+    #[test]
+    fn payloads_are_redacted() {
+        set_rules();
+        let log_id: LogId = new_log_id!("redacted_payload", LogLevel::Error);
+        let recv = LOGGER.subscribe(log_id).unwrap();
+
+        log!(log_id, "Payload.", add: AddonKind::Payload(serde_json::json!({
+            "user": { "email": "secret", "id": 42 },
+            "note": "Contact jane@example.com",
+        })));
+
+        let event = recv
+            .get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+
+        assert_eq!(
+            event.get_entry().get_payloads(),
+            &vec![serde_json::json!({
+                "user": { "email": "***", "id": 42 },
+                "note": "Contact ***",
+            })]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "registry")]
+mod registry_tests {
+    use logid::{
+        log_id::{LogId, LogLevel},
+        new_log_id, registry, ErrLogId, WarnLogId,
+    };
+
+    /// Errors of the registry tests.
+    #[derive(Debug, ErrLogId, thiserror::Error)]
+    enum RegistryError {
+        /// The file could not be read.
+        ///
+        /// Check the file permissions.
+        #[error("Could not read file.")]
+        ReadFailed,
+        #[log_id(code = "E0815", explain = "The disk may be full.")]
+        #[error("Could not write '{0}'.")]
+        WriteFailed(String),
+    }
+
+    /// Warning set for slow requests.
+    #[derive(WarnLogId)]
+    struct SlowRequest;
+
+    const CUSTOM_ID: LogId = new_log_id!("custom_registry_id", LogLevel::Info);
+
+    #[test]
+    fn derived_enum_variants_registered() {
+        let read_id: LogId = RegistryError::ReadFailed.into();
+        let registered = registry::find(&read_id).expect("Variant not registered.");
+
+        assert_eq!(
+            registered.get_doc(),
+            "The file could not be read.\n\nCheck the file permissions.",
+            "Doc comment of variant not registered."
+        );
+        assert_eq!(
+            registered.get_file(),
+            file!(),
+            "Wrong file registered for variant."
+        );
+        assert_eq!(
+            registered.get_line(),
+            15,
+            "Wrong line registered for variant."
+        );
+
+        assert_eq!(
+            registered.get_error_msg(),
+            Some("Could not read file."),
+            "Error message of variant not registered."
+        );
+
+        let write_id: LogId = RegistryError::WriteFailed(String::new()).into();
+        let registered = registry::find_by_code("E0815").expect("Variant not found by code.");
+        assert_eq!(
+            registered.get_log_id(),
+            write_id,
+            "Wrong variant found by code."
+        );
+        assert_eq!(
+            registered.get_error_msg(),
+            Some("Could not write '{0}'."),
+            "Error message with format arguments not registered."
+        );
+        assert_eq!(
+            registered.get_explanation(),
+            Some("The disk may be full."),
+            "Explanation of variant not registered."
+        );
+    }
+
+    #[test]
+    fn derived_struct_registered() {
+        let slow_id: LogId = SlowRequest.into();
+        let registered = registry::find(&slow_id).expect("Struct not registered.");
+
+        assert_eq!(
+            registered.get_log_level(),
+            LogLevel::Warn,
+            "Wrong level registered for struct."
+        );
+        assert_eq!(
+            registered.get_doc(),
+            "Warning set for slow requests.",
+            "Doc comment of struct not registered."
+        );
+    }
+
+    #[test]
+    fn all_ids_contains_ids_of_module() {
+        let module_ids: Vec<_> = registry::all_ids()
+            .into_iter()
+            .filter(|registered| registered.get_module_path() == module_path!())
+            .map(|registered| registered.get_identifier())
+            .collect();
+
+        assert_eq!(
+            module_ids,
+            vec![
+                "RegistryError::ReadFailed",
+                "RegistryError::WriteFailed(_)",
+                "SlowRequest",
+                "custom_registry_id",
+            ],
+            "Not all ids of the module registered."
+        );
+        assert_eq!(
+            registry::find(&CUSTOM_ID).map(|registered| registered.get_doc()),
+            Some(""),
+            "Id of `new_log_id!` not registered."
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use logid::{
+    event_handler::render::{render_captured, RenderFormat, RenderOptions},
+    field, lazy_info, log,
+    testing::capture_events,
+    ErrLogId,
+};
+use thiserror::Error;
+
+#[derive(Debug, ErrLogId, Error)]
+enum DiskError {
+    #[error("Disk is full.")]
+    Full,
+}
+
+#[derive(Debug, ErrLogId, Error)]
+enum SaveError {
+    #[error("Could not save file.")]
+    Failed(#[source] DiskError),
+}
+
+fn log_chain() {
+    log!(DiskError::Full, add: field!("free_bytes", 0u64));
+    log!(SaveError::Failed(DiskError::Full), add: lazy_info!("Path: {}", "/tmp/data"));
+}
+
+#[test]
+fn terminal_snapshot() {
+    let cap = capture_events();
+    log_chain();
+
+    let rendered = render_captured(
+        &cap,
+        RenderFormat::Terminal,
+        &RenderOptions::deterministic(),
+    );
+
+    assert_eq!(
+        rendered,
+        r#"ERR   Disk is full.
+├───> Event: id='render_snapshots::DiskError::Full', entry='<entry-1>'
+├───> Origin: module="render_snapshots", file="<file>", line=<line>
+╰───> Fields: free_bytes=0
+    ERR   Could not save file.
+    ├───> Event: id='render_snapshots::SaveError::Failed(_)', entry='<entry-2>'
+    ├───> Origin: module="render_snapshots", file="<file>", line=<line>
+    ├───> Caused by: lvl='ERR', id='render_snapshots::DiskError::Full', entry='<entry-1>'
+    ╰───> Info: Path: /tmp/data
+"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_snapshot() {
+    let cap = capture_events();
+    log_chain();
+
+    let rendered = render_captured(&cap, RenderFormat::Json, &RenderOptions::deterministic());
+
+    // Note: Thread names depend on how tests are run
+    let lines: Vec<serde_json::Value> = rendered
+        .lines()
+        .map(|line| {
+            let mut value: serde_json::Value = serde_json::from_str(line).unwrap();
+            value.as_object_mut().unwrap().remove("thread");
+            value
+        })
+        .collect();
+
+    assert_eq!(
+        lines,
+        vec![
+            serde_json::json!({
+                "entry": "<entry-1>",
+                "fields": { "free_bytes": 0 },
+                "id": "render_snapshots::DiskError::Full",
+                "level": "error",
+                "msg": "Disk is full.",
+                "origin": { "file": "<file>", "line": "<line>", "module": "render_snapshots" },
+            }),
+            serde_json::json!({
+                "entry": "<entry-2>",
+                "id": "render_snapshots::SaveError::Failed(_)",
+                "infos": ["Path: /tmp/data"],
+                "level": "error",
+                "msg": "Could not save file.",
+                "origin": { "file": "<file>", "line": "<line>", "module": "render_snapshots" },
+                "parent": { "entry": "<entry-1>", "id": "render_snapshots::DiskError::Full", "level": "error" },
+            }),
+        ]
+    );
+}
+
+#[test]
+fn uuids_in_messages_are_kept() {
+    let cap = capture_events();
+    log!(
+        DiskError::Full,
+        "Volume 67e55044-10b1-426f-9247-bb680e5fe0c8 is full."
+    );
+
+    let rendered = render_captured(
+        &cap,
+        RenderFormat::Terminal,
+        &RenderOptions::deterministic(),
+    );
+
+    assert!(
+        rendered.starts_with("ERR   Volume 67e55044-10b1-426f-9247-bb680e5fe0c8 is full.\n"),
+        "UUID in message was replaced: {rendered}"
+    );
+    assert!(rendered.contains("entry='<entry-1>'"));
+}
@@ -0,0 +1,81 @@
+use std::future::Future;
+
+use logid::{info_addon, log, scope};
+use logid_core::{
+    log_id::{LogId, LogLevel},
+    logging::{field::FieldValue, LOGGER},
+    new_log_id,
+};
+
+#[test]
+fn scope_addons_added_to_events() {
+    let log_id = new_log_id!("scoped_id", LogLevel::Info);
+    let recv = LOGGER.subscribe(log_id).unwrap();
+
+    {
+        let _guard = scope()
+            .with_field("request_id", 1234)
+            .with_info("Handling request.")
+            .enter();
+
+        log!(log_id, "In scope.", add: info_addon!("Call site info.".to_string()));
+    }
+
+    log!(log_id, "Out of scope.");
+
+    let in_scope = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    let entry = in_scope.get_entry();
+
+    assert_eq!(
+        entry.get_field("request_id").unwrap().get_value(),
+        &FieldValue::Int(1234),
+        "Scope field not added to event"
+    );
+    assert_eq!(
+        entry.get_infos(),
+        &vec![
+            "Handling request.".to_string(),
+            "Call site info.".to_string()
+        ],
+        "Scope info not added before call site info"
+    );
+
+    let out_of_scope = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert!(
+        out_of_scope.get_entry().get_fields().is_empty(),
+        "Scope field added after scope was left"
+    );
+}
+
+#[test]
+fn scope_follows_future() {
+    let log_id: LogId = new_log_id!("scoped_future_id", LogLevel::Info);
+    let recv = LOGGER.subscribe(log_id).unwrap();
+
+    let mut scoped = Box::pin(scope().with_field("task", "sync").in_scope(async move {
+        log!(log_id, "In future.");
+    }));
+    let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    assert!(
+        scoped.as_mut().poll(&mut cx).is_ready(),
+        "Future without await not ready"
+    );
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        event.get_entry().get_field("task").unwrap().get_value(),
+        &FieldValue::Str("sync".to_string()),
+        "Scope field not added to event created in future"
+    );
+}
@@ -0,0 +1,172 @@
+use logid::{log, span};
+use logid_core::{
+    log_id::{LogId, LogLevel},
+    logging::{
+        field::FieldValue,
+        filter::FilterConfig,
+        logger::Logger,
+        span::{ELAPSED_FIELD, END_FIELD, MAX_RELATED_ENTRIES, RELATED_DROPPED_FIELD, START_FIELD},
+        LOGGER,
+    },
+    new_log_id,
+};
+
+#[test]
+fn span_contains_duration_and_timestamps() {
+    let span_id: LogId = new_log_id!("timed_span", LogLevel::Info);
+    let recv = LOGGER.subscribe(span_id).unwrap();
+
+    let span = span!(span_id, "Loading data.");
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let finalized = span.finish();
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    let entry = event.get_entry();
+
+    assert_eq!(
+        event.get_entry_id(),
+        finalized.entry_id,
+        "Captured span event differs from finalized span"
+    );
+
+    match entry.get_field(ELAPSED_FIELD).unwrap().get_value() {
+        FieldValue::Duration(elapsed) => assert!(
+            *elapsed >= std::time::Duration::from_millis(2),
+            "Elapsed duration shorter than span"
+        ),
+        other => panic!("Elapsed field has wrong type: {:?}", other),
+    }
+
+    match (
+        entry.get_field(START_FIELD).unwrap().get_value(),
+        entry.get_field(END_FIELD).unwrap().get_value(),
+    ) {
+        (FieldValue::Timestamp(start), FieldValue::Timestamp(end)) => {
+            assert!(start < end, "Span ended before it started")
+        }
+        other => panic!("Timestamp fields have wrong types: {:?}", other),
+    }
+}
+
+#[test]
+fn events_in_span_are_related() {
+    let span_id: LogId = new_log_id!("outer_span", LogLevel::Info);
+    let inner_span_id: LogId = new_log_id!("inner_span", LogLevel::Info);
+    let inner_id: LogId = new_log_id!("inner_event", LogLevel::Info);
+    let recv = LOGGER
+        .subscribe_to_many(vec![span_id, inner_span_id])
+        .unwrap();
+
+    let inner_event;
+    let inner_span;
+    {
+        let _span = span!(span_id, "Outer span.");
+        {
+            let _inner = span!(inner_span_id, "Inner span.");
+            inner_event = log!(inner_id, "In span.");
+        }
+        inner_span = recv
+            .get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+    }
+    log!(inner_id, "After span.");
+
+    let outer_span = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+
+    assert_eq!(
+        inner_span.get_entry().get_related(),
+        &vec![inner_event.clone()],
+        "Event not related to inner span"
+    );
+
+    let outer_related: Vec<_> = outer_span
+        .get_entry()
+        .get_related()
+        .iter()
+        .map(|related| related.entry_id)
+        .collect();
+    assert_eq!(
+        outer_related,
+        vec![inner_event.entry_id, inner_span.get_entry_id()],
+        "Inner event and span not related to outer span"
+    );
+}
+
+#[test]
+fn filtered_events_are_not_related() {
+    let span_id: LogId = new_log_id!("filtered_span", LogLevel::Info);
+    let filtered_id: LogId = new_log_id!("filtered_event", LogLevel::Info);
+    let logger = Logger::builder().filter(FilterConfig::new("warn")).build();
+    let recv = LOGGER.subscribe(span_id).unwrap();
+
+    {
+        let _span = span!(span_id, "Filtering span.");
+        log!(target: logger, filtered_id, "Filtered out.");
+    }
+
+    let span_event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(10))
+        .unwrap();
+    assert!(
+        span_event.get_entry().get_related().is_empty(),
+        "Filtered out event related to span"
+    );
+}
+
+#[test]
+fn span_event_is_captured_by_target() {
+    let span_id: LogId = new_log_id!("target_span", LogLevel::Info);
+    let logger = Logger::builder().filter(FilterConfig::new("info")).build();
+    let recv = logger.subscribe(span_id).unwrap();
+    let default_recv = LOGGER.subscribe(span_id).unwrap();
+
+    let finalized = span!(target: logger, span_id, "Span of logger.").finish();
+
+    let event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(event.get_entry_id(), finalized.entry_id);
+    assert!(
+        default_recv
+            .get_receiver()
+            .recv_timeout(std::time::Duration::from_millis(10))
+            .is_err(),
+        "Span event set for a logger was captured by the default LOGGER."
+    );
+}
+
+#[test]
+fn long_span_keeps_latest_related_entries() {
+    let span_id: LogId = new_log_id!("long_span", LogLevel::Info);
+    let inner_id: LogId = new_log_id!("long_span_event", LogLevel::Trace);
+    let recv = LOGGER.subscribe(span_id).unwrap();
+
+    let span = span!(span_id, "Request loop.");
+    let mut last_event = None;
+    for nr in 0..MAX_RELATED_ENTRIES + 2 {
+        last_event = Some(log!(inner_id, format!("Request {nr}.")));
+    }
+    drop(span);
+
+    let span_event = recv
+        .get_receiver()
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .unwrap();
+    let entry = span_event.get_entry();
+
+    assert_eq!(entry.get_related().len(), MAX_RELATED_ENTRIES);
+    assert_eq!(entry.get_related().last(), last_event.as_ref());
+    assert_eq!(
+        entry.get_field(RELATED_DROPPED_FIELD).unwrap().get_value(),
+        &FieldValue::UInt(2)
+    );
+}
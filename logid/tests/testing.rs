@@ -0,0 +1,96 @@
+use logid::{
+    err, lazy_info, log,
+    logging::{filter::FilterConfig, logger::Logger},
+    testing::capture_events,
+    ErrLogId, InfoLogId, WarnLogId,
+};
+use thiserror::Error;
+
+#[derive(Debug, ErrLogId, Error)]
+enum StoreError {
+    #[error("Store is not reachable.")]
+    Unreachable,
+}
+
+#[derive(Debug, WarnLogId)]
+enum StoreWarning {
+    SlowResponse,
+}
+
+#[derive(Debug, InfoLogId)]
+enum StoreInfo {
+    Connected,
+}
+
+fn connect(reachable: bool) -> Result<(), StoreError> {
+    if !reachable {
+        return err!(StoreError::Unreachable, "Could not connect.");
+    }
+
+    log!(StoreInfo::Connected, "Connected.", add: lazy_info!("Host: {}", "localhost"));
+    Ok(())
+}
+
+#[test]
+fn captured_events_of_current_thread() {
+    let cap = capture_events();
+
+    connect(true).unwrap();
+
+    cap.assert_logged(StoreInfo::Connected);
+    cap.assert_info_contains(StoreInfo::Connected, "localhost");
+    cap.assert_not_logged(StoreError::Unreachable);
+    cap.assert_no_errors();
+}
+
+#[test]
+fn events_of_other_threads_are_not_captured() {
+    let cap = capture_events();
+
+    std::thread::spawn(|| connect(false))
+        .join()
+        .unwrap()
+        .unwrap_err();
+    log!(StoreWarning::SlowResponse, "Slow.");
+
+    cap.assert_not_logged(StoreError::Unreachable);
+    cap.assert_logged(StoreWarning::SlowResponse);
+    assert_eq!(cap.entries().len(), 1, "Events of other threads captured");
+}
+
+#[test]
+fn capturing_stops_on_drop() {
+    let cap = capture_events();
+    {
+        let inner = capture_events();
+        connect(false).unwrap_err();
+        inner.assert_logged(StoreError::Unreachable);
+    }
+    drop(cap);
+
+    let cap = capture_events();
+    log!(StoreWarning::SlowResponse, "Slow.");
+    cap.assert_not_logged(StoreError::Unreachable);
+}
+
+#[test]
+fn filtered_events_are_not_captured() {
+    let logger = Logger::builder().filter(FilterConfig::new("warn")).build();
+    let cap = capture_events();
+
+    log!(target: logger, StoreInfo::Connected, "Connected.");
+    log!(target: logger, StoreWarning::SlowResponse, "Slow.");
+
+    cap.assert_not_logged(StoreInfo::Connected);
+    cap.assert_logged(StoreWarning::SlowResponse);
+}
+
+#[test]
+#[should_panic(expected = "Errors were logged")]
+fn assert_no_errors_panics_on_errors() {
+    let cap = capture_events();
+
+    connect(false).unwrap_err();
+
+    cap.assert_no_errors();
+}
@@ -0,0 +1,495 @@
+//! Library to read, filter, and render logs written by the JSON handlers of [logid].
+//!
+//! Each line of a log must be one JSON object in the format of [`event_to_json()`](logid::event_handler::json::event_to_json).
+//! Events are filtered with the same filter grammar as [`FilterConfig::new()`],
+//! and rendered like the terminal handlers of [logid] render captured events.
+//!
+//! **Usage:**
+//!
+//! ~~~
+//! use logid_view::{ViewOptions, Viewer};
+//!
+//! let mut viewer = Viewer::new(ViewOptions {
+//!     filter: "warn(infos)".to_string(),
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//!
+//! let line = r#"{"level":"warn","id":"my_crate::cache::CacheMiss","entry":"a7c1cbb1-1a9f-4d2c-9b55-5f5ec2b4c8f1","msg":"Cache miss.","origin":{"module":"my_crate::cache","file":"src/cache.rs","line":42},"infos":["Key: users"]}"#;
+//!
+//! let rendered = viewer.view_line(line).unwrap().unwrap();
+//! assert!(rendered.contains("Cache miss."));
+//! assert!(rendered.contains("Key: users"));
+//! ~~~
+//!
+//! **Note:** Event ids are restored with their module path, identifier, level, and code.
+//! Help texts and formatted addons are not restored as they were, because the JSON output only contains their rendered form.
+
+use std::{
+    io::{BufRead, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use logid::{
+    event_handler::{
+        render::RenderOptions,
+        terminal::{render_terminal_with_filter, ChainDepths},
+    },
+    evident::{
+        event::{filter::Filter, finalized::FinalizedEvent, origin::Origin, Event},
+        uuid::Uuid,
+    },
+    log_id::{LogId, LogLevel},
+    logging::{
+        event_entry::{AddonKind, LogEventEntry},
+        field::{FieldValue, LogField},
+        filter::{FilterConfig, LogFilter},
+        intern::{intern, MAX_INTERNED_BYTES},
+        msg::LogMsg,
+    },
+    serde_json::{Map, Value},
+};
+
+/// Filter used if no filter is set, which shows all events with all their information.
+pub const DEFAULT_FILTER: &str = "trace(all)";
+
+/// Interval to check for new lines if the log is followed.
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Options to select and render events.
+#[derive(Debug, Clone)]
+pub struct ViewOptions {
+    /// Filter in the grammar of [`FilterConfig::new()`].
+    pub filter: String,
+    /// Only events whose log-id matches one of these ids are shown, if any is set.
+    ///
+    /// An id may be the full id (e.g. `my_crate::CacheMiss`), only the identifier, or a code prefixed with `#`.
+    pub ids: Vec<String>,
+    /// Only events with a timestamp at or after this time are shown.
+    pub since: Option<SystemTime>,
+    /// Only events with a timestamp at or before this time are shown.
+    pub until: Option<SystemTime>,
+    pub render: RenderOptions,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        ViewOptions {
+            filter: DEFAULT_FILTER.to_string(),
+            ids: Vec::new(),
+            since: None,
+            until: None,
+            render: RenderOptions::default(),
+        }
+    }
+}
+
+/// Event read from one line of a log.
+#[derive(Debug)]
+pub struct ViewedEvent {
+    pub event: Event<LogId, LogMsg, LogEventEntry>,
+    /// Time the event was captured at, if the log contains it.
+    pub timestamp: Option<SystemTime>,
+}
+
+/// Reads events from JSON lines, and renders the events that match the view options.
+pub struct Viewer {
+    filter: FilterConfig,
+    /// Same filter as `filter`, but used to decide which event information is rendered.
+    render_filter: LogFilter,
+    ids: Vec<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    render: RenderOptions,
+    /// Chain depth of rendered events, so caused events are indented below their cause.
+    depths: ChainDepths,
+}
+
+impl Viewer {
+    /// Creates a viewer with the given options, or returns an error if the filter is invalid.
+    pub fn new(options: ViewOptions) -> Result<Self, ViewError> {
+        let filter = FilterConfig::try_new(&options.filter)
+            .map_err(|err| ViewError::InvalidFilter(err.to_string()))?;
+        let render_filter = LogFilter::new();
+        render_filter
+            .set_filter(options.filter.as_str())
+            .map_err(|err| ViewError::InvalidFilter(err.to_string()))?;
+
+        Ok(Viewer {
+            filter,
+            render_filter,
+            ids: options.ids,
+            since: options.since,
+            until: options.until,
+            render: options.render,
+            depths: ChainDepths::new(),
+        })
+    }
+
+    /// Reads the event of the given line.
+    ///
+    /// Addons the filter does not allow for the event are not added.
+    pub fn read_line(&mut self, line: &str) -> Result<ViewedEvent, ViewError> {
+        let value: Value = logid::serde_json::from_str(line)
+            .map_err(|err| ViewError::InvalidJson(err.to_string()))?;
+        let obj = value.as_object().ok_or(ViewError::InvalidJson(
+            "Line is not a JSON object.".to_string(),
+        ))?;
+
+        let id = self.read_log_id(obj)?;
+        let entry_id = read_uuid(obj, "entry")?;
+        let origin = self.read_origin(obj)?;
+        let msg = match obj.get("msg") {
+            Some(msg) => Some(LogMsg::from(read_str(msg, "msg")?)),
+            None => None,
+        };
+        let timestamp = match obj.get("timestamp") {
+            Some(Value::Number(secs)) => secs
+                .as_f64()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .map(|since_epoch| UNIX_EPOCH + since_epoch),
+            _ => None,
+        };
+
+        let addons: Vec<AddonKind> = self
+            .read_addons(obj)?
+            .into_iter()
+            .filter(|addon| self.filter.allow_addon(id, &origin, addon))
+            .collect();
+        let entry = LogEventEntry::restore(id, entry_id, msg, origin, addons);
+
+        Ok(ViewedEvent {
+            event: Event::new(entry),
+            timestamp,
+        })
+    }
+
+    /// Returns `true` if the given event matches the view options.
+    pub fn matches(&self, viewed: &ViewedEvent) -> bool {
+        if self.since.is_some() || self.until.is_some() {
+            let Some(timestamp) = viewed.timestamp else {
+                return false;
+            };
+            if self.since.is_some_and(|since| timestamp < since)
+                || self.until.is_some_and(|until| timestamp > until)
+            {
+                return false;
+            }
+        }
+
+        let id = viewed.event.get_event_id();
+        if !self.ids.is_empty() && !self.ids.iter().any(|view_id| id_matches(id, view_id)) {
+            return false;
+        }
+
+        self.filter.allow_entry(viewed.event.get_entry())
+    }
+
+    /// Renders the given event indented below the event that caused it, if that event was rendered before.
+    pub fn render(&mut self, viewed: &ViewedEvent) -> String {
+        let depth = self
+            .depths
+            .depth_of(viewed.event.get_entry_id(), viewed.event.get_entry());
+
+        render_terminal_with_filter(&viewed.event, &self.render, &self.render_filter, depth)
+    }
+
+    /// Reads the event of the given line, and returns the rendered event if it matches the view options.
+    pub fn view_line(&mut self, line: &str) -> Result<Option<String>, ViewError> {
+        let viewed = self.read_line(line)?;
+
+        if self.matches(&viewed) {
+            Ok(Some(self.render(&viewed)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Renders all matching events of the given reader to the given writer.
+    ///
+    /// Empty lines are skipped, and lines that are no valid events are passed to `on_invalid` with their line number.
+    /// If `follow` is set, the reader is checked for new lines once its end is reached, until an IO error occurs.
+    pub fn view<R, W, F>(
+        &mut self,
+        mut reader: R,
+        mut writer: W,
+        follow: bool,
+        mut on_invalid: F,
+    ) -> std::io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        F: FnMut(usize, ViewError),
+    {
+        let mut line = String::new();
+        let mut line_nr = 0;
+
+        loop {
+            let read = reader.read_line(&mut line)?;
+
+            // Note: A followed log might be partially written, so lines are only viewed once they are complete
+            if follow && (read == 0 || !line.ends_with('\n')) {
+                std::thread::sleep(FOLLOW_INTERVAL);
+                continue;
+            }
+            if line.is_empty() {
+                return Ok(());
+            }
+
+            line_nr += 1;
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                match self.view_line(trimmed) {
+                    Ok(Some(rendered)) => {
+                        writer.write_all(rendered.as_bytes())?;
+                        writer.flush()?;
+                    }
+                    Ok(None) => {}
+                    Err(err) => on_invalid(line_nr, err),
+                }
+            }
+            line.clear();
+        }
+    }
+
+    fn read_log_id(&self, obj: &Map<String, Value>) -> Result<LogId, ViewError> {
+        let level = read_level(get(obj, "level")?)?;
+        let id = read_str(get(obj, "id")?, "id")?;
+        let (module_path, identifier) = id
+            .rsplit_once("::")
+            .ok_or(ViewError::InvalidValue("id", id.to_string()))?;
+
+        let mut log_id = LogId::new(
+            read_interned(module_path)?,
+            read_interned(identifier)?,
+            level,
+        );
+        if let Some(code) = obj.get("code") {
+            log_id = log_id.with_code(read_interned(read_str(code, "code")?)?);
+        }
+        Ok(log_id)
+    }
+
+    fn read_origin(&self, obj: &Map<String, Value>) -> Result<Origin, ViewError> {
+        let origin = get(obj, "origin")?
+            .as_object()
+            .ok_or(ViewError::InvalidValue(
+                "origin",
+                "not an object".to_string(),
+            ))?;
+        let module_path = read_str(get(origin, "module")?, "module")?;
+        let filename = read_str(get(origin, "file")?, "file")?;
+        // Note: Deterministic logs contain a placeholder instead of the line number
+        let line_nr = origin
+            .get("line")
+            .and_then(Value::as_u64)
+            .and_then(|line_nr| u32::try_from(line_nr).ok())
+            .unwrap_or_default();
+
+        Ok(Origin::new(
+            read_interned(module_path)?,
+            read_interned(filename)?,
+            line_nr,
+        ))
+    }
+
+    fn read_finalized(&self, value: &Value) -> Result<FinalizedEvent<LogId>, ViewError> {
+        let obj = value.as_object().ok_or(ViewError::InvalidValue(
+            "related",
+            "not an object".to_string(),
+        ))?;
+        Ok(FinalizedEvent::new(
+            self.read_log_id(obj)?,
+            read_uuid(obj, "entry")?,
+        ))
+    }
+
+    fn read_addons(&self, obj: &Map<String, Value>) -> Result<Vec<AddonKind>, ViewError> {
+        let mut addons = Vec::new();
+
+        for info in read_strings(obj, "infos")? {
+            addons.push(AddonKind::Info(info));
+        }
+        for debug in read_strings(obj, "debugs")? {
+            addons.push(AddonKind::Debug(debug));
+        }
+        for trace in read_strings(obj, "traces")? {
+            addons.push(AddonKind::Trace(trace));
+        }
+        for hint in read_strings(obj, "hints")? {
+            addons.push(AddonKind::Hint(hint));
+        }
+        for note in read_strings(obj, "notes")? {
+            addons.push(AddonKind::Note(note));
+        }
+
+        if let Some(fields) = obj.get("fields") {
+            let fields = fields.as_object().ok_or(ViewError::InvalidValue(
+                "fields",
+                "not an object".to_string(),
+            ))?;
+            for (key, value) in fields {
+                addons.push(AddonKind::Field(LogField::new(
+                    key.clone(),
+                    field_value(value),
+                )));
+            }
+        }
+
+        if let Some(parent) = obj.get("parent") {
+            addons.push(AddonKind::Parent(self.read_finalized(parent)?));
+        }
+        for related in read_array(obj, "related")? {
+            addons.push(AddonKind::Related(self.read_finalized(related)?));
+        }
+
+        for diagnostic in read_array(obj, "diagnostics")? {
+            let diagnostic = logid::serde_json::from_value(diagnostic.clone())
+                .map_err(|err| ViewError::InvalidValue("diagnostics", err.to_string()))?;
+            addons.push(AddonKind::Diagnostic(diagnostic));
+        }
+        for payload in read_array(obj, "payloads")? {
+            addons.push(AddonKind::Payload(payload.clone()));
+        }
+
+        Ok(addons)
+    }
+}
+
+/// Parses a time argument, which is either seconds since the Unix epoch (e.g. `1718000000`),
+/// or a duration before now with unit `s`, `m`, `h`, or `d` (e.g. `5m`).
+pub fn parse_time(arg: &str) -> Result<SystemTime, ViewError> {
+    let invalid = || ViewError::InvalidTime(arg.to_string());
+
+    if let Ok(secs) = arg.parse::<f64>() {
+        let since_epoch = Duration::try_from_secs_f64(secs).map_err(|_| invalid())?;
+        return Ok(UNIX_EPOCH + since_epoch);
+    }
+
+    let unit_secs = match arg.chars().last() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 60 * 60,
+        Some('d') => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let amount: u64 = arg[..arg.len() - 1].parse().map_err(|_| invalid())?;
+
+    SystemTime::now()
+        .checked_sub(Duration::from_secs(amount.saturating_mul(unit_secs)))
+        .ok_or_else(invalid)
+}
+
+/// Returns `true` if the given view id is the full id, the identifier, or the `#`-prefixed code of the given log-id.
+fn id_matches(id: &LogId, view_id: &str) -> bool {
+    match view_id.strip_prefix('#') {
+        Some(code) => id.get_code() == Some(code),
+        None => {
+            view_id == id.get_identifier()
+                || view_id
+                    .rsplit_once("::")
+                    .is_some_and(|(module_path, identifier)| {
+                        module_path == id.get_module_path() && identifier == id.get_identifier()
+                    })
+        }
+    }
+}
+
+fn get<'a>(obj: &'a Map<String, Value>, key: &'static str) -> Result<&'a Value, ViewError> {
+    obj.get(key).ok_or(ViewError::MissingField(key))
+}
+
+fn read_str<'a>(value: &'a Value, key: &'static str) -> Result<&'a str, ViewError> {
+    value
+        .as_str()
+        .ok_or(ViewError::InvalidValue(key, value.to_string()))
+}
+
+fn read_level(value: &Value) -> Result<LogLevel, ViewError> {
+    match read_str(value, "level")? {
+        "error" => Ok(LogLevel::Error),
+        "warn" => Ok(LogLevel::Warn),
+        "info" => Ok(LogLevel::Info),
+        "debug" => Ok(LogLevel::Debug),
+        "trace" => Ok(LogLevel::Trace),
+        level => Err(ViewError::InvalidValue("level", level.to_string())),
+    }
+}
+
+fn read_uuid(obj: &Map<String, Value>, key: &'static str) -> Result<Uuid, ViewError> {
+    let uuid = read_str(get(obj, key)?, key)?;
+    Uuid::parse_str(uuid).map_err(|_| ViewError::InvalidValue(key, uuid.to_string()))
+}
+
+fn read_array<'a>(
+    obj: &'a Map<String, Value>,
+    key: &'static str,
+) -> Result<&'a [Value], ViewError> {
+    match obj.get(key) {
+        Some(Value::Array(values)) => Ok(values),
+        Some(value) => Err(ViewError::InvalidValue(key, value.to_string())),
+        None => Ok(&[]),
+    }
+}
+
+fn read_strings(obj: &Map<String, Value>, key: &'static str) -> Result<Vec<String>, ViewError> {
+    read_array(obj, key)?
+        .iter()
+        .map(|value| read_str(value, key).map(str::to_string))
+        .collect()
+}
+
+fn field_value(value: &Value) -> FieldValue {
+    match value {
+        Value::Bool(v) => FieldValue::Bool(*v),
+        Value::Number(v) => match (v.as_i64(), v.as_u64(), v.as_f64()) {
+            (Some(v), _, _) => FieldValue::Int(v),
+            (_, Some(v), _) => FieldValue::UInt(v),
+            (_, _, Some(v)) => FieldValue::Float(v),
+            _ => FieldValue::Str(v.to_string()),
+        },
+        Value::String(v) => FieldValue::Str(v.clone()),
+        value => FieldValue::Str(value.to_string()),
+    }
+}
+
+/// Returns the interned string, because log-ids and origins require static strings.
+fn read_interned(s: &str) -> Result<&'static str, ViewError> {
+    intern(s).ok_or(ViewError::TooManyStrings)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewError {
+    InvalidFilter(String),
+    InvalidJson(String),
+    MissingField(&'static str),
+    InvalidValue(&'static str, String),
+    InvalidTime(String),
+    /// Interning a string of the event would exceed the limit of interned strings.
+    TooManyStrings,
+}
+
+impl std::error::Error for ViewError {}
+
+impl std::fmt::Display for ViewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewError::InvalidFilter(err) => write!(f, "Invalid filter: {}", err),
+            ViewError::InvalidJson(err) => write!(f, "Invalid JSON: {}", err),
+            ViewError::MissingField(key) => write!(f, "Missing field '{}'.", key),
+            ViewError::InvalidValue(key, value) => {
+                write!(f, "Invalid value for '{}': {}", key, value)
+            }
+            ViewError::InvalidTime(time) => write!(
+                f,
+                "Invalid time '{}'. Expected seconds since the Unix epoch, or a duration like '5m'.",
+                time
+            ),
+            ViewError::TooManyStrings => write!(
+                f,
+                "Strings of read events exceed the limit of {} bytes.",
+                MAX_INTERNED_BYTES
+            ),
+        }
+    }
+}
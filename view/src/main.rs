@@ -0,0 +1,118 @@
+//! Binary to view JSON Lines logs written by logid.
+//!
+//! **Usage:**
+//!
+//! ```text
+//! logid-view [--filter <filter>] [--id <id>]... [--since <time>] [--until <time>] [--follow] [--no-color] [<file>...]
+//! ```
+//!
+//! Events are read from the given files in order, or from stdin if no file or `-` is given.
+//! Times are either seconds since the Unix epoch, or a duration before now like `5m`, `2h`, or `1d`.
+
+use std::{
+    fs::File,
+    io::{BufReader, IsTerminal},
+    path::PathBuf,
+};
+
+use logid_view::{parse_time, ViewOptions, Viewer};
+
+const USAGE: &str = "Usage: logid-view [--filter <filter>] [--id <id>]... [--since <time>] [--until <time>] [--follow] [--no-color] [<file>...]";
+
+struct Args {
+    options: ViewOptions,
+    follow: bool,
+    files: Vec<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        options: ViewOptions::default(),
+        follow: false,
+        files: Vec::new(),
+    };
+    args.options.render.colored = std::io::stdout().is_terminal();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--filter" => {
+                args.options.filter = iter.next().ok_or("Missing value for `--filter`.")?;
+            }
+            "--id" => {
+                let id = iter.next().ok_or("Missing value for `--id`.")?;
+                args.options.ids.push(id);
+            }
+            "--since" => {
+                let since = iter.next().ok_or("Missing value for `--since`.")?;
+                args.options.since = Some(parse_time(&since).map_err(|err| err.to_string())?);
+            }
+            "--until" => {
+                let until = iter.next().ok_or("Missing value for `--until`.")?;
+                args.options.until = Some(parse_time(&until).map_err(|err| err.to_string())?);
+            }
+            "-f" | "--follow" => args.follow = true,
+            "--no-color" => args.options.render.colored = false,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            "-" => args.files.push(PathBuf::from(arg)),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'.", arg)),
+            _ => args.files.push(PathBuf::from(arg)),
+        }
+    }
+
+    if args.follow && args.files.len() > 1 {
+        return Err("Only one file may be followed.".to_string());
+    }
+
+    Ok(args)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let mut viewer = match Viewer::new(args.options) {
+        Ok(viewer) => viewer,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+    let stdout = std::io::stdout();
+
+    let files = if args.files.is_empty() {
+        vec![PathBuf::from("-")]
+    } else {
+        args.files
+    };
+
+    for file in files {
+        let name = file.display().to_string();
+        let on_invalid = |line_nr, err| eprintln!("Skipping {}:{}: {}", name, line_nr, err);
+
+        let res = if file.as_os_str() == "-" {
+            // Note: Stdin ends once the writing side is closed, so following it would never show new lines
+            viewer.view(std::io::stdin().lock(), stdout.lock(), false, on_invalid)
+        } else {
+            match File::open(&file) {
+                Ok(opened) => viewer.view(
+                    BufReader::new(opened),
+                    stdout.lock(),
+                    args.follow,
+                    on_invalid,
+                ),
+                Err(err) => Err(err),
+            }
+        };
+
+        if let Err(err) = res {
+            eprintln!("Could not read '{}': {}", name, err);
+            std::process::exit(1);
+        }
+    }
+}
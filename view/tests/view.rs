@@ -0,0 +1,256 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use logid::{
+    event_handler::{json::event_to_json, render::RenderOptions},
+    evident::event::{entry::EventEntry, Event},
+    field, lazy_info, log,
+    logging::filter::{set_filter, FilterConfig},
+    testing::capture_events,
+    ErrLogId,
+};
+use logid_view::{parse_time, ViewError, ViewOptions, Viewer};
+use thiserror::Error;
+
+#[derive(Debug, ErrLogId, Error)]
+enum DiskError {
+    #[error("Disk is full.")]
+    Full,
+}
+
+#[derive(Debug, ErrLogId, Error)]
+enum SaveError {
+    #[error("Could not save file.")]
+    Failed(#[source] DiskError),
+}
+
+fn render_options() -> RenderOptions {
+    RenderOptions {
+        placeholder_entry_ids: false,
+        ..RenderOptions::deterministic()
+    }
+}
+
+fn viewer(filter: &str) -> Viewer {
+    Viewer::new(ViewOptions {
+        filter: filter.to_string(),
+        render: render_options(),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn line(level: &str, id: &str, extra: &str) -> String {
+    format!(
+        r#"{{"level":"{level}","id":"{id}","entry":"{}","msg":"{id} happened.","origin":{{"module":"my_crate::io","file":"src/io.rs","line":7}}{extra}}}"#,
+        logid::evident::uuid::Uuid::new_v4()
+    )
+}
+
+fn view_all(viewer: &mut Viewer, input: &str) -> (String, Vec<(usize, ViewError)>) {
+    let mut output = Vec::new();
+    let mut invalid = Vec::new();
+    viewer
+        .view(input.as_bytes(), &mut output, false, |line_nr, err| {
+            invalid.push((line_nr, err))
+        })
+        .unwrap();
+    (String::from_utf8(output).unwrap(), invalid)
+}
+
+#[test]
+fn json_logs_render_like_captured_events() {
+    set_filter(FilterConfig::new("trace(all)")).unwrap();
+    let cap = capture_events();
+    log!(DiskError::Full, add: field!("free_bytes", 0u64));
+    log!(SaveError::Failed(DiskError::Full), add: lazy_info!("Path: {}", "/tmp/data"));
+
+    let entries = cap.entries();
+    let input: String = entries
+        .iter()
+        .map(|entry| format!("{}\n", event_to_json(&Event::new(entry.clone()))))
+        .collect();
+    let (disk_entry, save_entry) = (entries[0].get_entry_id(), entries[1].get_entry_id());
+
+    let (rendered, invalid) = view_all(&mut viewer("trace(all)"), &input);
+
+    assert!(invalid.is_empty(), "Invalid lines: {:?}", invalid);
+    assert_eq!(
+        rendered,
+        format!(
+            r#"ERR   Disk is full.
+├───> Event: id='view::DiskError::Full', entry='{disk_entry}'
+├───> Origin: module="view", file="<file>", line=<line>
+╰───> Fields: free_bytes=0
+    ERR   Could not save file.
+    ├───> Event: id='view::SaveError::Failed(_)', entry='{save_entry}'
+    ├───> Origin: module="view", file="<file>", line=<line>
+    ├───> Caused by: lvl='ERR', id='view::DiskError::Full', entry='{disk_entry}'
+    ╰───> Info: Path: /tmp/data
+"#
+        )
+    );
+}
+
+#[test]
+fn filter_grammar_selects_events_and_addons() {
+    let input = [
+        line("info", "my_crate::io::Opened", ""),
+        line(
+            "warn",
+            "my_crate::io::Slow",
+            r#","infos":["Took 3s."],"debugs":["Retry 2."]"#,
+        ),
+    ]
+    .join("\n");
+
+    let (rendered, _) = view_all(&mut viewer("warn(infos)"), &input);
+
+    assert_eq!(
+        rendered,
+        "WARN  my_crate::io::Slow happened.\n╰───> Info: Took 3s.\n"
+    );
+}
+
+#[test]
+fn ids_select_events() {
+    let input = [
+        line("error", "my_crate::io::Closed", r#","code":"E0007""#),
+        line("error", "my_crate::io::Broken", ""),
+        line("error", "my_crate::net::Broken", ""),
+    ]
+    .join("\n");
+
+    let mut by_code = Viewer::new(ViewOptions {
+        ids: vec!["#E0007".to_string()],
+        render: render_options(),
+        ..Default::default()
+    })
+    .unwrap();
+    let (rendered, _) = view_all(&mut by_code, &input);
+    assert_eq!(rendered.matches("ERR").count(), 1);
+    assert!(rendered.contains("my_crate::io::Closed happened."));
+
+    let mut by_identifier = Viewer::new(ViewOptions {
+        ids: vec!["Broken".to_string()],
+        render: render_options(),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        view_all(&mut by_identifier, &input)
+            .0
+            .matches("ERR")
+            .count(),
+        2
+    );
+
+    let mut by_full_id = Viewer::new(ViewOptions {
+        ids: vec!["my_crate::net::Broken".to_string()],
+        render: render_options(),
+        ..Default::default()
+    })
+    .unwrap();
+    let (rendered, _) = view_all(&mut by_full_id, &input);
+    assert_eq!(rendered.matches("ERR").count(), 1);
+    assert!(rendered.contains("my_crate::net::Broken happened."));
+}
+
+#[test]
+fn invalid_filter_is_rejected() {
+    let res = Viewer::new(ViewOptions {
+        filter: "error{msg ~ }".to_string(),
+        ..Default::default()
+    });
+
+    assert!(
+        matches!(res, Err(ViewError::InvalidFilter(_))),
+        "Invalid filter was accepted."
+    );
+}
+
+#[test]
+fn time_window_selects_events() {
+    let input = [
+        line("error", "my_crate::io::Early", r#","timestamp":100.5"#),
+        line("error", "my_crate::io::Late", r#","timestamp":200.0"#),
+        line("error", "my_crate::io::Unknown", ""),
+    ]
+    .join("\n");
+
+    let mut viewer = Viewer::new(ViewOptions {
+        filter: "trace".to_string(),
+        since: Some(parse_time("150").unwrap()),
+        until: Some(parse_time("250").unwrap()),
+        render: render_options(),
+        ..Default::default()
+    })
+    .unwrap();
+    let (rendered, _) = view_all(&mut viewer, &input);
+
+    assert_eq!(rendered, "ERR   my_crate::io::Late happened.\n");
+}
+
+#[test]
+fn invalid_lines_are_reported() {
+    let input = format!(
+        "{}\n\nnot json\n{}",
+        line("error", "my_crate::io::First", ""),
+        r#"{"level":"error","id":"my_crate::io::NoEntry"}"#
+    );
+
+    let (rendered, invalid) = view_all(&mut viewer("trace"), &input);
+
+    assert_eq!(rendered, "ERR   my_crate::io::First happened.\n");
+    assert_eq!(invalid.len(), 2);
+    assert!(matches!(invalid[0], (3, ViewError::InvalidJson(_))));
+    assert_eq!(invalid[1], (4, ViewError::MissingField("entry")));
+}
+
+#[test]
+fn relative_and_absolute_times() {
+    assert_eq!(
+        parse_time("1718000000").unwrap(),
+        UNIX_EPOCH + Duration::from_secs(1_718_000_000)
+    );
+
+    let five_minutes_ago = parse_time("5m").unwrap();
+    let elapsed = SystemTime::now().duration_since(five_minutes_ago).unwrap();
+    assert!(elapsed >= Duration::from_secs(300) && elapsed < Duration::from_secs(310));
+
+    assert_eq!(
+        parse_time("5 minutes"),
+        Err(ViewError::InvalidTime("5 minutes".to_string()))
+    );
+}
+
+#[test]
+fn causes_rendered_long_before_start_a_new_chain() {
+    let cause = logid::evident::uuid::Uuid::new_v4();
+    let cause_line = format!(
+        r#"{{"level":"error","id":"my_crate::DiskFull","entry":"{cause}","msg":"Disk is full.","origin":{{"module":"my_crate::io","file":"src/io.rs","line":7}}}}"#
+    );
+    let effect_line = line(
+        "error",
+        "my_crate::SaveFailed",
+        &format!(r#","parent":{{"id":"my_crate::DiskFull","level":"error","entry":"{cause}"}}"#),
+    );
+    let mut viewer = viewer("error(all)");
+
+    viewer.view_line(&cause_line).unwrap().unwrap();
+    let recent_effect = viewer.view_line(&effect_line).unwrap().unwrap();
+    assert!(
+        recent_effect.starts_with("    ERR"),
+        "Effect of a recent cause was not indented."
+    );
+
+    for _ in 0..1000 {
+        viewer
+            .view_line(&line("error", "my_crate::Unrelated", ""))
+            .unwrap();
+    }
+    let late_effect = viewer.view_line(&effect_line).unwrap().unwrap();
+    assert!(
+        late_effect.starts_with("ERR"),
+        "Depth of a cause rendered long before was still remembered."
+    );
+}